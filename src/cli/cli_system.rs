@@ -5,7 +5,7 @@
 
 use crate::{
     example::{Controller10, Controller13},
-    openflow::{ofp10::ControllerFrame10, ofp13::ControllerFrame13},
+    openflow::{multi_version_listener, ofp10::ControllerFrame10, ofp13::ControllerFrame13},
 };
 use clap::{command, CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
@@ -60,6 +60,8 @@ pub enum Controllers {
     Ctrl13,
     /// Openflow 1.0 with Controller10
     Ctrl10,
+    /// Openflow 1.0 and 1.3 on the same port, negotiated per switch
+    Auto,
 }
 
 /// Runs a controller instance on the specified address
@@ -72,6 +74,14 @@ async fn run_controller(addr: SocketAddr, controller: Option<Controllers>) -> Re
     match controller {
         Controllers::Ctrl13 => Ok(Controller13::new().listener(&addr.to_string()).await),
         Controllers::Ctrl10 => Ok(Controller10::new().listener(&addr.to_string()).await),
+        Controllers::Auto => {
+            println!("server run at {}", addr);
+            let address = addr.to_string();
+            Ok(
+                multi_version_listener(&address, &Controller10::new(), &Controller13::new())
+                    .await?,
+            )
+        }
     }
 }
 
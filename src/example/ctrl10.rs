@@ -9,13 +9,12 @@ use crate::{
     openflow::ofp10::{
         self,
         events::{flow_mod::MatchFields, Action},
-        ControllerFrame10, FlowModEvent, OfpMsgEvent, PacketInEvent,
+        ControllerFrame10, FlowModEvent, OfpMsgEvent, PacketInEvent, SwitchHandle,
     },
     utils::{net::GetMacAddr, MacAddr},
 };
 use etherparse::{EtherType, Ethernet2Header};
 use std::collections::HashMap;
-use tokio::net::TcpStream;
 
 /// OpenFlow 1.0 Controller implementation
 ///
@@ -48,7 +47,7 @@ impl ControllerFrame10 for Controller10 {
         &mut self,
         xid: u32,
         packetin: PacketInEvent,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) {
         // Parse the ethernet packet
         let pkt = match packetin.ether_parse() {
@@ -108,14 +107,14 @@ impl ControllerFrame10 for Controller10 {
             // Use buffer ID if available to avoid packet duplication
             if let Some(buf_id) = packetin.buf_id {
                 let _ = self
-                    .add_flow(xid, 1, match_fields, &actions, Some(buf_id), stream)
+                    .add_flow(xid, 1, match_fields, &actions, Some(buf_id), switch)
                     .await;
                 return;
             }
 
             // Add flow rule without buffer ID
             let _ = self
-                .add_flow(xid, 1, match_fields, &actions, None, stream)
+                .add_flow(xid, 1, match_fields, &actions, None, switch)
                 .await;
         }
 
@@ -123,7 +122,7 @@ impl ControllerFrame10 for Controller10 {
         let packet_out = self
             .ofp()
             .packet_out(Some(packetin.in_port), packetin.payload, actions);
-        let _ = self.send_msg(packet_out, xid, stream).await;
+        let _ = self.send_msg(packet_out, xid, switch).await;
     }
 }
 
@@ -136,7 +135,7 @@ impl Controller10 {
     /// * `flow` - Match fields for the flow
     /// * `actions` - Actions to perform on matching packets
     /// * `buffer_id` - Optional buffer ID for packet buffering
-    /// * `stream` - TCP stream to the switch
+    /// * `switch` - Handle of the switch
     async fn add_flow(
         &self,
         xid: u32,
@@ -144,13 +143,13 @@ impl Controller10 {
         flow: MatchFields,
         actions: &[Action],
         buffer_id: Option<u32>,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) {
         let _ = self
             .send_msg(
                 FlowModEvent::add_flow(priority, flow, actions.to_vec(), buffer_id),
                 xid,
-                stream,
+                switch,
             )
             .await;
     }
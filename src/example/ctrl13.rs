@@ -9,13 +9,12 @@ use crate::{
     openflow::ofp13::{
         self,
         events::{flow_mod::MatchFields, Action},
-        ControllerFrame13, FlowModEvent, OfpMsgEvent, PacketInEvent,
+        ControllerFrame13, FlowModEvent, OfpMsgEvent, PacketInEvent, SwitchHandle,
     },
     utils::{net::GetMacAddr, MacAddr},
 };
 use etherparse::{EtherType, Ethernet2Header};
 use std::collections::HashMap;
-use tokio::net::TcpStream;
 
 /// OpenFlow 1.3 Controller implementation
 ///
@@ -42,12 +41,12 @@ impl ControllerFrame13 for Controller13 {
         &self,
         xid: u32,
         features_reply: ofp13::FeaturesReplyEvent,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) {
         let matchs = MatchFields::match_all();
-        let actions = vec![Action::Oputput(ofp13::PseudoPort::Controller(!0))];
+        let actions = vec![Action::output(ofp13::PseudoPort::Controller(!0))];
         let _ = self
-            .add_flow(xid, 0, matchs, &actions, 0, None, stream)
+            .add_flow(xid, 0, matchs, &actions, 0, None, switch)
             .await;
     }
 
@@ -63,7 +62,7 @@ impl ControllerFrame13 for Controller13 {
         &mut self,
         xid: u32,
         packetin: PacketInEvent,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) {
         let pkt = match packetin.ether_parse() {
             Ok(pkt) => pkt,
@@ -118,7 +117,7 @@ impl ControllerFrame13 for Controller13 {
             None => ofp13::PseudoPort::Flood,
         };
 
-        let actions = vec![Action::Oputput(out_port.clone())];
+        let actions = vec![Action::output(out_port.clone())];
 
         // If we know the destination port, set up a flow rule
         if let ofp13::PseudoPort::PhysicalPort(_) = out_port {
@@ -137,7 +136,7 @@ impl ControllerFrame13 for Controller13 {
                         &actions,
                         packetin.table_id,
                         Some(buf_id),
-                        stream,
+                        switch,
                     )
                     .await;
                 return;
@@ -152,7 +151,7 @@ impl ControllerFrame13 for Controller13 {
                     &actions,
                     packetin.table_id,
                     None,
-                    stream,
+                    switch,
                 )
                 .await;
         }
@@ -161,7 +160,7 @@ impl ControllerFrame13 for Controller13 {
         let packet_out = self
             .ofp()
             .packet_out(Some(in_port), packetin.payload, actions);
-        let _ = self.send_msg(packet_out, xid, stream).await;
+        let _ = self.send_msg(packet_out, xid, switch).await;
     }
 }
 
@@ -175,7 +174,7 @@ impl Controller13 {
     /// * `actions` - Actions to perform on matching packets
     /// * `table_id` - Table to add the flow to
     /// * `buffer_id` - Optional buffer ID for packet buffering
-    /// * `stream` - TCP stream to the switch
+    /// * `switch` - Handle of the switch
     async fn add_flow(
         &self,
         xid: u32,
@@ -184,13 +183,13 @@ impl Controller13 {
         actions: &[Action],
         table_id: u8,
         buffer_id: Option<u32>,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) {
         let _ = self
             .send_msg(
                 FlowModEvent::add_flow(priority, flow, actions.to_vec(), table_id, buffer_id),
                 xid,
-                stream,
+                switch,
             )
             .await;
     }
@@ -0,0 +1,274 @@
+//! OpenFlow Experimenter Extensions
+//!
+//! Experimenter (OpenFlow 1.3) and vendor (OpenFlow 1.0) extensions are identified by
+//! a 32 bit experimenter id, usually the IEEE OUI of the organization defining them.
+//! The protocol stacks keep every extension as raw bytes: experimenter messages,
+//! actions, instructions and OXM fields of foreign classes are parsed and marshaled
+//! unchanged, so nothing a switch sends is dropped.
+//!
+//! Typed access is added by registering an `ExperimenterCodec` for an experimenter id
+//! in an `ExperimenterRegistry`. The registry decodes the raw bodies into the values of
+//! the codec and encodes such values back into raw bodies:
+//!
+//! | Extension   | Raw form (`ofp13`)      | Raw form (`ofp10`) |
+//! |-------------|-------------------------|--------------------|
+//! | Message     | `ExperimenterEvent`     | `VendorEvent`      |
+//! | Action      | `ExperimenterAction`    | `Action::Vendor`   |
+//! | Instruction | `InstructExperimenter`  | -                  |
+//! | OXM field   | `OxmExtension`          | -                  |
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    io::{Error, ErrorKind},
+    sync::Arc,
+};
+
+use super::ofp13::OxmExtension;
+
+/// Value decoded by an `ExperimenterCodec`, downcast it to the type of the codec
+pub type ExperimenterValue = Box<dyn Any + Send + Sync>;
+
+/// Encoder and decoder of the extensions defined by one experimenter
+///
+/// Every method has a default that rejects the extension, so a codec only implements
+/// the kinds of extensions it defines. Bodies passed to the decoders start right after
+/// the experimenter id; action and instruction bodies still carry their padding.
+pub trait ExperimenterCodec: Send + Sync {
+    /// Returns the experimenter id of the extensions handled by this codec
+    fn experimenter(&self) -> u32;
+
+    /// Returns the OXM classes owned by this codec besides the experimenter class
+    ///
+    /// Used for vendor classes that predate the experimenter class, e.g. the NXM
+    /// classes `0x0000` and `0x0001` of Open vSwitch.
+    fn oxm_classes(&self) -> Vec<u16> {
+        Vec::new()
+    }
+
+    /// Decodes the body of an experimenter message
+    ///
+    /// # Arguments
+    /// * `exp_type` - Experimenter defined message type
+    /// * `data` - Body of the message following the message type
+    #[allow(unused)]
+    fn decode_message(&self, exp_type: u32, data: &[u8]) -> Result<ExperimenterValue, Error> {
+        Err(unsupported(self.experimenter(), "message"))
+    }
+
+    /// Encodes a value into the type and body of an experimenter message
+    #[allow(unused)]
+    fn encode_message(&self, value: &dyn Any) -> Result<(u32, Vec<u8>), Error> {
+        Err(unsupported(self.experimenter(), "message"))
+    }
+
+    /// Decodes the body of an experimenter action
+    #[allow(unused)]
+    fn decode_action(&self, data: &[u8]) -> Result<ExperimenterValue, Error> {
+        Err(unsupported(self.experimenter(), "action"))
+    }
+
+    /// Encodes a value into the body of an experimenter action, padding excluded
+    #[allow(unused)]
+    fn encode_action(&self, value: &dyn Any) -> Result<Vec<u8>, Error> {
+        Err(unsupported(self.experimenter(), "action"))
+    }
+
+    /// Decodes the body of an experimenter instruction
+    #[allow(unused)]
+    fn decode_instruction(&self, data: &[u8]) -> Result<ExperimenterValue, Error> {
+        Err(unsupported(self.experimenter(), "instruction"))
+    }
+
+    /// Encodes a value into the body of an experimenter instruction, padding excluded
+    #[allow(unused)]
+    fn encode_instruction(&self, value: &dyn Any) -> Result<Vec<u8>, Error> {
+        Err(unsupported(self.experimenter(), "instruction"))
+    }
+
+    /// Decodes an OXM field of the experimenter class or of a class of `oxm_classes`
+    #[allow(unused)]
+    fn decode_oxm(&self, oxm: &OxmExtension) -> Result<ExperimenterValue, Error> {
+        Err(unsupported(self.experimenter(), "oxm field"))
+    }
+
+    /// Encodes a value into an OXM field
+    #[allow(unused)]
+    fn encode_oxm(&self, value: &dyn Any) -> Result<OxmExtension, Error> {
+        Err(unsupported(self.experimenter(), "oxm field"))
+    }
+}
+
+/// Codecs of the experimenter extensions known to a controller
+///
+/// Decoding an extension without a registered codec yields `Ok(None)`, the raw form
+/// stays usable. Encoding requires a codec.
+#[derive(Clone, Default)]
+pub struct ExperimenterRegistry {
+    codecs: HashMap<u32, Arc<dyn ExperimenterCodec>>,
+    oxm_classes: HashMap<u16, Arc<dyn ExperimenterCodec>>,
+}
+
+impl ExperimenterRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a codec, replacing the codec previously registered for its id
+    ///
+    /// # Arguments
+    /// * `codec` - The codec to register
+    pub fn register(&mut self, codec: impl ExperimenterCodec + 'static) {
+        let codec: Arc<dyn ExperimenterCodec> = Arc::new(codec);
+        for class in codec.oxm_classes() {
+            self.oxm_classes.insert(class, codec.clone());
+        }
+        self.codecs.insert(codec.experimenter(), codec);
+    }
+
+    /// Returns the codec registered for an experimenter id
+    pub fn codec(&self, experimenter: u32) -> Option<&Arc<dyn ExperimenterCodec>> {
+        self.codecs.get(&experimenter)
+    }
+
+    /// Decodes the body of an experimenter message
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id of the message
+    /// * `exp_type` - Experimenter defined message type
+    /// * `data` - Body of the message following the message type
+    ///
+    /// # Returns
+    /// The decoded value, `None` if no codec is registered for the experimenter
+    pub fn decode_message(
+        &self,
+        experimenter: u32,
+        exp_type: u32,
+        data: &[u8],
+    ) -> Result<Option<ExperimenterValue>, Error> {
+        self.codec(experimenter)
+            .map(|codec| codec.decode_message(exp_type, data))
+            .transpose()
+    }
+
+    /// Encodes a value into the type and body of an experimenter message
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id of the codec to use
+    /// * `value` - The value to encode
+    pub fn encode_message(
+        &self,
+        experimenter: u32,
+        value: &dyn Any,
+    ) -> Result<(u32, Vec<u8>), Error> {
+        self.require(experimenter)?.encode_message(value)
+    }
+
+    /// Decodes the body of an experimenter action
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id of the action
+    /// * `data` - Body of the action following the experimenter id
+    ///
+    /// # Returns
+    /// The decoded value, `None` if no codec is registered for the experimenter
+    pub fn decode_action(
+        &self,
+        experimenter: u32,
+        data: &[u8],
+    ) -> Result<Option<ExperimenterValue>, Error> {
+        self.codec(experimenter)
+            .map(|codec| codec.decode_action(data))
+            .transpose()
+    }
+
+    /// Encodes a value into the body of an experimenter action
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id of the codec to use
+    /// * `value` - The value to encode
+    pub fn encode_action(&self, experimenter: u32, value: &dyn Any) -> Result<Vec<u8>, Error> {
+        self.require(experimenter)?.encode_action(value)
+    }
+
+    /// Decodes the body of an experimenter instruction
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id of the instruction
+    /// * `data` - Body of the instruction following the experimenter id
+    ///
+    /// # Returns
+    /// The decoded value, `None` if no codec is registered for the experimenter
+    pub fn decode_instruction(
+        &self,
+        experimenter: u32,
+        data: &[u8],
+    ) -> Result<Option<ExperimenterValue>, Error> {
+        self.codec(experimenter)
+            .map(|codec| codec.decode_instruction(data))
+            .transpose()
+    }
+
+    /// Encodes a value into the body of an experimenter instruction
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id of the codec to use
+    /// * `value` - The value to encode
+    pub fn encode_instruction(&self, experimenter: u32, value: &dyn Any) -> Result<Vec<u8>, Error> {
+        self.require(experimenter)?.encode_instruction(value)
+    }
+
+    /// Decodes an OXM field of a class other than OpenFlow basic
+    ///
+    /// Fields of the experimenter class are looked up by their experimenter id, fields
+    /// of other classes by the classes the codecs claim through `oxm_classes`.
+    ///
+    /// # Returns
+    /// The decoded value, `None` if no codec handles the field
+    pub fn decode_oxm(&self, oxm: &OxmExtension) -> Result<Option<ExperimenterValue>, Error> {
+        let codec = match oxm.experimenter() {
+            Some(experimenter) => self.codec(experimenter),
+            None => self.oxm_classes.get(&oxm.class),
+        };
+        codec.map(|codec| codec.decode_oxm(oxm)).transpose()
+    }
+
+    /// Encodes a value into an OXM field
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id of the codec to use
+    /// * `value` - The value to encode
+    pub fn encode_oxm(&self, experimenter: u32, value: &dyn Any) -> Result<OxmExtension, Error> {
+        self.require(experimenter)?.encode_oxm(value)
+    }
+
+    /// Returns the codec of an experimenter id or an error if there is none
+    fn require(&self, experimenter: u32) -> Result<&Arc<dyn ExperimenterCodec>, Error> {
+        self.codec(experimenter).ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("no codec registered for experimenter {:#x}", experimenter),
+            )
+        })
+    }
+}
+
+impl fmt::Debug for ExperimenterRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut experimenters: Vec<&u32> = self.codecs.keys().collect();
+        experimenters.sort();
+        f.debug_struct("ExperimenterRegistry")
+            .field("experimenters", &experimenters)
+            .finish()
+    }
+}
+
+/// Builds the error of a codec that does not define an extension kind
+fn unsupported(experimenter: u32, kind: &str) -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        format!("experimenter {:#x} defines no {}", experimenter, kind),
+    )
+}
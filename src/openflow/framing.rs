@@ -0,0 +1,121 @@
+//! OpenFlow Message Framing
+//!
+//! This module implements the length-aware framing layer shared by the OpenFlow 1.0
+//! and 1.3 stacks. TCP is a byte stream, so a single read may return part of a
+//! message, exactly one message or several messages back to back. The framer
+//! accumulates bytes and only yields a message once the `length` field of its
+//! OpenFlow header is satisfied.
+//!
+//! The 16 bit `length` field already bounds a message to 64 KiB, so the only length
+//! the framer has to reject is one shorter than the header itself.
+//!
+//! Every OpenFlow version shares the same 8 byte header layout:
+//!
+//! | Version (1 byte) | Type (1 byte) | Length (2 bytes) | Xid (4 bytes) |
+//! |------------------|---------------|------------------|---------------|
+
+use std::io::{Error, ErrorKind};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Size of the OpenFlow header in bytes, identical for every protocol version
+pub const OFP_HEADER_SIZE: usize = 8;
+
+/// Size of the chunk requested from the socket on each read
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// Accumulates bytes from a stream and splits them into complete OpenFlow messages
+///
+/// Each yielded message contains the full header followed by its body, so it can be
+/// handed directly to the header parser of either protocol version.
+pub struct MessageFramer {
+    /// Bytes received but not yet returned as a complete message
+    buffer: Vec<u8>,
+}
+
+impl MessageFramer {
+    /// Creates a new, empty framer
+    pub fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    /// Returns the number of buffered bytes that do not yet form a complete message
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Appends raw bytes received from the peer
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes read from the stream
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Takes the next complete message out of the buffer
+    ///
+    /// # Returns
+    /// * `Ok(Some(message))` - A complete message including its header
+    /// * `Ok(None)` - More bytes are needed before a message is complete
+    /// * `Err(_)` - The header announces a length shorter than the header
+    pub fn next_message(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        if self.buffer.len() < OFP_HEADER_SIZE {
+            return Ok(None);
+        }
+        let length = u16::from_be_bytes([self.buffer[2], self.buffer[3]]) as usize;
+        if length < OFP_HEADER_SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "invalid OpenFlow message length {} (expected at least {})",
+                    length, OFP_HEADER_SIZE
+                ),
+            ));
+        }
+        if self.buffer.len() < length {
+            return Ok(None);
+        }
+        let rest = self.buffer.split_off(length);
+        Ok(Some(std::mem::replace(&mut self.buffer, rest)))
+    }
+
+    /// Reads from the stream until a complete message is available
+    ///
+    /// Messages already buffered by a previous read are returned without touching the stream.
+    ///
+    /// # Arguments
+    /// * `reader` - The stream to read from
+    ///
+    /// # Returns
+    /// * `Ok(Some(message))` - A complete message including its header
+    /// * `Ok(None)` - The peer closed the connection
+    /// * `Err(_)` - A read error occurred or the peer sent a length shorter than the header
+    pub async fn read_message<R: AsyncRead + Unpin>(
+        &mut self,
+        reader: &mut R,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        loop {
+            if let Some(message) = self.next_message()? {
+                return Ok(Some(message));
+            }
+            let n = reader.read(&mut chunk).await?;
+            if n == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "connection closed in the middle of an OpenFlow message",
+                ));
+            }
+            self.push(&chunk[..n]);
+        }
+    }
+}
+
+impl Default for MessageFramer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,292 @@
+//! OpenFlow Version Negotiation
+//!
+//! This module implements the Hello exchange that opens every OpenFlow connection.
+//! Both sides send a Hello as soon as the connection is up. Since OpenFlow 1.3.1 the
+//! Hello may carry a list of elements; the `OFPHET_VERSIONBITMAP` element lists every
+//! version the sender supports:
+//!
+//! | Type (2 bytes) | Length (2 bytes) | Bitmaps (4 bytes each) | Padding to 8 bytes |
+//! |----------------|------------------|------------------------|--------------------|
+//!
+//! When both sides send a bitmap the highest version set in both is used. Otherwise the
+//! smaller of the two header versions is used if it is supported. When no version is
+//! left the connection is refused with an `OFPHFC_INCOMPATIBLE` error.
+
+use std::io::{Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use super::{framing::OFP_HEADER_SIZE, MessageFramer};
+
+/// Message type of Hello, identical for every protocol version
+pub const OFPT_HELLO: u8 = 0;
+
+/// Message type of Error, identical for every protocol version
+pub const OFPT_ERROR: u8 = 1;
+
+/// Hello element listing the supported versions (`OFPHET_VERSIONBITMAP`)
+const OFPHET_VERSIONBITMAP: u16 = 1;
+
+/// Error type of a failed Hello exchange (`OFPET_HELLO_FAILED`)
+const OFPET_HELLO_FAILED: u16 = 0;
+
+/// Error code for peers without a common version (`OFPHFC_INCOMPATIBLE`)
+const OFPHFC_INCOMPATIBLE: u16 = 0;
+
+/// Element carried in the body of a Hello message
+#[derive(Clone, Debug, PartialEq)]
+pub enum HelloElement {
+    /// Every protocol version supported by the sender, in ascending order
+    VersionBitmap(Vec<u8>),
+    /// Element type this implementation does not know
+    Unknown {
+        /// Type of the element
+        elem_type: u16,
+        /// Body of the element, padding excluded
+        data: Vec<u8>,
+    },
+}
+
+impl HelloElement {
+    /// Parses every element of a Hello body
+    ///
+    /// # Arguments
+    /// * `body` - The Hello body, header excluded
+    pub fn parse_all(body: &[u8]) -> Result<Vec<Self>, Error> {
+        let mut elements = Vec::new();
+        let mut rest = body;
+        while rest.len() >= 4 {
+            let elem_type = (&rest[0..2]).read_u16::<BigEndian>()?;
+            let length = (&rest[2..4]).read_u16::<BigEndian>()? as usize;
+            if length < 4 || length > rest.len() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid hello element length {}", length),
+                ));
+            }
+            let data = &rest[4..length];
+            elements.push(match elem_type {
+                OFPHET_VERSIONBITMAP => Self::VersionBitmap(bitmap_versions(data)?),
+                _ => Self::Unknown {
+                    elem_type,
+                    data: data.to_vec(),
+                },
+            });
+            // elements are padded to a multiple of 8 bytes
+            let padded = length.div_ceil(8) * 8;
+            rest = &rest[padded.min(rest.len())..];
+        }
+        Ok(elements)
+    }
+
+    /// Marshals the element, padding included
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the element to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) {
+        let (elem_type, data) = match self {
+            Self::VersionBitmap(versions) => (OFPHET_VERSIONBITMAP, versions_bitmap(versions)),
+            Self::Unknown { elem_type, data } => (*elem_type, data.clone()),
+        };
+        let length = 4 + data.len();
+        let _ = bytes.write_u16::<BigEndian>(elem_type);
+        let _ = bytes.write_u16::<BigEndian>(length as u16);
+        bytes.extend_from_slice(&data);
+        bytes.resize(bytes.len() + (8 - length % 8) % 8, 0);
+    }
+}
+
+/// Hello message of any protocol version
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hello {
+    /// Highest protocol version supported by the sender
+    pub version: u8,
+    /// Transaction id of the message
+    pub xid: u32,
+    /// Elements of the message body
+    pub elements: Vec<HelloElement>,
+}
+
+impl Hello {
+    /// Creates a Hello advertising the given versions
+    ///
+    /// A version bitmap is only added when it tells the peer more than the header does,
+    /// so a plain OpenFlow 1.0 Hello stays empty.
+    ///
+    /// # Arguments
+    /// * `versions` - Supported protocol versions, e.g. `[0x01, 0x04]`
+    pub fn new(versions: &[u8]) -> Self {
+        let mut versions = versions.to_vec();
+        versions.sort_unstable();
+        versions.dedup();
+        let version = versions.last().copied().unwrap_or_default();
+        let elements = if version >= 0x04 || versions.len() > 1 {
+            vec![HelloElement::VersionBitmap(versions)]
+        } else {
+            Vec::new()
+        };
+        Self {
+            version,
+            xid: 0,
+            elements,
+        }
+    }
+
+    /// Parses a Hello message
+    ///
+    /// # Arguments
+    /// * `message` - The whole message, header included
+    pub fn parse(message: &[u8]) -> Result<Self, Error> {
+        if message.len() < OFP_HEADER_SIZE || message[1] != OFPT_HELLO {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "expected a hello message",
+            ));
+        }
+        let length = (&message[2..4]).read_u16::<BigEndian>()? as usize;
+        let body = &message[OFP_HEADER_SIZE..length.clamp(OFP_HEADER_SIZE, message.len())];
+        Ok(Self {
+            version: message[0],
+            xid: (&message[4..8]).read_u32::<BigEndian>()?,
+            elements: HelloElement::parse_all(body)?,
+        })
+    }
+
+    /// Marshals the message, header included
+    pub fn marshal(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        for element in &self.elements {
+            element.marshal(&mut body);
+        }
+        let mut bytes = header(self.version, OFPT_HELLO, body.len(), self.xid);
+        bytes.extend_from_slice(&body);
+        bytes
+    }
+
+    /// Returns the versions of the version bitmap element, if the peer sent one
+    pub fn version_bitmap(&self) -> Option<&[u8]> {
+        self.elements.iter().find_map(|element| match element {
+            HelloElement::VersionBitmap(versions) => Some(versions.as_slice()),
+            _ => None,
+        })
+    }
+
+    /// Picks the protocol version to use with the sender of this Hello
+    ///
+    /// # Arguments
+    /// * `supported` - Protocol versions supported by this side
+    ///
+    /// # Returns
+    /// The highest common version, `None` if there is none
+    pub fn negotiate(&self, supported: &[u8]) -> Option<u8> {
+        match self.version_bitmap() {
+            Some(versions) => supported
+                .iter()
+                .filter(|version| versions.contains(version))
+                .max()
+                .copied(),
+            None => {
+                let version = self.version.min(supported.iter().max().copied()?);
+                supported.contains(&version).then_some(version)
+            }
+        }
+    }
+}
+
+/// Builds the `OFPHFC_INCOMPATIBLE` error sent to a peer without a common version
+///
+/// # Arguments
+/// * `version` - Protocol version of the error header
+/// * `xid` - Transaction id of the peer's Hello
+/// * `supported` - Protocol versions supported by this side
+pub fn incompatible_error(version: u8, xid: u32, supported: &[u8]) -> Vec<u8> {
+    let reason = format!("supported versions: {:x?}", supported);
+    let mut bytes = header(version, OFPT_ERROR, 4 + reason.len(), xid);
+    let _ = bytes.write_u16::<BigEndian>(OFPET_HELLO_FAILED);
+    let _ = bytes.write_u16::<BigEndian>(OFPHFC_INCOMPATIBLE);
+    bytes.extend_from_slice(reason.as_bytes());
+    bytes
+}
+
+/// Exchanges Hello messages and picks the protocol version of the connection
+///
+/// Sends a Hello advertising `supported`, waits for the Hello of the peer and replies
+/// with an `OFPHFC_INCOMPATIBLE` error when there is no common version.
+///
+/// # Arguments
+/// * `reader` - The read half of the connection
+/// * `writer` - The write half of the connection
+/// * `framer` - Framer of the connection, kept for the messages that follow
+/// * `supported` - Protocol versions supported by this side
+///
+/// # Returns
+/// The negotiated version and the transaction id of the peer's Hello
+pub async fn handshake<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    framer: &mut MessageFramer,
+    supported: &[u8],
+) -> Result<(u8, u32), Error>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    tokio::io::AsyncWriteExt::write_all(writer, &Hello::new(supported).marshal()).await?;
+    let message = framer.read_message(reader).await?.ok_or_else(|| {
+        Error::new(
+            ErrorKind::UnexpectedEof,
+            "connection closed before the hello exchange",
+        )
+    })?;
+    let hello = Hello::parse(&message)?;
+    match hello.negotiate(supported) {
+        Some(version) => Ok((version, hello.xid)),
+        None => {
+            let version = hello.version.min(Hello::new(supported).version);
+            let error = incompatible_error(version, hello.xid, supported);
+            tokio::io::AsyncWriteExt::write_all(writer, &error).await?;
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                format!("no common version with peer version {:#x}", hello.version),
+            ))
+        }
+    }
+}
+
+/// Builds an OpenFlow header
+fn header(version: u8, message: u8, body_len: usize, xid: u32) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(OFP_HEADER_SIZE + body_len);
+    let _ = bytes.write_u8(version);
+    let _ = bytes.write_u8(message);
+    let _ = bytes.write_u16::<BigEndian>((OFP_HEADER_SIZE + body_len) as u16);
+    let _ = bytes.write_u32::<BigEndian>(xid);
+    bytes
+}
+
+/// Decodes the versions set in a version bitmap
+fn bitmap_versions(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut versions = Vec::new();
+    for (word, mut chunk) in data.chunks_exact(4).enumerate() {
+        let bits = chunk.read_u32::<BigEndian>()?;
+        for bit in 0..32 {
+            if bits & (1 << bit) != 0 {
+                versions.push((word * 32 + bit) as u8);
+            }
+        }
+    }
+    Ok(versions)
+}
+
+/// Encodes versions into a version bitmap
+fn versions_bitmap(versions: &[u8]) -> Vec<u8> {
+    let words = versions
+        .iter()
+        .max()
+        .map_or(0, |max| *max as usize / 32 + 1);
+    let mut bitmap = vec![0u32; words];
+    for version in versions {
+        bitmap[*version as usize / 32] |= 1 << (version % 32);
+    }
+    bitmap.iter().flat_map(|word| word.to_be_bytes()).collect()
+}
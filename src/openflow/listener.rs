@@ -0,0 +1,87 @@
+//! Multi-Version Listener
+//!
+//! This module serves OpenFlow 1.0 and 1.3 switches on a single port. Every accepted
+//! connection starts with the Hello exchange of `hello::handshake`; the negotiated
+//! version decides whether the connection is handed to the `ofp10` or the `ofp13`
+//! stack. Switches without a common version receive an `OFPHFC_INCOMPATIBLE` error.
+
+use std::io::Error;
+
+use tokio::net::TcpListener;
+
+use super::{
+    handshake,
+    ofp10::{self, ControllerFrame10, OfpMsgEvent as _, Openflow10},
+    ofp13::{self, ControllerFrame13, OfpMsgEvent as _, Openflow13},
+    MessageFramer,
+};
+
+/// Handles incoming TCP connections for both OpenFlow 1.0 and 1.3
+///
+/// # Arguments
+/// * `address` - The address to bind the TCP listener to (e.g. "127.0.0.1:6633")
+/// * `ctrl10` - Controller serving the switches that negotiate OpenFlow 1.0
+/// * `ctrl13` - Controller serving the switches that negotiate OpenFlow 1.3
+///
+/// # Returns
+/// * `Result<(), std::io::Error>` - Returns Ok(()) if successful, or an IO error if binding fails
+pub async fn multi_version_listener(
+    address: &str,
+    ctrl10: &(impl ControllerFrame10 + 'static + Clone + Sync),
+    ctrl13: &(impl ControllerFrame13 + 'static + Clone + Sync),
+) -> Result<(), Error> {
+    let listener = TcpListener::bind(address).await?;
+    let supported = [
+        Openflow10::ofp_version() as u8,
+        Openflow13::ofp_version() as u8,
+    ];
+
+    // Each stack keeps its own registry, shared by the connections of its version
+    let registry10 = ctrl10.registry().unwrap_or_default();
+    let registry13 = ctrl13.registry().unwrap_or_default();
+
+    loop {
+        let (stream, addr) = listener.accept().await?;
+        println!("server has connection from {}", addr);
+
+        let mut ctrl10 = ctrl10.clone();
+        let mut ctrl13 = ctrl13.clone();
+        let registry10 = registry10.clone();
+        let registry13 = registry13.clone();
+        tokio::spawn(async move {
+            let (mut reader, mut writer) = stream.into_split();
+            let mut framer = MessageFramer::new();
+            let (version, hello_xid) =
+                match handshake(&mut reader, &mut writer, &mut framer, &supported).await {
+                    Ok(negotiated) => negotiated,
+                    Err(e) => {
+                        println!("hello exchange with {} failed: {}", addr, e);
+                        return Ok(());
+                    }
+                };
+            if version == supported[0] {
+                ofp10::tcp_listener::serve(
+                    &mut ctrl10,
+                    reader,
+                    writer,
+                    framer,
+                    addr,
+                    registry10,
+                    hello_xid,
+                )
+                .await
+            } else {
+                ofp13::tcp_listener::serve(
+                    &mut ctrl13,
+                    reader,
+                    writer,
+                    framer,
+                    addr,
+                    registry13,
+                    hello_xid,
+                )
+                .await
+            }
+        });
+    }
+}
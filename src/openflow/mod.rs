@@ -6,8 +6,38 @@
 //! - OpenFlow 1.3 (ofp13)
 //!
 //! Each version is implemented in its own submodule with specific message types,
-//! event handling, and protocol-specific functionality.
+//! event handling, and protocol-specific functionality. Pieces that do not depend
+//! on the protocol version, such as message framing and request/reply correlation, live directly in this module.
 
 pub mod ofp10;
 
 pub mod ofp13;
+
+pub mod framing;
+pub use framing::MessageFramer;
+
+pub mod writer;
+pub use writer::spawn_writer;
+
+pub mod request;
+pub use request::{Delivery, PendingRequests, RawReply, RequestError};
+
+pub mod hello;
+pub use hello::{handshake, Hello, HelloElement};
+
+pub mod listener;
+pub use listener::multi_version_listener;
+
+pub mod experimenter;
+pub use experimenter::{ExperimenterCodec, ExperimenterRegistry, ExperimenterValue};
+
+#[cfg(feature = "nicira")]
+pub mod nicira;
+
+/// Converts a NUL padded name field, such as a port name, into a string
+///
+/// Both protocol versions pad names with NUL bytes up to a fixed length.
+pub(crate) fn port_name(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
@@ -0,0 +1,588 @@
+//! Nicira Actions
+//!
+//! Open vSwitch actions travel as experimenter (OpenFlow 1.3) or vendor (OpenFlow 1.0)
+//! actions with the Nicira vendor id. Their body starts with a 16 bit subtype:
+//!
+//! | Type (0xffff) | Length | Vendor (0x2320) | Subtype (16 bits) | Body... |
+//! |---------------|--------|-----------------|-------------------|---------|
+//!
+//! `NxAction::encode` and `NxAction::decode` work on the part following the vendor id,
+//! the form used by `ExperimenterAction` and `ofp10::Action::Vendor`.
+
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use super::{
+    nxm::{ofs_nbits, split_ofs_nbits, NxmHeader},
+    NX_VENDOR_ID,
+};
+use crate::openflow::{ofp10, ofp13};
+
+/// Subtype of `resubmit` without a table (`NXAST_RESUBMIT`)
+const NXAST_RESUBMIT: u16 = 1;
+/// Subtype of `load` (`NXAST_REG_LOAD`)
+const NXAST_REG_LOAD: u16 = 7;
+/// Subtype of `note` (`NXAST_NOTE`)
+const NXAST_NOTE: u16 = 8;
+/// Subtype of `resubmit` with a table (`NXAST_RESUBMIT_TABLE`)
+const NXAST_RESUBMIT_TABLE: u16 = 14;
+/// Subtype of `output:reg` (`NXAST_OUTPUT_REG`)
+const NXAST_OUTPUT_REG: u16 = 15;
+/// Subtype of `learn` (`NXAST_LEARN`)
+const NXAST_LEARN: u16 = 16;
+/// Subtype of `dec_ttl` with controller ids (`NXAST_DEC_TTL_CNT_IDS`)
+const NXAST_DEC_TTL_CNT_IDS: u16 = 21;
+/// Subtype of `ct` (`NXAST_CT`)
+const NXAST_CT: u16 = 35;
+
+/// Nicira action
+#[derive(Clone, Debug, PartialEq)]
+pub enum NxAction {
+    /// Looks the packet up again in a table: `resubmit(port,table)`
+    Resubmit {
+        /// Ingress port used for the lookup, `RESUBMIT_IN_PORT` keeps the real one
+        in_port: u16,
+        /// Table of the lookup, `None` for the current table
+        table: Option<u8>,
+    },
+    /// Loads an immediate value into a sub-field: `load:value->field[ofs..]`
+    RegLoad {
+        /// Field to write
+        dst: NxmHeader,
+        /// Offset of the first bit written
+        ofs: u16,
+        /// Number of bits written
+        n_bits: u16,
+        /// Value to write
+        value: u64,
+    },
+    /// Carries opaque bytes, ignored by the switch: `note:hex`
+    ///
+    /// Decoded notes keep the zero padding of the action.
+    Note(Vec<u8>),
+    /// Outputs to the port stored in a sub-field: `output:field[ofs..]`
+    OutputReg {
+        /// Field holding the port
+        src: NxmHeader,
+        /// Offset of the first bit of the port
+        ofs: u16,
+        /// Number of bits of the port
+        n_bits: u16,
+        /// Bytes sent to the controller when the port is `CONTROLLER`
+        max_len: u16,
+    },
+    /// Adds or modifies a flow from the packet: `learn(...)`
+    Learn(Learn),
+    /// Decrements the IP TTL, sending expired packets to these controllers: `dec_ttl(ids)`
+    DecTtl {
+        /// Controller connection ids receiving packets with an expired TTL
+        controller_ids: Vec<u16>,
+    },
+    /// Sends the packet through connection tracking: `ct(...)`
+    Conntrack(Conntrack),
+}
+
+impl NxAction {
+    /// Port number of `resubmit` keeping the ingress port (`OFPP_IN_PORT`)
+    pub const RESUBMIT_IN_PORT: u16 = 0xfff8;
+
+    /// Encodes the action body following the vendor id, padding excluded
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        match self {
+            NxAction::Resubmit {
+                in_port,
+                table: None,
+            } => {
+                let _ = bytes.write_u16::<BigEndian>(NXAST_RESUBMIT);
+                let _ = bytes.write_u16::<BigEndian>(*in_port);
+                bytes.extend_from_slice(&[0; 4]);
+            }
+            NxAction::Resubmit {
+                in_port,
+                table: Some(table),
+            } => {
+                let _ = bytes.write_u16::<BigEndian>(NXAST_RESUBMIT_TABLE);
+                let _ = bytes.write_u16::<BigEndian>(*in_port);
+                let _ = bytes.write_u8(*table);
+                bytes.extend_from_slice(&[0; 3]);
+            }
+            NxAction::RegLoad {
+                dst,
+                ofs,
+                n_bits,
+                value,
+            } => {
+                let _ = bytes.write_u16::<BigEndian>(NXAST_REG_LOAD);
+                let _ = bytes.write_u16::<BigEndian>(ofs_nbits(*ofs, *n_bits));
+                let _ = bytes.write_u32::<BigEndian>(dst.0);
+                let _ = bytes.write_u64::<BigEndian>(*value);
+            }
+            NxAction::Note(note) => {
+                let _ = bytes.write_u16::<BigEndian>(NXAST_NOTE);
+                bytes.extend_from_slice(note);
+            }
+            NxAction::OutputReg {
+                src,
+                ofs,
+                n_bits,
+                max_len,
+            } => {
+                let _ = bytes.write_u16::<BigEndian>(NXAST_OUTPUT_REG);
+                let _ = bytes.write_u16::<BigEndian>(ofs_nbits(*ofs, *n_bits));
+                let _ = bytes.write_u32::<BigEndian>(src.0);
+                let _ = bytes.write_u16::<BigEndian>(*max_len);
+                bytes.extend_from_slice(&[0; 6]);
+            }
+            NxAction::Learn(learn) => {
+                let _ = bytes.write_u16::<BigEndian>(NXAST_LEARN);
+                learn.marshal(&mut bytes);
+            }
+            NxAction::DecTtl { controller_ids } => {
+                let _ = bytes.write_u16::<BigEndian>(NXAST_DEC_TTL_CNT_IDS);
+                let _ = bytes.write_u16::<BigEndian>(controller_ids.len() as u16);
+                bytes.extend_from_slice(&[0; 4]);
+                for id in controller_ids {
+                    let _ = bytes.write_u16::<BigEndian>(*id);
+                }
+            }
+            NxAction::Conntrack(ct) => {
+                let _ = bytes.write_u16::<BigEndian>(NXAST_CT);
+                ct.marshal(&mut bytes);
+            }
+        }
+        bytes
+    }
+
+    /// Decodes an action body following the vendor id, padding included
+    ///
+    /// # Arguments
+    /// * `data` - The body, starting with the subtype
+    pub fn decode(data: &[u8]) -> Result<Self, Error> {
+        let mut bytes = Cursor::new(data);
+        let subtype = bytes.read_u16::<BigEndian>()?;
+        let action = match subtype {
+            NXAST_RESUBMIT => NxAction::Resubmit {
+                in_port: bytes.read_u16::<BigEndian>()?,
+                table: None,
+            },
+            NXAST_RESUBMIT_TABLE => NxAction::Resubmit {
+                in_port: bytes.read_u16::<BigEndian>()?,
+                table: Some(bytes.read_u8()?),
+            },
+            NXAST_REG_LOAD => {
+                let (ofs, n_bits) = split_ofs_nbits(bytes.read_u16::<BigEndian>()?);
+                NxAction::RegLoad {
+                    dst: NxmHeader(bytes.read_u32::<BigEndian>()?),
+                    ofs,
+                    n_bits,
+                    value: bytes.read_u64::<BigEndian>()?,
+                }
+            }
+            NXAST_NOTE => NxAction::Note(data[2..].to_vec()),
+            NXAST_OUTPUT_REG => {
+                let (ofs, n_bits) = split_ofs_nbits(bytes.read_u16::<BigEndian>()?);
+                NxAction::OutputReg {
+                    src: NxmHeader(bytes.read_u32::<BigEndian>()?),
+                    ofs,
+                    n_bits,
+                    max_len: bytes.read_u16::<BigEndian>()?,
+                }
+            }
+            NXAST_LEARN => NxAction::Learn(Learn::parse(&mut bytes)?),
+            NXAST_DEC_TTL_CNT_IDS => {
+                let count = bytes.read_u16::<BigEndian>()?;
+                bytes.read_u32::<BigEndian>()?;
+                let mut controller_ids = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    controller_ids.push(bytes.read_u16::<BigEndian>()?);
+                }
+                NxAction::DecTtl { controller_ids }
+            }
+            NXAST_CT => NxAction::Conntrack(Conntrack::parse(&mut bytes)?),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unsupported nicira action subtype {}", subtype),
+                ))
+            }
+        };
+        Ok(action)
+    }
+
+    /// Wraps the action into an OpenFlow 1.3 experimenter action
+    pub fn to_ofp13(&self) -> ofp13::Action {
+        ofp13::Action::Experimenter(ofp13::ExperimenterAction::new(NX_VENDOR_ID, self.encode()))
+    }
+
+    /// Wraps the action into an OpenFlow 1.0 vendor action
+    pub fn to_ofp10(&self) -> ofp10::Action {
+        ofp10::Action::Vendor(NX_VENDOR_ID, self.encode())
+    }
+}
+
+/// Body of a `learn` action
+#[derive(Clone, Debug, PartialEq)]
+pub struct Learn {
+    /// Idle timeout of the learned flow
+    pub idle_timeout: u16,
+    /// Hard timeout of the learned flow
+    pub hard_timeout: u16,
+    /// Priority of the learned flow
+    pub priority: u16,
+    /// Cookie of the learned flow
+    pub cookie: u64,
+    /// `NX_LEARN_F_*` flags
+    pub flags: u16,
+    /// Table of the learned flow
+    pub table_id: u8,
+    /// Idle timeout applied after a FIN or RST
+    pub fin_idle_timeout: u16,
+    /// Hard timeout applied after a FIN or RST
+    pub fin_hard_timeout: u16,
+    /// How the learned flow is built
+    pub specs: Vec<LearnSpec>,
+}
+
+impl Learn {
+    /// Flag sending a flow removed message when the learned flow expires
+    pub const SEND_FLOW_REM: u16 = 1 << 0;
+    /// Flag deleting the learned flow when the learning flow is removed
+    pub const DELETE_LEARNED: u16 = 1 << 1;
+
+    /// Creates a learn action with the defaults of `ovs-ofctl`
+    ///
+    /// # Arguments
+    /// * `table_id` - Table of the learned flow
+    /// * `specs` - How the learned flow is built
+    pub fn new(table_id: u8, specs: Vec<LearnSpec>) -> Self {
+        Self {
+            idle_timeout: 0,
+            hard_timeout: 0,
+            priority: 0x8000,
+            cookie: 0,
+            flags: 0,
+            table_id,
+            fin_idle_timeout: 0,
+            fin_hard_timeout: 0,
+            specs,
+        }
+    }
+
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u16::<BigEndian>(self.idle_timeout);
+        let _ = bytes.write_u16::<BigEndian>(self.hard_timeout);
+        let _ = bytes.write_u16::<BigEndian>(self.priority);
+        let _ = bytes.write_u64::<BigEndian>(self.cookie);
+        let _ = bytes.write_u16::<BigEndian>(self.flags);
+        let _ = bytes.write_u8(self.table_id);
+        // padding
+        let _ = bytes.write_u8(0);
+        let _ = bytes.write_u16::<BigEndian>(self.fin_idle_timeout);
+        let _ = bytes.write_u16::<BigEndian>(self.fin_hard_timeout);
+        for spec in &self.specs {
+            spec.marshal(bytes);
+        }
+    }
+
+    fn parse(bytes: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let idle_timeout = bytes.read_u16::<BigEndian>()?;
+        let hard_timeout = bytes.read_u16::<BigEndian>()?;
+        let priority = bytes.read_u16::<BigEndian>()?;
+        let cookie = bytes.read_u64::<BigEndian>()?;
+        let flags = bytes.read_u16::<BigEndian>()?;
+        let table_id = bytes.read_u8()?;
+        // padding
+        bytes.read_u8()?;
+        let fin_idle_timeout = bytes.read_u16::<BigEndian>()?;
+        let fin_hard_timeout = bytes.read_u16::<BigEndian>()?;
+        let mut specs = Vec::new();
+        // the specs end at a zero header or with the action
+        let len = bytes.get_ref().len() as u64;
+        while len - bytes.position() >= 2 {
+            let header = bytes.read_u16::<BigEndian>()?;
+            if header == 0 {
+                break;
+            }
+            specs.push(LearnSpec::parse(header, bytes)?);
+        }
+        Ok(Self {
+            idle_timeout,
+            hard_timeout,
+            priority,
+            cookie,
+            flags,
+            table_id,
+            fin_idle_timeout,
+            fin_hard_timeout,
+            specs,
+        })
+    }
+}
+
+/// Source of the bits of a learn spec
+#[derive(Clone, Debug, PartialEq)]
+pub enum LearnSrc {
+    /// Bits of a field of the packet
+    Field {
+        /// Field to read
+        field: NxmHeader,
+        /// Offset of the first bit read
+        ofs: u16,
+    },
+    /// Immediate value, `2 * ceil(n_bits / 16)` bytes long
+    Immediate(Vec<u8>),
+}
+
+/// Destination of the bits of a learn spec
+#[derive(Clone, Debug, PartialEq)]
+pub enum LearnDst {
+    /// Match field of the learned flow
+    Match {
+        /// Field to match
+        field: NxmHeader,
+        /// Offset of the first bit matched
+        ofs: u16,
+    },
+    /// `load` action of the learned flow
+    Load {
+        /// Field to load
+        field: NxmHeader,
+        /// Offset of the first bit loaded
+        ofs: u16,
+    },
+    /// `output` action of the learned flow, the source holds the port
+    Output,
+}
+
+/// One match or action of the flow built by a learn action
+#[derive(Clone, Debug, PartialEq)]
+pub struct LearnSpec {
+    /// Number of bits copied
+    pub n_bits: u16,
+    /// Where the bits come from
+    pub src: LearnSrc,
+    /// Where the bits go to
+    pub dst: LearnDst,
+}
+
+impl LearnSpec {
+    /// Bit of the spec header for an immediate source
+    const SRC_IMMEDIATE: u16 = 1 << 13;
+    /// Mask of the destination type in the spec header
+    const DST_MASK: u16 = 3 << 11;
+    /// Destination type of a `load`
+    const DST_LOAD: u16 = 1 << 11;
+    /// Destination type of an `output`
+    const DST_OUTPUT: u16 = 2 << 11;
+
+    /// Creates a spec matching a field of the learned flow on a field of the packet,
+    /// e.g. `NXM_OF_ETH_DST[]=NXM_OF_ETH_SRC[]`
+    ///
+    /// # Arguments
+    /// * `dst` - Field matched by the learned flow
+    /// * `src` - Field of the packet providing the value
+    /// * `n_bits` - Number of bits, the whole field in most cases
+    pub fn match_field(dst: NxmHeader, src: NxmHeader, n_bits: u16) -> Self {
+        Self {
+            n_bits,
+            src: LearnSrc::Field { field: src, ofs: 0 },
+            dst: LearnDst::Match { field: dst, ofs: 0 },
+        }
+    }
+
+    /// Creates a spec loading a field of the packet in the learned flow,
+    /// e.g. `load:NXM_OF_IN_PORT[]->NXM_NX_REG0[0..15]`
+    ///
+    /// # Arguments
+    /// * `src` - Field of the packet providing the value
+    /// * `dst` - Field loaded by the learned flow
+    /// * `n_bits` - Number of bits
+    pub fn load_field(src: NxmHeader, dst: NxmHeader, n_bits: u16) -> Self {
+        Self {
+            n_bits,
+            src: LearnSrc::Field { field: src, ofs: 0 },
+            dst: LearnDst::Load { field: dst, ofs: 0 },
+        }
+    }
+
+    /// Creates a spec outputting the learned flow to the port held by a field,
+    /// e.g. `output:NXM_OF_IN_PORT[]`
+    ///
+    /// # Arguments
+    /// * `src` - Field of the packet holding the port
+    /// * `n_bits` - Number of bits
+    pub fn output(src: NxmHeader, n_bits: u16) -> Self {
+        Self {
+            n_bits,
+            src: LearnSrc::Field { field: src, ofs: 0 },
+            dst: LearnDst::Output,
+        }
+    }
+
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let mut header = self.n_bits;
+        if let LearnSrc::Immediate(_) = self.src {
+            header |= Self::SRC_IMMEDIATE;
+        }
+        header |= match self.dst {
+            LearnDst::Match { .. } => 0,
+            LearnDst::Load { .. } => Self::DST_LOAD,
+            LearnDst::Output => Self::DST_OUTPUT,
+        };
+        let _ = bytes.write_u16::<BigEndian>(header);
+        match &self.src {
+            LearnSrc::Field { field, ofs } => {
+                let _ = bytes.write_u32::<BigEndian>(field.0);
+                let _ = bytes.write_u16::<BigEndian>(*ofs);
+            }
+            LearnSrc::Immediate(value) => bytes.extend_from_slice(value),
+        }
+        match &self.dst {
+            LearnDst::Match { field, ofs } | LearnDst::Load { field, ofs } => {
+                let _ = bytes.write_u32::<BigEndian>(field.0);
+                let _ = bytes.write_u16::<BigEndian>(*ofs);
+            }
+            LearnDst::Output => (),
+        }
+    }
+
+    fn parse(header: u16, bytes: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let n_bits = header & 0x3ff;
+        let src = if header & Self::SRC_IMMEDIATE != 0 {
+            let mut value = vec![0; n_bits.div_ceil(16) as usize * 2];
+            bytes.read_exact(&mut value)?;
+            LearnSrc::Immediate(value)
+        } else {
+            LearnSrc::Field {
+                field: NxmHeader(bytes.read_u32::<BigEndian>()?),
+                ofs: bytes.read_u16::<BigEndian>()?,
+            }
+        };
+        let dst = match header & Self::DST_MASK {
+            0 => LearnDst::Match {
+                field: NxmHeader(bytes.read_u32::<BigEndian>()?),
+                ofs: bytes.read_u16::<BigEndian>()?,
+            },
+            Self::DST_LOAD => LearnDst::Load {
+                field: NxmHeader(bytes.read_u32::<BigEndian>()?),
+                ofs: bytes.read_u16::<BigEndian>()?,
+            },
+            Self::DST_OUTPUT => LearnDst::Output,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid learn spec header {:#x}", header),
+                ))
+            }
+        };
+        Ok(Self { n_bits, src, dst })
+    }
+}
+
+/// Zone of a `ct` action
+#[derive(Clone, Debug, PartialEq)]
+pub enum CtZone {
+    /// Immediate zone id: `zone=5`
+    Immediate(u16),
+    /// Zone id read from a sub-field: `zone=NXM_NX_REG0[0..15]`
+    Field {
+        /// Field holding the zone
+        src: NxmHeader,
+        /// Offset of the first bit of the zone
+        ofs: u16,
+        /// Number of bits of the zone
+        n_bits: u16,
+    },
+}
+
+/// Body of a `ct` action
+#[derive(Clone, Debug, PartialEq)]
+pub struct Conntrack {
+    /// `COMMIT` and `FORCE` flags
+    pub flags: u16,
+    /// Connection tracking zone
+    pub zone: CtZone,
+    /// Table the packet is resubmitted to after tracking, `RECIRC_NONE` for none
+    pub recirc_table: u8,
+    /// Application layer gateway, e.g. 21 for FTP, 0 for none
+    pub alg: u16,
+    /// Encoded actions applied to committed connections (`exec(...)`), in the
+    /// action format of the connection's OpenFlow version
+    pub actions: Vec<u8>,
+}
+
+impl Conntrack {
+    /// Flag committing the connection to the connection tracker
+    pub const COMMIT: u16 = 1 << 0;
+    /// Flag replacing a committed connection going in the other direction
+    pub const FORCE: u16 = 1 << 1;
+    /// Value of `recirc_table` without recirculation
+    pub const RECIRC_NONE: u8 = 0xff;
+
+    /// Creates a `ct` action in zone 0 without flags, recirculation or actions
+    pub fn new() -> Self {
+        Self {
+            flags: 0,
+            zone: CtZone::Immediate(0),
+            recirc_table: Self::RECIRC_NONE,
+            alg: 0,
+            actions: Vec::new(),
+        }
+    }
+
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u16::<BigEndian>(self.flags);
+        match &self.zone {
+            CtZone::Immediate(zone) => {
+                let _ = bytes.write_u32::<BigEndian>(0);
+                let _ = bytes.write_u16::<BigEndian>(*zone);
+            }
+            CtZone::Field { src, ofs, n_bits } => {
+                let _ = bytes.write_u32::<BigEndian>(src.0);
+                let _ = bytes.write_u16::<BigEndian>(ofs_nbits(*ofs, *n_bits));
+            }
+        }
+        let _ = bytes.write_u8(self.recirc_table);
+        bytes.extend_from_slice(&[0; 3]);
+        let _ = bytes.write_u16::<BigEndian>(self.alg);
+        bytes.extend_from_slice(&self.actions);
+    }
+
+    fn parse(bytes: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let flags = bytes.read_u16::<BigEndian>()?;
+        let zone_src = bytes.read_u32::<BigEndian>()?;
+        let zone = bytes.read_u16::<BigEndian>()?;
+        let zone = match zone_src {
+            0 => CtZone::Immediate(zone),
+            src => {
+                let (ofs, n_bits) = split_ofs_nbits(zone);
+                CtZone::Field {
+                    src: NxmHeader(src),
+                    ofs,
+                    n_bits,
+                }
+            }
+        };
+        let recirc_table = bytes.read_u8()?;
+        // padding
+        bytes.read_u24::<BigEndian>()?;
+        let alg = bytes.read_u16::<BigEndian>()?;
+        let mut actions = Vec::new();
+        bytes.read_to_end(&mut actions)?;
+        Ok(Self {
+            flags,
+            zone,
+            recirc_table,
+            alg,
+            actions,
+        })
+    }
+}
+
+impl Default for Conntrack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,225 @@
+//! Nicira Messages
+//!
+//! Open vSwitch messages travel as experimenter (OpenFlow 1.3) or vendor (OpenFlow 1.0)
+//! messages with the Nicira vendor id, followed by a 32 bit subtype. This module covers
+//! `NXT_PACKET_IN2`, the extensible packet-in of Open vSwitch, and
+//! `NXT_SET_PACKET_IN_FORMAT`, which asks the switch to send it.
+//!
+//! The body of `NXT_PACKET_IN2` is a list of properties, each padded to 8 bytes:
+//!
+//! | Type (16 bits) | Length (16 bits, padding excluded) | Value | Padding |
+//! |----------------|------------------------------------|-------|---------|
+
+use std::io::{Cursor, Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use super::NX_VENDOR_ID;
+use crate::openflow::{ofp10, ofp13};
+
+/// Subtype of `NXT_SET_PACKET_IN_FORMAT`
+const NXT_SET_PACKET_IN_FORMAT: u32 = 16;
+/// Subtype of `NXT_PACKET_IN2`
+const NXT_PACKET_IN2: u32 = 30;
+
+/// Property holding the packet (`NXPINT_PACKET`)
+const NXPINT_PACKET: u16 = 0;
+/// Property holding the length of the untruncated packet (`NXPINT_FULL_LEN`)
+const NXPINT_FULL_LEN: u16 = 1;
+/// Property holding the buffer id (`NXPINT_BUFFER_ID`)
+const NXPINT_BUFFER_ID: u16 = 2;
+/// Property holding the table id (`NXPINT_TABLE_ID`)
+const NXPINT_TABLE_ID: u16 = 3;
+/// Property holding the flow cookie (`NXPINT_COOKIE`)
+const NXPINT_COOKIE: u16 = 4;
+/// Property holding the reason (`NXPINT_REASON`)
+const NXPINT_REASON: u16 = 5;
+/// Property holding the pipeline metadata as OXM fields (`NXPINT_METADATA`)
+const NXPINT_METADATA: u16 = 6;
+/// Property holding the `userdata` of the controller action (`NXPINT_USERDATA`)
+const NXPINT_USERDATA: u16 = 7;
+/// Property holding the continuation of a paused pipeline (`NXPINT_CONTINUATION`)
+const NXPINT_CONTINUATION: u16 = 8;
+
+/// Nicira message
+#[derive(Clone, Debug, PartialEq)]
+pub enum NxMessage {
+    /// Selects the format of packet-in messages (`NXT_SET_PACKET_IN_FORMAT`)
+    SetPacketInFormat(u32),
+    /// Extensible packet-in (`NXT_PACKET_IN2`)
+    PacketIn2(PacketIn2),
+}
+
+impl NxMessage {
+    /// Packet-in format of the OpenFlow version in use (`NXPIF_STANDARD`)
+    pub const FORMAT_STANDARD: u32 = 0;
+    /// Packet-in format of `NXT_PACKET_IN` (`NXPIF_NXT_PACKET_IN`)
+    pub const FORMAT_NXT_PACKET_IN: u32 = 1;
+    /// Packet-in format of `NXT_PACKET_IN2` (`NXPIF_NXT_PACKET_IN2`)
+    pub const FORMAT_NXT_PACKET_IN2: u32 = 2;
+
+    /// Encodes the message into its subtype and body
+    pub fn encode(&self) -> (u32, Vec<u8>) {
+        let mut bytes = Vec::new();
+        match self {
+            NxMessage::SetPacketInFormat(format) => {
+                let _ = bytes.write_u32::<BigEndian>(*format);
+                (NXT_SET_PACKET_IN_FORMAT, bytes)
+            }
+            NxMessage::PacketIn2(packet_in) => {
+                packet_in.marshal(&mut bytes);
+                (NXT_PACKET_IN2, bytes)
+            }
+        }
+    }
+
+    /// Decodes a message from its subtype and body
+    ///
+    /// # Arguments
+    /// * `exp_type` - Subtype of the message
+    /// * `data` - Body of the message following the subtype
+    pub fn decode(exp_type: u32, data: &[u8]) -> Result<Self, Error> {
+        match exp_type {
+            NXT_SET_PACKET_IN_FORMAT => Ok(NxMessage::SetPacketInFormat(
+                Cursor::new(data).read_u32::<BigEndian>()?,
+            )),
+            NXT_PACKET_IN2 => Ok(NxMessage::PacketIn2(PacketIn2::parse(data)?)),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unsupported nicira message subtype {}", exp_type),
+            )),
+        }
+    }
+
+    /// Wraps the message into an OpenFlow 1.3 experimenter message
+    pub fn to_ofp13(&self) -> ofp13::ExperimenterEvent {
+        let (exp_type, data) = self.encode();
+        ofp13::ExperimenterEvent::new(NX_VENDOR_ID, exp_type, data)
+    }
+
+    /// Wraps the message into an OpenFlow 1.0 vendor message
+    pub fn to_ofp10(&self) -> ofp10::VendorEvent {
+        let (exp_type, data) = self.encode();
+        let mut body = exp_type.to_be_bytes().to_vec();
+        body.extend_from_slice(&data);
+        ofp10::VendorEvent::new(NX_VENDOR_ID, body)
+    }
+}
+
+/// Extensible packet-in of Open vSwitch
+///
+/// Properties the switch did not send are `None` or empty.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PacketIn2 {
+    /// The packet, possibly truncated
+    pub packet: Vec<u8>,
+    /// Length of the untruncated packet, sent when the packet was truncated
+    pub full_len: Option<u32>,
+    /// Id of the buffer holding the packet on the switch
+    pub buffer_id: Option<u32>,
+    /// Table of the flow that sent the packet
+    pub table_id: Option<u8>,
+    /// Cookie of the flow that sent the packet
+    pub cookie: Option<u64>,
+    /// Reason the packet was sent (`OFPR_*`)
+    pub reason: Option<u8>,
+    /// Pipeline metadata as OXM fields without a match header, e.g. `in_port` and `reg0`
+    pub metadata: Vec<u8>,
+    /// `userdata` of the `controller` action
+    pub userdata: Option<Vec<u8>>,
+    /// Opaque continuation to resume a paused pipeline with `NXT_RESUME`
+    pub continuation: Option<Vec<u8>>,
+}
+
+impl PacketIn2 {
+    /// Parses the properties of the message
+    ///
+    /// # Arguments
+    /// * `data` - Body of the message following the subtype
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut packet_in = PacketIn2::default();
+        let mut rest = data;
+        while rest.len() >= 4 {
+            let typ = (&rest[0..2]).read_u16::<BigEndian>()?;
+            let length = (&rest[2..4]).read_u16::<BigEndian>()? as usize;
+            if length < 4 || length > rest.len() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid packet-in2 property length {}", length),
+                ));
+            }
+            let mut value = &rest[4..length];
+            match typ {
+                NXPINT_PACKET => packet_in.packet = value.to_vec(),
+                NXPINT_FULL_LEN => packet_in.full_len = Some(value.read_u32::<BigEndian>()?),
+                NXPINT_BUFFER_ID => packet_in.buffer_id = Some(value.read_u32::<BigEndian>()?),
+                NXPINT_TABLE_ID => packet_in.table_id = Some(value.read_u8()?),
+                NXPINT_COOKIE => {
+                    // 64 bit values are aligned after 4 bytes of padding
+                    value.read_u32::<BigEndian>()?;
+                    packet_in.cookie = Some(value.read_u64::<BigEndian>()?)
+                }
+                NXPINT_REASON => packet_in.reason = Some(value.read_u8()?),
+                NXPINT_METADATA => packet_in.metadata = value.to_vec(),
+                NXPINT_USERDATA => packet_in.userdata = Some(value.to_vec()),
+                NXPINT_CONTINUATION => packet_in.continuation = Some(value.to_vec()),
+                _ => (),
+            }
+            rest = &rest[(length.div_ceil(8) * 8).min(rest.len())..];
+        }
+        Ok(packet_in)
+    }
+
+    /// Returns the pipeline metadata as OpenFlow 1.3 match fields
+    ///
+    /// Registers and other Nicira fields end up in `MatchFields::extensions`.
+    pub fn metadata_fields(&self) -> Result<ofp13::MatchFields, Error> {
+        let length = 4 + self.metadata.len();
+        let mut bytes = Vec::with_capacity(length.div_ceil(8) * 8);
+        // OXM match header
+        bytes.write_u16::<BigEndian>(1)?;
+        bytes.write_u16::<BigEndian>(length as u16)?;
+        bytes.extend_from_slice(&self.metadata);
+        bytes.resize(length.div_ceil(8) * 8, 0);
+        ofp13::MatchFields::parse(&mut Cursor::new(bytes))
+    }
+
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        put_property(bytes, NXPINT_PACKET, &self.packet);
+        if let Some(full_len) = self.full_len {
+            put_property(bytes, NXPINT_FULL_LEN, &full_len.to_be_bytes());
+        }
+        if let Some(buffer_id) = self.buffer_id {
+            put_property(bytes, NXPINT_BUFFER_ID, &buffer_id.to_be_bytes());
+        }
+        if let Some(table_id) = self.table_id {
+            put_property(bytes, NXPINT_TABLE_ID, &[table_id]);
+        }
+        if let Some(cookie) = self.cookie {
+            let mut value = vec![0; 4];
+            value.extend_from_slice(&cookie.to_be_bytes());
+            put_property(bytes, NXPINT_COOKIE, &value);
+        }
+        if let Some(reason) = self.reason {
+            put_property(bytes, NXPINT_REASON, &[reason]);
+        }
+        if !self.metadata.is_empty() {
+            put_property(bytes, NXPINT_METADATA, &self.metadata);
+        }
+        if let Some(userdata) = &self.userdata {
+            put_property(bytes, NXPINT_USERDATA, userdata);
+        }
+        if let Some(continuation) = &self.continuation {
+            put_property(bytes, NXPINT_CONTINUATION, continuation);
+        }
+    }
+}
+
+/// Writes a property and pads it to a multiple of 8 bytes
+fn put_property(bytes: &mut Vec<u8>, typ: u16, value: &[u8]) {
+    let length = 4 + value.len();
+    let _ = bytes.write_u16::<BigEndian>(typ);
+    let _ = bytes.write_u16::<BigEndian>(length as u16);
+    bytes.extend_from_slice(value);
+    bytes.resize(bytes.len() + length.div_ceil(8) * 8 - length, 0);
+}
@@ -0,0 +1,82 @@
+//! Nicira (NX) Extensions
+//!
+//! This module implements the Open vSwitch extensions most controllers rely on, on top
+//! of the experimenter hooks of the `ofp13` and `ofp10` modules. It is only compiled
+//! with the `nicira` feature.
+//!
+//! - Actions: `resubmit`, `load`, `note`, `output:reg`, `learn`, `dec_ttl` with
+//!   controller ids and `ct` (`NxAction`)
+//! - Matches: `reg0` to `reg15`, `ct_state`, `ct_zone` and `ct_mark` (`NxMatch`)
+//! - Messages: `NXT_PACKET_IN2` and `NXT_SET_PACKET_IN_FORMAT` (`NxMessage`)
+//!
+//! The types convert directly into the raw experimenter forms, e.g. with
+//! `NxAction::to_ofp13`, or through an `ExperimenterRegistry` holding `NiciraCodec`.
+
+use std::{
+    any::{type_name, Any},
+    io::{Error, ErrorKind},
+};
+
+use super::{ofp13::OxmExtension, ExperimenterCodec, ExperimenterValue};
+
+pub mod actions;
+pub use actions::{Conntrack, CtZone, Learn, LearnDst, LearnSpec, LearnSrc, NxAction};
+
+pub mod messages;
+pub use messages::{NxMessage, PacketIn2};
+
+pub mod nxm;
+pub use nxm::{NxMatch, NxmHeader, NXM_0, NXM_1};
+
+/// Vendor id of Nicira, used by every Open vSwitch extension
+pub const NX_VENDOR_ID: u32 = 0x0000_2320;
+
+/// Codec of the Nicira extensions for an `ExperimenterRegistry`
+///
+/// Decodes into and encodes from `NxMessage`, `NxAction` and `NxMatch`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NiciraCodec;
+
+impl ExperimenterCodec for NiciraCodec {
+    fn experimenter(&self) -> u32 {
+        NX_VENDOR_ID
+    }
+
+    fn oxm_classes(&self) -> Vec<u16> {
+        vec![NXM_0, NXM_1]
+    }
+
+    fn decode_message(&self, exp_type: u32, data: &[u8]) -> Result<ExperimenterValue, Error> {
+        Ok(Box::new(NxMessage::decode(exp_type, data)?))
+    }
+
+    fn encode_message(&self, value: &dyn Any) -> Result<(u32, Vec<u8>), Error> {
+        Ok(downcast::<NxMessage>(value)?.encode())
+    }
+
+    fn decode_action(&self, data: &[u8]) -> Result<ExperimenterValue, Error> {
+        Ok(Box::new(NxAction::decode(data)?))
+    }
+
+    fn encode_action(&self, value: &dyn Any) -> Result<Vec<u8>, Error> {
+        Ok(downcast::<NxAction>(value)?.encode())
+    }
+
+    fn decode_oxm(&self, oxm: &OxmExtension) -> Result<ExperimenterValue, Error> {
+        Ok(Box::new(NxMatch::from_oxm(oxm)?))
+    }
+
+    fn encode_oxm(&self, value: &dyn Any) -> Result<OxmExtension, Error> {
+        Ok(downcast::<NxMatch>(value)?.to_oxm())
+    }
+}
+
+/// Casts a value handed to the codec to the type it encodes
+fn downcast<T: 'static>(value: &dyn Any) -> Result<&T, Error> {
+    value.downcast_ref::<T>().ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("expected a {}", type_name::<T>()),
+        )
+    })
+}
@@ -0,0 +1,248 @@
+//! Nicira Extensible Match (NXM)
+//!
+//! Open vSwitch identifies fields by 32 bit NXM headers, both in matches and in the
+//! actions that read or write fields (`load`, `output:reg`, `learn`, `ct`):
+//!
+//! | Class (16 bits) | Field (7 bits) | Hasmask (1 bit) | Length (8 bits) |
+//! |-----------------|----------------|-----------------|-----------------|
+//!
+//! Class `0x0000` holds the fields of OpenFlow 1.0, class `0x0001` the Open vSwitch
+//! extensions such as the registers `reg0` to `reg15` and the conntrack fields. In
+//! OpenFlow 1.3 matches these fields travel as OXM fields of those classes.
+
+use std::io::{Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::openflow::ofp13::OxmExtension;
+
+/// NXM class of the OpenFlow 1.0 fields (`NXM_OF_*`)
+pub const NXM_0: u16 = 0x0000;
+
+/// NXM class of the Open vSwitch extension fields (`NXM_NX_*`)
+pub const NXM_1: u16 = 0x0001;
+
+/// Header of an NXM field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NxmHeader(pub u32);
+
+impl NxmHeader {
+    /// Ingress port (`NXM_OF_IN_PORT`)
+    pub const IN_PORT: Self = Self::new(NXM_0, 0, 2);
+    /// Ethernet destination address (`NXM_OF_ETH_DST`)
+    pub const ETH_DST: Self = Self::new(NXM_0, 1, 6);
+    /// Ethernet source address (`NXM_OF_ETH_SRC`)
+    pub const ETH_SRC: Self = Self::new(NXM_0, 2, 6);
+    /// Ethernet type (`NXM_OF_ETH_TYPE`)
+    pub const ETH_TYPE: Self = Self::new(NXM_0, 3, 2);
+    /// 802.1Q TCI (`NXM_OF_VLAN_TCI`)
+    pub const VLAN_TCI: Self = Self::new(NXM_0, 4, 2);
+    /// IPv4 source address (`NXM_OF_IP_SRC`)
+    pub const IP_SRC: Self = Self::new(NXM_0, 7, 4);
+    /// IPv4 destination address (`NXM_OF_IP_DST`)
+    pub const IP_DST: Self = Self::new(NXM_0, 8, 4);
+    /// Tunnel id (`NXM_NX_TUN_ID`)
+    pub const TUN_ID: Self = Self::new(NXM_1, 16, 8);
+    /// Connection tracking state (`NXM_NX_CT_STATE`)
+    pub const CT_STATE: Self = Self::new(NXM_1, 105, 4);
+    /// Connection tracking zone (`NXM_NX_CT_ZONE`)
+    pub const CT_ZONE: Self = Self::new(NXM_1, 106, 2);
+    /// Connection tracking mark (`NXM_NX_CT_MARK`)
+    pub const CT_MARK: Self = Self::new(NXM_1, 107, 4);
+
+    /// Creates the header of an unmasked field
+    ///
+    /// # Arguments
+    /// * `class` - NXM class of the field
+    /// * `field` - Field number within the class
+    /// * `length` - Length of the value in bytes
+    pub const fn new(class: u16, field: u8, length: u8) -> Self {
+        Self((class as u32) << 16 | (field as u32) << 9 | length as u32)
+    }
+
+    /// Returns the header of register `reg<index>` (`NXM_NX_REG0` to `NXM_NX_REG15`)
+    pub const fn reg(index: u8) -> Self {
+        Self::new(NXM_1, index, 4)
+    }
+
+    /// Returns the class of the field
+    pub fn class(&self) -> u16 {
+        (self.0 >> 16) as u16
+    }
+
+    /// Returns the field number within the class
+    pub fn field(&self) -> u8 {
+        (self.0 >> 9 & 0x7f) as u8
+    }
+
+    /// Returns true for the header of a masked field
+    pub fn hasmask(&self) -> bool {
+        self.0 & 0x100 != 0
+    }
+
+    /// Returns the length of the payload in bytes, mask included
+    pub fn length(&self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Returns the width of the field value in bits
+    pub fn n_bits(&self) -> u16 {
+        let length = if self.hasmask() {
+            self.length() / 2
+        } else {
+            self.length()
+        };
+        length as u16 * 8
+    }
+}
+
+/// Encodes the offset and width of a sub-field (`ofs_nbits`)
+pub(crate) fn ofs_nbits(ofs: u16, n_bits: u16) -> u16 {
+    ofs << 6 | (n_bits - 1)
+}
+
+/// Decodes an `ofs_nbits` value into the offset and width of a sub-field
+pub(crate) fn split_ofs_nbits(ofs_nbits: u16) -> (u16, u16) {
+    (ofs_nbits >> 6, (ofs_nbits & 0x3f) + 1)
+}
+
+/// Nicira match field carried in an OpenFlow 1.3 match
+#[derive(Clone, Debug, PartialEq)]
+pub enum NxMatch {
+    /// Register `reg<index>` with an optional mask
+    Reg {
+        /// Register number, 0 to 15
+        index: u8,
+        /// Value of the register
+        value: u32,
+        /// Mask of the register
+        mask: Option<u32>,
+    },
+    /// Connection tracking state (`CS_*` flags) with an optional mask
+    CtState {
+        /// State flags
+        value: u32,
+        /// Mask of the state flags
+        mask: Option<u32>,
+    },
+    /// Connection tracking zone
+    CtZone(u16),
+    /// Connection tracking mark with an optional mask
+    CtMark {
+        /// Mark of the connection
+        value: u32,
+        /// Mask of the mark
+        mask: Option<u32>,
+    },
+}
+
+impl NxMatch {
+    /// Connection tracking state: new connection (`+new`)
+    pub const CS_NEW: u32 = 1 << 0;
+    /// Connection tracking state: established connection (`+est`)
+    pub const CS_ESTABLISHED: u32 = 1 << 1;
+    /// Connection tracking state: related connection (`+rel`)
+    pub const CS_RELATED: u32 = 1 << 2;
+    /// Connection tracking state: reply direction (`+rpl`)
+    pub const CS_REPLY_DIR: u32 = 1 << 3;
+    /// Connection tracking state: invalid packet (`+inv`)
+    pub const CS_INVALID: u32 = 1 << 4;
+    /// Connection tracking state: packet went through conntrack (`+trk`)
+    pub const CS_TRACKED: u32 = 1 << 5;
+
+    /// Converts the match into an OXM field of its NXM class
+    pub fn to_oxm(&self) -> OxmExtension {
+        let (header, value, mask) = match self {
+            NxMatch::Reg { index, value, mask } => (
+                NxmHeader::reg(*index),
+                value.to_be_bytes().to_vec(),
+                mask.map(|mask| mask.to_be_bytes().to_vec()),
+            ),
+            NxMatch::CtState { value, mask } => (
+                NxmHeader::CT_STATE,
+                value.to_be_bytes().to_vec(),
+                mask.map(|mask| mask.to_be_bytes().to_vec()),
+            ),
+            NxMatch::CtZone(zone) => (NxmHeader::CT_ZONE, zone.to_be_bytes().to_vec(), None),
+            NxMatch::CtMark { value, mask } => (
+                NxmHeader::CT_MARK,
+                value.to_be_bytes().to_vec(),
+                mask.map(|mask| mask.to_be_bytes().to_vec()),
+            ),
+        };
+        let mut payload = value;
+        if let Some(mask) = &mask {
+            payload.extend_from_slice(mask);
+        }
+        OxmExtension::new(header.class(), header.field(), mask.is_some(), payload)
+    }
+
+    /// Decodes an OXM field of an NXM class
+    ///
+    /// # Arguments
+    /// * `oxm` - The field, as kept in `MatchFields::extensions`
+    pub fn from_oxm(oxm: &OxmExtension) -> Result<Self, Error> {
+        let header = NxmHeader::new(oxm.class, oxm.field, 0);
+        let (value, mask) = split_masked(oxm)?;
+        let matched = match header {
+            h if h.class() == NXM_1 && h.field() < 16 => NxMatch::Reg {
+                index: h.field(),
+                value: read_u32(value)?,
+                mask: mask.map(read_u32).transpose()?,
+            },
+            h if h == NxmHeader::new(NXM_1, 105, 0) => NxMatch::CtState {
+                value: read_u32(value)?,
+                mask: mask.map(read_u32).transpose()?,
+            },
+            h if h == NxmHeader::new(NXM_1, 106, 0) => NxMatch::CtZone(read_u16(value)?),
+            h if h == NxmHeader::new(NXM_1, 107, 0) => NxMatch::CtMark {
+                value: read_u32(value)?,
+                mask: mask.map(read_u32).transpose()?,
+            },
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unsupported nxm field {:#x}:{}", oxm.class, oxm.field),
+                ))
+            }
+        };
+        Ok(matched)
+    }
+}
+
+/// Splits the payload of a field into its value and mask
+fn split_masked(oxm: &OxmExtension) -> Result<(&[u8], Option<&[u8]>), Error> {
+    if !oxm.hasmask {
+        return Ok((&oxm.payload, None));
+    }
+    if !oxm.payload.len().is_multiple_of(2) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("invalid masked nxm field length {}", oxm.payload.len()),
+        ));
+    }
+    let (value, mask) = oxm.payload.split_at(oxm.payload.len() / 2);
+    Ok((value, Some(mask)))
+}
+
+/// Reads a 16 bit value filling the whole slice
+fn read_u16(mut bytes: &[u8]) -> Result<u16, Error> {
+    if bytes.len() != 2 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("expected a 16 bit nxm value, got {} bytes", bytes.len()),
+        ));
+    }
+    bytes.read_u16::<BigEndian>()
+}
+
+/// Reads a 32 bit value filling the whole slice
+fn read_u32(mut bytes: &[u8]) -> Result<u32, Error> {
+    if bytes.len() != 4 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("expected a 32 bit nxm value, got {} bytes", bytes.len()),
+        ));
+    }
+    bytes.read_u32::<BigEndian>()
+}
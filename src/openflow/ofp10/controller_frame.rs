@@ -5,7 +5,7 @@
 //! message parsing, event handling, and TCP communication functionality.
 //!
 //! The controller frame is responsible for:
-//! - Managing TCP connections with switches
+//! - Managing connections with switches through `SwitchHandle`
 //! - Parsing and handling OpenFlow messages
 //! - Processing various OpenFlow events
 //! - Sending responses and commands to switches
@@ -15,12 +15,10 @@
 //! of message handling and event processing while maintaining compatibility with
 //! the OpenFlow 1.0 protocol specification.
 
-use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
-    net::TcpStream,
+use crate::openflow::ofp10::{
+    self, Datapath, DatapathRegistry, ErrorEvent, FeaturesReplyEvent, FlowRemovedEvent, Msg,
+    PacketInEvent, PortStatusEvent, SwitchHandle, VendorEvent,
 };
-
-use crate::openflow::ofp10::{self, ErrorEvent, Msg, PacketInEvent};
 use std::future::Future;
 
 use super::{
@@ -32,11 +30,18 @@ pub trait ControllerFrame10: Send {
     fn ofp(&self) -> ofp10::Openflow10 {
         Openflow10::new()
     }
+
+    /// Returns the registry shared by all connections of this controller
+    ///
+    /// Override this to share the registry with code outside of the callbacks.
+    fn registry(&self) -> Option<DatapathRegistry> {
+        None
+    }
     fn packet_in_handler(
         &mut self,
         xid: u32,
         packetin: PacketInEvent,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl Future<Output = ()> + Send;
     fn new() -> Self;
 
@@ -63,7 +68,7 @@ pub trait ControllerFrame10: Send {
     fn request_handler(
         &mut self,
         buf: &mut Vec<u8>,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl Future<Output = ()> + Send
     where
         Self: Sync,
@@ -74,24 +79,60 @@ pub trait ControllerFrame10: Send {
                 Some(header) => header,
                 None => return,
             };
-            let mut payload = vec![0u8; pkt_size];
-            let _ = stream.read(&mut payload).await;
+            let header_size = ofp.header_size();
+            if buf.len() < header_size + pkt_size {
+                return;
+            }
+            let payload = buf[header_size..header_size + pkt_size].to_vec();
             let message = ofp.msg_parse(message as u8);
+
+            // Replies awaited through `SwitchHandle::request` skip the callbacks
+            if !message.is_unsolicited()
+                && switch.complete_request(xid, message.to_int(), payload.clone())
+            {
+                return;
+            }
+
             match message {
-                Msg::Hello => self.hello_handler(xid, stream).await,
+                Msg::Hello => self.hello_handler(xid, switch).await,
                 Msg::Error => {
                     if let Ok(error) = ErrorEvent::parse(&payload) {
                         self.error_handler(error)
                     }
                 }
                 Msg::EchoRequest => {
-                    self.echo_request_handler(xid, EchoRequestEvent::new(payload), stream)
+                    self.echo_request_handler(xid, EchoRequestEvent::new(payload), switch)
                         .await
                 }
+                Msg::FeaturesReply => {
+                    if let Ok(features) = FeaturesReplyEvent::parse(&payload) {
+                        switch.set_datapath_id(features.datapath_id);
+                        let datapath = Datapath::new(switch.clone(), features.clone());
+                        switch.registry().insert(datapath.clone());
+                        self.switch_connected_handler(datapath).await;
+                        self.switch_features_handler(xid, features, switch).await
+                    }
+                }
                 Msg::PacketIn => match PacketInEvent::parse(&payload) {
-                    Ok(pkt_in) => self.packet_in_handler(xid, pkt_in, stream).await,
+                    Ok(pkt_in) => self.packet_in_handler(xid, pkt_in, switch).await,
                     Err(_) => (),
                 },
+                Msg::FlowRemove => {
+                    if let Ok(flow_removed) = FlowRemovedEvent::parse(&payload) {
+                        self.flow_removed_handler(xid, flow_removed, switch).await
+                    }
+                }
+                Msg::PortStatus => {
+                    if let Ok(port_status) = PortStatusEvent::parse(&payload) {
+                        switch.record_port_status(&port_status);
+                        self.port_status_handler(xid, port_status, switch).await
+                    }
+                }
+                Msg::Vendor => {
+                    if let Ok(message) = VendorEvent::parse(&payload) {
+                        self.vendor_handler(xid, message, switch).await
+                    }
+                }
                 _ => (),
             }
         }
@@ -101,33 +142,25 @@ pub trait ControllerFrame10: Send {
         &self,
         msg: MSM,
         xid: u32,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl Future<Output = ()> + Send
     where
         Self: Sync,
     {
         async move {
-            let ofp = self.ofp();
-            let mut header_bytes: Vec<u8> = Vec::new();
-            let mut body_bytes: Vec<u8> = Vec::new();
-
-            msg.marshal(&mut body_bytes);
-            let ofp_header = ofp.header(msg.msg_usize() as u8, body_bytes.len() as u16, xid);
-            ofp_header.marshal(&mut header_bytes);
-            header_bytes.append(&mut body_bytes);
-            let _ = stream.write_all(&header_bytes).await;
+            let _ = switch.send_msg(msg, xid).await;
         }
     }
 
     /**
      * for handle message
      */
-    fn hello_handler(&self, xid: u32, stream: &mut TcpStream) -> impl Future<Output = ()> + Send
+    fn hello_handler(&self, xid: u32, switch: &SwitchHandle) -> impl Future<Output = ()> + Send
     where
         Self: Sync,
     {
         async move {
-            self.send_msg(self.ofp().fetures_req(), xid, stream).await;
+            self.send_msg(self.ofp().fetures_req(), xid, switch).await;
         }
     }
     fn error_handler(&self, error: ErrorEvent) {
@@ -137,14 +170,68 @@ pub trait ControllerFrame10: Send {
         &self,
         xid: u32,
         echo: EchoRequestEvent,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl Future<Output = ()> + Send
     where
         Self: Sync,
     {
         async move {
-            self.send_msg(EchoReplyEvent::new(echo.payload), xid, stream)
+            self.send_msg(EchoReplyEvent::new(echo.payload), xid, switch)
                 .await;
         }
     }
+
+    #[allow(unused)]
+    fn flow_removed_handler(
+        &mut self,
+        xid: u32,
+        flow_removed: FlowRemovedEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    #[allow(unused)]
+    fn port_status_handler(
+        &mut self,
+        xid: u32,
+        port_status: PortStatusEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles vendor messages that were not awaited through `SwitchHandle::request`
+    #[allow(unused)]
+    fn vendor_handler(
+        &mut self,
+        xid: u32,
+        message: VendorEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    #[allow(unused)]
+    fn switch_features_handler(
+        &self,
+        xid: u32,
+        features_reply: FeaturesReplyEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    #[allow(unused)]
+    fn switch_connected_handler(&mut self, datapath: Datapath) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    #[allow(unused)]
+    fn switch_disconnected_handler(
+        &mut self,
+        datapath: Datapath,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
 }
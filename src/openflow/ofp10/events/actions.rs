@@ -12,7 +12,7 @@
 //! - Size checking and controller action reordering
 
 use std::{
-    io::{BufRead, Cursor, Error},
+    io::{BufRead, Cursor, Error, Read},
     mem::size_of,
 };
 
@@ -50,6 +50,8 @@ pub enum ActionType {
     SetTpDst = 10,
     /// Forward packet to a specific queue
     Enqueue = 11,
+    /// Vendor defined action
+    Vendor = 0xffff,
 }
 
 /// Represents an OpenFlow 1.0 action with its associated parameters
@@ -80,6 +82,8 @@ pub enum Action {
     SetTpDest(u16),
     /// Forward packet to a specific queue
     Enqueue(PseudoPort, u32),
+    /// Vendor defined action: vendor id and body, padded to 8 bytes on the wire
+    Vendor(u32, Vec<u8>),
     /// Action that could not be parsed
     Unparsable,
 }
@@ -102,6 +106,7 @@ impl Action {
             Action::SetTpSrc(_) => ActionType::SetTpSrc,
             Action::SetTpDest(_) => ActionType::SetTpDst,
             Action::Enqueue(_, _) => ActionType::Enqueue,
+            Action::Vendor(_, _) => ActionType::Vendor,
             Action::Unparsable => panic!("Unparse Action to ActionType"),
         }
     }
@@ -124,6 +129,7 @@ impl Action {
             Action::SetTpSrc(_) => size_of::<(u16, u16)>(),
             Action::SetTpDest(_) => size_of::<(u16, u16)>(),
             Action::Enqueue(_, _) => size_of::<(u16, [u8; 6], u32)>(),
+            Action::Vendor(_, data) => (size_of::<u32>() + data.len()).next_multiple_of(8) - header,
             Action::Unparsable => 0,
         };
         header + body
@@ -166,6 +172,9 @@ impl Action {
             }
             Action::SetTos(n) => {
                 let _ = bytes.write_u8(*n);
+                for _ in 0..3 {
+                    let _ = bytes.write_u8(0);
+                }
             }
             Action::SetTpSrc(pt) | Action::SetTpDest(pt) => {
                 let _ = bytes.write_u16::<BigEndian>(*pt);
@@ -178,6 +187,11 @@ impl Action {
                 }
                 let _ = bytes.write_u32::<BigEndian>(*qid);
             }
+            Action::Vendor(vendor, data) => {
+                let _ = bytes.write_u32::<BigEndian>(*vendor);
+                bytes.extend_from_slice(data);
+                bytes.resize(bytes.len() + self.length() - 8 - data.len(), 0);
+            }
             Action::Unparsable => (),
         }
     }
@@ -212,7 +226,7 @@ impl Action {
     /// Result containing either the parsed action or an error
     pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Action, Error> {
         let action_code = bytes.read_u16::<BigEndian>()?;
-        let _ = bytes.read_u16::<BigEndian>()?;
+        let length = bytes.read_u16::<BigEndian>()? as usize;
         match action_code {
             t if t == (ActionType::Output as u16) => {
                 let port_code = bytes.read_u16::<BigEndian>()?;
@@ -283,7 +297,17 @@ impl Action {
                 let qid = bytes.read_u32::<BigEndian>()?;
                 Ok(Action::Enqueue(PseudoPort::new(pt, Some(0)), qid))
             }
-            _ => Ok(Action::Unparsable),
+            t if t == (ActionType::Vendor as u16) => {
+                let vendor = bytes.read_u32::<BigEndian>()?;
+                let mut data = vec![0; length.saturating_sub(8)];
+                bytes.read_exact(&mut data)?;
+                Ok(Action::Vendor(vendor, data))
+            }
+            _ => {
+                // skip the body so that the following actions stay readable
+                bytes.consume(length.saturating_sub(4));
+                Ok(Action::Unparsable)
+            }
         }
     }
 }
@@ -0,0 +1,70 @@
+//! OpenFlow 1.0 Barrier Request
+//!
+//! This module implements the barrier request message for OpenFlow 1.0.
+//! A switch answers a barrier request only after it has processed every message
+//! received before it, so the reply confirms that earlier messages were applied.
+//!
+//! The module provides:
+//! - Barrier request event structure
+//! - Message marshaling implementation
+
+use crate::openflow::ofp10::{MessageMarshal, Msg};
+
+/// Represents a barrier request message to the switch
+///
+/// The message has no payload. The switch answers with a barrier reply that
+/// carries the xid of the request and no payload either.
+#[derive(Debug)]
+pub struct BarrierRequestEvent {}
+
+impl BarrierRequestEvent {
+    /// Creates a new barrier request event
+    ///
+    /// # Returns
+    /// A new BarrierRequestEvent instance
+    pub fn new() -> Self {
+        BarrierRequestEvent {}
+    }
+}
+
+impl Default for BarrierRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for BarrierRequestEvent {
+    /// Serializes the barrier request message into a byte buffer
+    ///
+    /// Barrier requests have no payload, so this is a no-op.
+    ///
+    /// # Arguments
+    /// * `_` - Unused byte buffer
+    fn marshal(&self, _: &mut Vec<u8>) {}
+
+    /// Returns the message type code for barrier request
+    ///
+    /// # Returns
+    /// The Msg::BarrierRequest variant
+    fn msg_code(&self) -> Msg {
+        Msg::BarrierRequest
+    }
+
+    /// Returns the size of the message payload
+    ///
+    /// Barrier requests have no payload, so this returns 0.
+    ///
+    /// # Returns
+    /// 0 (no payload)
+    fn size_of(&self) -> usize {
+        0
+    }
+
+    /// Returns the message type code as a usize
+    ///
+    /// # Returns
+    /// The numeric value of the barrier request message type
+    fn msg_usize(&self) -> usize {
+        Msg::BarrierRequest as usize
+    }
+}
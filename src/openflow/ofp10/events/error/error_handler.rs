@@ -13,6 +13,7 @@ use byteorder::{BigEndian, ReadBytesExt};
 
 /// Represents an OpenFlow error event received from a switch.
 /// Contains the error type and any additional payload data associated with the error.
+#[derive(Debug)]
 pub struct ErrorEvent {
     /// The specific type of error that occurred
     pub error_type: ErrorType,
@@ -0,0 +1,114 @@
+//! OpenFlow 1.0 Features Reply
+//!
+//! This module implements the features reply message handling for OpenFlow 1.0.
+//! Features replies are sent by the switch in response to a features request and
+//! describe the datapath, its buffers, tables and capabilities.
+//!
+//! The module provides:
+//! - Features reply event structure
+//! - Capabilities bitmap handling
+//! - Port list parsing
+//! - Message parsing implementation
+
+use std::io::{BufRead, Cursor, Error};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::openflow::ofp10::ofp_port::Port;
+
+/// Represents a features reply message from the switch
+///
+/// Contains the datapath id that identifies the switch, as well as the
+/// buffer, table and capability information it advertises.
+#[derive(Clone, Debug)]
+pub struct FeaturesReplyEvent {
+    /// Unique identifier for the datapath (switch)
+    pub datapath_id: u64,
+    /// Number of packets that can be buffered at once
+    pub n_buffers: u32,
+    /// Number of tables supported by the datapath
+    pub n_tables: u8,
+    /// Bitmap of switch capabilities
+    pub capabilities: Capabilities,
+    /// Bitmap of supported action types
+    pub actions: u32,
+    /// Physical ports of the switch
+    pub ports: Vec<Port>,
+}
+
+impl FeaturesReplyEvent {
+    /// Parses a features reply event from a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The byte buffer containing the features reply data
+    ///
+    /// # Returns
+    /// Result containing either the parsed FeaturesReplyEvent or an error
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let mut bytes = Cursor::new(bytes);
+        let datapath_id = bytes.read_u64::<BigEndian>()?;
+        let n_buffers = bytes.read_u32::<BigEndian>()?;
+        let n_tables = bytes.read_u8()?;
+        // padding
+        bytes.consume(3);
+        let capabilities = Capabilities::from(bytes.read_u32::<BigEndian>()?);
+        let actions = bytes.read_u32::<BigEndian>()?;
+        let mut ports = Vec::new();
+        while (bytes.get_ref().len() as u64) - bytes.position() >= Port::SIZE as u64 {
+            ports.push(Port::parse(&mut bytes)?);
+        }
+        Ok(Self {
+            datapath_id,
+            n_buffers,
+            n_tables,
+            capabilities,
+            actions,
+            ports,
+        })
+    }
+}
+
+/// Represents the capabilities advertised by an OpenFlow 1.0 switch
+#[derive(Clone, Debug)]
+pub struct Capabilities {
+    /// Support for flow statistics
+    pub flow_stats: bool,
+    /// Support for table statistics
+    pub table_stats: bool,
+    /// Support for port statistics
+    pub port_stats: bool,
+    /// Support for 802.1d spanning tree
+    pub stp: bool,
+    /// Support for IP reassembly
+    pub ip_reasm: bool,
+    /// Support for queue statistics
+    pub queue_stats: bool,
+    /// Support for matching IP addresses in ARP packets
+    pub arp_match_ip: bool,
+}
+
+impl From<u32> for Capabilities {
+    fn from(value: u32) -> Self {
+        Self {
+            flow_stats: value & 1 == 1,
+            table_stats: value >> 1 & 1 == 1,
+            port_stats: value >> 2 & 1 == 1,
+            stp: value >> 3 & 1 == 1,
+            ip_reasm: value >> 5 & 1 == 1,
+            queue_stats: value >> 6 & 1 == 1,
+            arp_match_ip: value >> 7 & 1 == 1,
+        }
+    }
+}
+
+impl From<Capabilities> for u32 {
+    fn from(value: Capabilities) -> Self {
+        (value.flow_stats as u32)
+            | (value.table_stats as u32) << 1
+            | (value.port_stats as u32) << 2
+            | (value.stp as u32) << 3
+            | (value.ip_reasm as u32) << 5
+            | (value.queue_stats as u32) << 6
+            | (value.arp_match_ip as u32) << 7
+    }
+}
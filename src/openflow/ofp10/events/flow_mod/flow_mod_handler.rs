@@ -103,21 +103,157 @@ impl FlowModEvent {
         match_fileds: MatchFields,
         actions: Vec<Action>,
         buffer_id: Option<u32>,
+    ) -> Self {
+        let mut flow = Self::new(FlowModCommand::Add, priority, match_fileds, actions);
+        flow.buffer_id = buffer_id;
+        flow
+    }
+
+    /// Creates a new flow modification event for modifying flow entries
+    ///
+    /// Replaces the actions of every flow entry matching the fields, regardless
+    /// of its priority. Counters and timeouts are kept.
+    ///
+    /// # Arguments
+    /// * `match_fields` - Fields selecting the flow entries to modify
+    /// * `actions` - New actions of the flow entries
+    pub fn modify_flow(match_fields: MatchFields, actions: Vec<Action>) -> Self {
+        Self::new(FlowModCommand::Modify, 0, match_fields, actions)
+    }
+
+    /// Creates a new flow modification event for modifying a single flow entry
+    ///
+    /// Only the entry with exactly the same match fields and priority is modified.
+    ///
+    /// # Arguments
+    /// * `priority` - Priority of the flow entry
+    /// * `match_fields` - Match fields of the flow entry
+    /// * `actions` - New actions of the flow entry
+    pub fn modify_flow_strict(
+        priority: u16,
+        match_fields: MatchFields,
+        actions: Vec<Action>,
+    ) -> Self {
+        Self::new(
+            FlowModCommand::ModifyStrict,
+            priority,
+            match_fields,
+            actions,
+        )
+    }
+
+    /// Creates a new flow modification event for deleting flow entries
+    ///
+    /// Deletes every flow entry matching the fields, regardless of its priority.
+    ///
+    /// # Arguments
+    /// * `match_fields` - Fields selecting the flow entries to delete
+    pub fn delete_flow(match_fields: MatchFields) -> Self {
+        Self::new(FlowModCommand::Delete, 0, match_fields, Vec::new())
+    }
+
+    /// Creates a new flow modification event for deleting a single flow entry
+    ///
+    /// Only the entry with exactly the same match fields and priority is deleted.
+    ///
+    /// # Arguments
+    /// * `priority` - Priority of the flow entry
+    /// * `match_fields` - Match fields of the flow entry
+    pub fn delete_flow_strict(priority: u16, match_fields: MatchFields) -> Self {
+        Self::new(
+            FlowModCommand::DeleteStrict,
+            priority,
+            match_fields,
+            Vec::new(),
+        )
+    }
+
+    /// Creates a new flow modification event deleting every flow entry
+    pub fn delete_all() -> Self {
+        Self::delete_flow(MatchFields::match_all())
+    }
+
+    /// Creates a new flow modification event with default settings
+    ///
+    /// # Arguments
+    /// * `command` - The type of flow modification command
+    /// * `priority` - Priority of the flow entry
+    /// * `match_fields` - Match fields for the flow entry
+    /// * `actions` - Actions to apply to matching packets
+    fn new(
+        command: FlowModCommand,
+        priority: u16,
+        match_fields: MatchFields,
+        actions: Vec<Action>,
     ) -> Self {
         Self {
-            command: FlowModCommand::Add,
-            match_fields: match_fileds,
+            command,
+            match_fields,
             priority,
             actions,
             cookie: 0,
             idle_timeout: Timeout::Permanent,
             hard_timeout: Timeout::Permanent,
             flags: FlowModFlags::all_false(),
-            buffer_id,
+            buffer_id: None,
             out_port: None,
         }
     }
 
+    /// Sets the idle timeout of the flow entry
+    ///
+    /// # Arguments
+    /// * `timeout` - Time without matching packets after which the entry expires
+    pub fn idle_timeout(mut self, timeout: Timeout) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Sets the hard timeout of the flow entry
+    ///
+    /// # Arguments
+    /// * `timeout` - Time after which the entry expires, matching packets or not
+    pub fn hard_timeout(mut self, timeout: Timeout) -> Self {
+        self.hard_timeout = timeout;
+        self
+    }
+
+    /// Sets the cookie of the flow entry
+    ///
+    /// # Arguments
+    /// * `cookie` - Opaque controller-issued identifier
+    pub fn cookie(mut self, cookie: u64) -> Self {
+        self.cookie = cookie;
+        self
+    }
+
+    /// Restricts delete commands to flow entries outputting to a port
+    ///
+    /// # Arguments
+    /// * `out_port` - Required output port
+    pub fn out_port(mut self, out_port: PseudoPort) -> Self {
+        self.out_port = Some(out_port);
+        self
+    }
+
+    /// Sets the flags of the flow entry
+    ///
+    /// # Arguments
+    /// * `flags` - Flow modification flags
+    pub fn flags(mut self, flags: FlowModFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Applies the flow entry to a packet buffered by the switch
+    ///
+    /// # Arguments
+    /// * `buffer_id` - ID of the buffered packet
+    pub fn buffer_id(mut self, buffer_id: u32) -> Self {
+        self.buffer_id = Some(buffer_id);
+        self
+    }
+
     /// Parses a flow modification event from a byte buffer
     ///
     /// # Arguments
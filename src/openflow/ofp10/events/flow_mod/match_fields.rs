@@ -10,7 +10,7 @@
 //! - IP address masking
 //! - Serialization/deserialization of match fields
 
-use std::io::{BufRead, Cursor, Error};
+use std::io::{BufRead, Cursor, Error, Read};
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
@@ -312,98 +312,61 @@ impl MatchFields {
     /// # Returns
     /// Result containing either the parsed MatchFields or an error
     pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<MatchFields, Error> {
+        // ofp_match has a fixed layout: wildcarded fields are still on the wire
         let wildcards = Wildcards::parse(bytes.read_u32::<BigEndian>()?);
-        let in_port = if wildcards.in_port {
-            None
-        } else {
-            Some(bytes.read_u16::<BigEndian>()?)
-        };
-        let mac_src = if wildcards.mac_src {
-            None
-        } else {
-            let mut arr: [u8; 6] = [0; 6];
-            for i in 0..6 {
-                arr[i] = bytes.read_u8()?;
-            }
-            Some(MacAddr::new(arr))
-        };
-        let mac_dest = if wildcards.mac_dest {
-            None
-        } else {
-            let mut arr: [u8; 6] = [0; 6];
-            for i in 0..6 {
-                arr[i] = bytes.read_u8()?;
-            }
-            Some(MacAddr::new(arr))
-        };
-        let vlan_vid = if wildcards.vlan_vid {
-            None
-        } else {
-            let vid = bytes.read_u16::<BigEndian>()?;
-            if vid == 0xfff {
-                None
-            } else {
-                Some(bytes.read_u16::<BigEndian>()?)
-            }
-        };
-        let vlan_pcp = if wildcards.vlan_pcp {
-            None
-        } else {
-            Some(bytes.read_u8()?)
-        };
+        let in_port = bytes.read_u16::<BigEndian>()?;
+        let mut mac_src = [0u8; 6];
+        bytes.read_exact(&mut mac_src)?;
+        let mut mac_dest = [0u8; 6];
+        bytes.read_exact(&mut mac_dest)?;
+        let vlan_vid = bytes.read_u16::<BigEndian>()?;
+        let vlan_pcp = bytes.read_u8()?;
         bytes.consume(1);
-        let ethernet_type = if wildcards.ethernet_type {
-            None
-        } else {
-            Some(bytes.read_u16::<BigEndian>()?)
-        };
-        let tos = if wildcards.tos {
-            None
-        } else {
-            Some(bytes.read_u8()?)
-        };
-        let protocol = if wildcards.protocol {
-            None
-        } else {
-            Some(bytes.read_u8()?)
-        };
+        let ethernet_type = bytes.read_u16::<BigEndian>()?;
+        let tos = bytes.read_u8()?;
+        let protocol = bytes.read_u8()?;
         bytes.consume(2);
-        let ip_src = if wildcards.ip_src >= 32 {
+        let ip_src = bytes.read_u32::<BigEndian>()?;
+        let ip_dest = bytes.read_u32::<BigEndian>()?;
+        let transport_src = bytes.read_u16::<BigEndian>()?;
+        let transport_dest = bytes.read_u16::<BigEndian>()?;
+
+        let in_port = (!wildcards.in_port).then_some(in_port);
+        let mac_src = (!wildcards.mac_src).then(|| MacAddr::new(mac_src));
+        let mac_dest = (!wildcards.mac_dest).then(|| MacAddr::new(mac_dest));
+        let vlan_vid = if wildcards.vlan_vid || vlan_vid == 0xfff {
             None
-        } else if wildcards.ip_src == 0 {
-            Some(Mask {
-                ip: bytes.read_u32::<BigEndian>()?,
-                mask: None,
-            })
         } else {
-            Some(Mask {
-                ip: bytes.read_u32::<BigEndian>()?,
-                mask: Some(wildcards.ip_src),
-            })
+            Some(vlan_vid)
         };
-        let ip_dest = if wildcards.ip_dest >= 32 {
-            None
-        } else if wildcards.ip_dest == 0 {
-            Some(Mask {
-                ip: bytes.read_u32::<BigEndian>()?,
+        let vlan_pcp = (!wildcards.vlan_pcp).then_some(vlan_pcp);
+        let ethernet_type = (!wildcards.ethernet_type).then_some(ethernet_type);
+        let tos = (!wildcards.tos).then_some(tos);
+        let protocol = (!wildcards.protocol).then_some(protocol);
+        let ip_src = match wildcards.ip_src {
+            n if n >= 32 => None,
+            0 => Some(Mask {
+                ip: ip_src,
                 mask: None,
-            })
-        } else {
-            Some(Mask {
-                ip: bytes.read_u32::<BigEndian>()?,
-                mask: Some(wildcards.ip_dest),
-            })
-        };
-        let transport_src = if wildcards.transport_src {
-            None
-        } else {
-            Some(bytes.read_u16::<BigEndian>()?)
+            }),
+            n => Some(Mask {
+                ip: ip_src,
+                mask: Some(n),
+            }),
         };
-        let transport_dest = if wildcards.transport_dest {
-            None
-        } else {
-            Some(bytes.read_u16::<BigEndian>()?)
+        let ip_dest = match wildcards.ip_dest {
+            n if n >= 32 => None,
+            0 => Some(Mask {
+                ip: ip_dest,
+                mask: None,
+            }),
+            n => Some(Mask {
+                ip: ip_dest,
+                mask: Some(n),
+            }),
         };
+        let transport_src = (!wildcards.transport_src).then_some(transport_src);
+        let transport_dest = (!wildcards.transport_dest).then_some(transport_dest);
         Ok(MatchFields {
             in_port,
             mac_src,
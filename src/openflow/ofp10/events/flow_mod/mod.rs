@@ -11,7 +11,7 @@
 //! - `flow_mod_flags`: Flow modification flags and options
 
 pub mod flow_mod_handler;
-pub use flow_mod_handler::FlowModEvent;
+pub use flow_mod_handler::{FlowModEvent, Timeout};
 
 pub mod command;
 pub use command::FlowModCommand;
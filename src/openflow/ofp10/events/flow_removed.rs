@@ -0,0 +1,106 @@
+//! OpenFlow 1.0 Flow-Removed Event
+//!
+//! This module implements the flow-removed event handling for OpenFlow 1.0.
+//! Flow-removed events are sent by the switch to the controller when a flow
+//! entry installed with the `send_flow_rem` flag times out or is deleted.
+//!
+//! The module provides:
+//! - Flow-removed reason enumeration
+//! - Flow-removed event structure and parsing
+
+use std::io::{BufRead, Cursor, Error};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use super::{MatchFields, Timeout};
+
+/// Represents the reason why a flow entry was removed
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+pub enum FlowRemovedReason {
+    /// Flow idle time exceeded idle_timeout
+    IdleTimeout,
+    /// Time exceeded hard_timeout
+    HardTimeout,
+    /// Evicted by a DELETE flow mod
+    Delete,
+    /// Unknown reason code
+    Unknown(u8),
+}
+
+impl FlowRemovedReason {
+    /// Creates a new FlowRemovedReason from a reason code
+    ///
+    /// # Arguments
+    /// * `code` - The numeric reason code
+    ///
+    /// # Returns
+    /// The corresponding FlowRemovedReason variant
+    fn new(code: u8) -> Self {
+        match code {
+            0 => FlowRemovedReason::IdleTimeout,
+            1 => FlowRemovedReason::HardTimeout,
+            2 => FlowRemovedReason::Delete,
+            t => FlowRemovedReason::Unknown(t),
+        }
+    }
+}
+
+/// Represents a flow-removed event in OpenFlow 1.0
+///
+/// Contains the match of the removed flow entry along with its final counters.
+pub struct FlowRemovedEvent {
+    /// Match fields of the flow entry
+    pub match_fields: MatchFields,
+    /// Opaque controller-issued identifier of the flow entry
+    pub cookie: u64,
+    /// Priority level of the flow entry
+    pub priority: u16,
+    /// Reason why the flow entry was removed
+    pub reason: FlowRemovedReason,
+    /// Time the flow entry was alive in seconds
+    pub duration_sec: u32,
+    /// Time the flow entry was alive in nanoseconds beyond duration_sec
+    pub duration_nsec: u32,
+    /// Idle timeout of the original flow mod
+    pub idle_timeout: Timeout,
+    /// Number of packets matched by the flow entry
+    pub packet_count: u64,
+    /// Number of bytes matched by the flow entry
+    pub byte_count: u64,
+}
+
+impl FlowRemovedEvent {
+    /// Parses a flow-removed event from a byte buffer
+    ///
+    /// # Arguments
+    /// * `payload` - The byte buffer containing the flow-removed event data
+    ///
+    /// # Returns
+    /// Result containing either the parsed FlowRemovedEvent or an error
+    pub fn parse(payload: &[u8]) -> Result<FlowRemovedEvent, Error> {
+        let mut bytes = Cursor::new(payload.to_vec());
+        let match_fields = MatchFields::parse(&mut bytes)?;
+        let cookie = bytes.read_u64::<BigEndian>()?;
+        let priority = bytes.read_u16::<BigEndian>()?;
+        let reason = FlowRemovedReason::new(bytes.read_u8()?);
+        bytes.consume(1);
+        let duration_sec = bytes.read_u32::<BigEndian>()?;
+        let duration_nsec = bytes.read_u32::<BigEndian>()?;
+        let idle_timeout = Timeout::parse(bytes.read_u16::<BigEndian>()?);
+        bytes.consume(2);
+        let packet_count = bytes.read_u64::<BigEndian>()?;
+        let byte_count = bytes.read_u64::<BigEndian>()?;
+        Ok(FlowRemovedEvent {
+            match_fields,
+            cookie,
+            priority,
+            reason,
+            duration_sec,
+            duration_nsec,
+            idle_timeout,
+            packet_count,
+            byte_count,
+        })
+    }
+}
@@ -19,11 +19,20 @@ pub use error::ErrorEvent;
 pub mod packet_in;
 pub use packet_in::{PacketInEvent, PacketInReason};
 
+pub mod flow_removed;
+pub use flow_removed::{FlowRemovedEvent, FlowRemovedReason};
+
+pub mod port_status;
+pub use port_status::{PortStatusEvent, PortStatusReason};
+
 pub mod packet_out;
 pub use packet_out::PacketOutEvent;
 
 pub mod flow_mod;
-pub use flow_mod::{FlowModEvent, MatchFields};
+pub use flow_mod::{FlowModCommand, FlowModEvent, FlowModFlags, MatchFields, Timeout};
+
+pub mod port_mod;
+pub use port_mod::PortModEvent;
 
 pub mod actions;
 pub use actions::Action;
@@ -31,9 +40,18 @@ pub use actions::Action;
 pub mod hello;
 pub use hello::HelloEvent;
 
+pub mod barrier;
+pub use barrier::BarrierRequestEvent;
+
+pub mod switch_config;
+pub use switch_config::{FragHandling, GetConfigRequestEvent, SetConfigEvent, SwitchConfig};
+
 pub mod features_req;
 pub use features_req::FeaturesReqEvent;
 
+pub mod features_reply;
+pub use features_reply::FeaturesReplyEvent;
+
 pub mod payload;
 pub use payload::Payload;
 
@@ -42,3 +60,6 @@ pub use echo_request::EchoRequestEvent;
 
 pub mod echo_reply;
 pub use echo_reply::EchoReplyEvent;
+
+pub mod vendor;
+pub use vendor::VendorEvent;
@@ -0,0 +1,194 @@
+//! OpenFlow 1.0 Switch Configuration
+//!
+//! This module implements the switch configuration messages for OpenFlow 1.0.
+//! The switch configuration selects how IP fragments are handled and how many
+//! bytes of a packet are included in packet-in messages for unmatched packets.
+//!
+//! The module provides:
+//! - Fragment handling enumeration
+//! - Switch configuration structure and parsing
+//! - Get config request and set config events
+
+use std::io::{Cursor, Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp10::{MessageMarshal, Msg};
+
+/// Handling of IP fragments (`ofp_config_flags`)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(u16)]
+pub enum FragHandling {
+    /// No special handling for fragments
+    #[default]
+    Normal = 0,
+    /// Drop fragments
+    Drop = 1,
+    /// Reassemble fragments, only if the switch supports it
+    Reassemble = 2,
+}
+
+impl TryFrom<u16> for FragHandling {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value & 3 {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Drop),
+            2 => Ok(Self::Reassemble),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown fragment handling {}", value),
+            )),
+        }
+    }
+}
+
+impl From<FragHandling> for u16 {
+    fn from(value: FragHandling) -> Self {
+        value as u16
+    }
+}
+
+/// Represents the configuration of a switch (`ofp_switch_config`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwitchConfig {
+    /// Handling of IP fragments
+    pub frag: FragHandling,
+    /// Max bytes of an unmatched packet sent to the controller
+    pub miss_send_len: u16,
+}
+
+impl SwitchConfig {
+    /// Size of the configuration on the wire
+    pub const SIZE: usize = 4;
+
+    /// Creates a new switch configuration
+    ///
+    /// # Arguments
+    /// * `frag` - Handling of IP fragments
+    /// * `miss_send_len` - Max bytes of a packet sent to the controller
+    ///
+    /// # Returns
+    /// A new SwitchConfig instance
+    pub fn new(frag: FragHandling, miss_send_len: u16) -> Self {
+        Self {
+            frag,
+            miss_send_len,
+        }
+    }
+
+    /// Parses a switch configuration from a get config reply body
+    ///
+    /// # Arguments
+    /// * `payload` - The byte buffer containing the reply body
+    ///
+    /// # Returns
+    /// Result containing either the parsed SwitchConfig or an error
+    pub fn parse(payload: &[u8]) -> Result<Self, Error> {
+        let mut bytes = Cursor::new(payload);
+        let frag = FragHandling::try_from(bytes.read_u16::<BigEndian>()?)?;
+        let miss_send_len = bytes.read_u16::<BigEndian>()?;
+        Ok(Self::new(frag, miss_send_len))
+    }
+
+    /// Serializes the configuration into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - Mutable reference to the byte buffer to write to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u16::<BigEndian>(self.frag.into());
+        let _ = bytes.write_u16::<BigEndian>(self.miss_send_len);
+    }
+}
+
+/// The configuration a switch starts with: normal fragments and 128 byte packet-ins
+impl Default for SwitchConfig {
+    fn default() -> Self {
+        Self::new(FragHandling::Normal, 128)
+    }
+}
+
+/// Represents a get config request message to the switch
+#[derive(Debug)]
+pub struct GetConfigRequestEvent {}
+
+impl GetConfigRequestEvent {
+    /// Creates a new get config request event
+    ///
+    /// # Returns
+    /// A new GetConfigRequestEvent instance
+    pub fn new() -> Self {
+        GetConfigRequestEvent {}
+    }
+}
+
+impl Default for GetConfigRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for GetConfigRequestEvent {
+    /// Get config requests have no payload, so this is a no-op.
+    fn marshal(&self, _: &mut Vec<u8>) {}
+
+    /// Returns the message type code for get config request
+    fn msg_code(&self) -> Msg {
+        Msg::ConfigRequest
+    }
+
+    /// Returns the size of the message payload, always 0
+    fn size_of(&self) -> usize {
+        0
+    }
+
+    /// Returns the message type code as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::ConfigRequest as usize
+    }
+}
+
+/// Represents a set config message to the switch
+pub struct SetConfigEvent {
+    /// Configuration to apply to the switch
+    pub config: SwitchConfig,
+}
+
+impl SetConfigEvent {
+    /// Creates a new set config event
+    ///
+    /// # Arguments
+    /// * `config` - Configuration to apply to the switch
+    ///
+    /// # Returns
+    /// A new SetConfigEvent instance
+    pub fn new(config: SwitchConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl MessageMarshal for SetConfigEvent {
+    /// Serializes the set config message into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - Mutable reference to the byte buffer to write to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        self.config.marshal(bytes);
+    }
+
+    /// Returns the message type code for set config
+    fn msg_code(&self) -> Msg {
+        Msg::SetConfig
+    }
+
+    /// Returns the size of the message payload
+    fn size_of(&self) -> usize {
+        SwitchConfig::SIZE
+    }
+
+    /// Returns the message type code as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::SetConfig as usize
+    }
+}
@@ -0,0 +1,108 @@
+//! OpenFlow 1.0 Vendor Message
+//!
+//! This module implements the vendor message for OpenFlow 1.0. Vendor messages carry
+//! extensions outside of the specification, identified by a vendor id. The body is kept
+//! as raw bytes; `ExperimenterRegistry` decodes it for registered vendors.
+//!
+//! The module provides:
+//! - Vendor event structure
+//! - Message parsing and marshaling implementation
+
+use std::io::{Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp10::{MessageMarshal, Msg};
+
+/// Represents a vendor message
+#[derive(Clone, Debug, PartialEq)]
+pub struct VendorEvent {
+    /// Vendor id
+    pub vendor: u32,
+    /// Vendor defined body
+    pub data: Vec<u8>,
+}
+
+impl VendorEvent {
+    /// Creates a new vendor event
+    ///
+    /// # Arguments
+    /// * `vendor` - Vendor id
+    /// * `data` - Vendor defined body
+    ///
+    /// # Returns
+    /// A new VendorEvent instance
+    pub fn new(vendor: u32, data: Vec<u8>) -> Self {
+        Self { vendor, data }
+    }
+
+    /// Parses a vendor message
+    ///
+    /// # Arguments
+    /// * `bytes` - The message body, header excluded
+    ///
+    /// # Returns
+    /// Result containing either the parsed event or an error
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 4 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("vendor message too short: {}", bytes.len()),
+            ));
+        }
+        Ok(Self {
+            vendor: (&bytes[..4]).read_u32::<BigEndian>()?,
+            data: bytes[4..].to_vec(),
+        })
+    }
+
+    /// Splits the body into the vendor defined message type and the rest
+    ///
+    /// Vendors such as Nicira start the body with a 32 bit message type, the layout
+    /// OpenFlow 1.3 adopted for experimenter messages.
+    ///
+    /// # Returns
+    /// The message type and the remaining body, `None` if the body is too short
+    pub fn split_type(&self) -> Option<(u32, &[u8])> {
+        if self.data.len() < 4 {
+            return None;
+        }
+        let exp_type = (&self.data[..4]).read_u32::<BigEndian>().ok()?;
+        Some((exp_type, &self.data[4..]))
+    }
+}
+
+impl MessageMarshal for VendorEvent {
+    /// Serializes the vendor message into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - Byte buffer to write to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u32::<BigEndian>(self.vendor);
+        bytes.extend_from_slice(&self.data);
+    }
+
+    /// Returns the message type code for vendor messages
+    ///
+    /// # Returns
+    /// The Msg::Vendor variant
+    fn msg_code(&self) -> Msg {
+        Msg::Vendor
+    }
+
+    /// Returns the size of the message payload
+    ///
+    /// # Returns
+    /// Size of the vendor id and the body in bytes
+    fn size_of(&self) -> usize {
+        4 + self.data.len()
+    }
+
+    /// Returns the message type code as a usize
+    ///
+    /// # Returns
+    /// The numeric value of the vendor message type
+    fn msg_usize(&self) -> usize {
+        Msg::Vendor as usize
+    }
+}
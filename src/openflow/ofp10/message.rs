@@ -79,4 +79,14 @@ impl Msg {
         }
         unsafe { transmute::<u8, Msg>(msg_code) }
     }
+
+    /// Returns true for messages the switch sends on its own rather than as a reply
+    ///
+    /// Such messages never complete a request, even if their xid happens to match one.
+    pub fn is_unsolicited(&self) -> bool {
+        matches!(
+            self,
+            Msg::Hello | Msg::EchoRequest | Msg::PacketIn | Msg::FlowRemove | Msg::PortStatus
+        )
+    }
 }
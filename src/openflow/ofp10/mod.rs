@@ -12,12 +12,13 @@ pub mod message;
 pub use message::Msg;
 
 pub mod ofp_port;
-pub use ofp_port::PseudoPort;
+pub use ofp_port::{Port, PortConfig, PortFeatures, PortState, PseudoPort};
 
 pub mod events;
 pub use events::{
-    Action, EchoReplyEvent, EchoRequestEvent, ErrorEvent, FlowModEvent, HelloEvent, MatchFields,
-    PacketInEvent, PacketOutEvent,
+    Action, BarrierRequestEvent, EchoReplyEvent, EchoRequestEvent, ErrorEvent, FeaturesReplyEvent,
+    FlowModEvent, FlowRemovedEvent, GetConfigRequestEvent, HelloEvent, MatchFields, PacketInEvent,
+    PacketOutEvent, PortModEvent, PortStatusEvent, SetConfigEvent, SwitchConfig, VendorEvent,
 };
 
 pub mod ofp_header;
@@ -29,6 +30,12 @@ pub use ofp_manager::Openflow10;
 pub mod controller_frame;
 pub use controller_frame::ControllerFrame10;
 
+pub mod registry;
+pub use registry::{Datapath, DatapathRegistry};
+
+pub mod switch_handle;
+pub use switch_handle::{Reply, RequestError, SwitchHandle};
+
 pub mod tcp_listener;
 pub use tcp_listener::tcp_listener_handler;
 
@@ -1,7 +1,10 @@
 // OpenFlow Protocol v1.0 manager implementation
 // This struct handles the core functionality for OpenFlow v1.0 protocol operations
 use super::{
-    events::{Action, FeaturesReqEvent, Payload},
+    events::{
+        Action, BarrierRequestEvent, FeaturesReqEvent, GetConfigRequestEvent, Payload,
+        SetConfigEvent, SwitchConfig, VendorEvent,
+    },
     ofp_header::OfpHeader,
     HelloEvent, Msg, OfpMsgEvent, OpenflowHeader, PacketOutEvent,
 };
@@ -39,6 +42,31 @@ impl OfpMsgEvent for Openflow10 {
         FeaturesReqEvent::new()
     }
 
+    /// Creates a new Barrier Request event to confirm earlier messages were processed
+    fn barrier_req(&self) -> BarrierRequestEvent {
+        BarrierRequestEvent::new()
+    }
+
+    /// Creates a new Get Config Request event to read the switch configuration
+    fn get_config_req(&self) -> GetConfigRequestEvent {
+        GetConfigRequestEvent::new()
+    }
+
+    /// Creates a new Set Config event
+    /// Parameters:
+    /// - config: Switch configuration to apply
+    fn set_config(&self, config: SwitchConfig) -> SetConfigEvent {
+        SetConfigEvent::new(config)
+    }
+
+    /// Creates a new Vendor event
+    /// Parameters:
+    /// - vendor: Vendor id
+    /// - data: Vendor defined body
+    fn vendor(&self, vendor: u32, data: Vec<u8>) -> VendorEvent {
+        VendorEvent::new(vendor, data)
+    }
+
     /// Creates a new Packet Out event for sending packets through the switch
     /// Parameters:
     /// - port_id: Optional port number to send the packet out
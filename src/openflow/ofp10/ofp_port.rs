@@ -3,7 +3,11 @@
 //! This module defines the port types used in OpenFlow 1.0 protocol, including
 //! both physical ports and special ports used for packet forwarding and control.
 
-use byteorder::{BigEndian, WriteBytesExt};
+use std::io::{Error, Read};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{openflow::port_name, utils::MacAddr};
 
 /// Represents the standard OpenFlow 1.0 port numbers
 ///
@@ -123,3 +127,226 @@ impl PseudoPort {
         let _ = bytes.write_u16::<BigEndian>(port);
     }
 }
+
+/// Length of the port name field in bytes
+pub const OFP_MAX_PORT_NAME_LEN: usize = 16;
+
+/// Description of a physical switch port (`ofp_phy_port`)
+///
+/// Switches report this structure in the features reply and in port status messages.
+#[derive(Clone, Debug)]
+pub struct Port {
+    /// Port number
+    pub port_no: u16,
+    /// Hardware address of the port
+    pub hw_addr: MacAddr,
+    /// Human readable name of the port
+    pub name: String,
+    /// Port configuration flags
+    pub config: PortConfig,
+    /// Port state flags
+    pub state: PortState,
+    /// Current features
+    pub curr: PortFeatures,
+    /// Features being advertised by the port
+    pub advertised: PortFeatures,
+    /// Features supported by the port
+    pub supported: PortFeatures,
+    /// Features advertised by the peer
+    pub peer: PortFeatures,
+}
+
+impl Port {
+    /// Size of the port structure on the wire
+    pub const SIZE: usize = 48;
+
+    /// Parses a port description from a reader
+    ///
+    /// # Arguments
+    /// * `bytes` - Reader positioned at the start of the port structure
+    ///
+    /// # Returns
+    /// Result containing either the parsed Port or an error
+    pub fn parse(bytes: &mut impl Read) -> Result<Self, Error> {
+        let port_no = bytes.read_u16::<BigEndian>()?;
+        let mut mac = [0u8; 6];
+        bytes.read_exact(&mut mac)?;
+        let mut name = [0u8; OFP_MAX_PORT_NAME_LEN];
+        bytes.read_exact(&mut name)?;
+        Ok(Self {
+            port_no,
+            hw_addr: MacAddr::new(mac),
+            name: port_name(&name),
+            config: bytes.read_u32::<BigEndian>()?.into(),
+            state: bytes.read_u32::<BigEndian>()?.into(),
+            curr: bytes.read_u32::<BigEndian>()?.into(),
+            advertised: bytes.read_u32::<BigEndian>()?.into(),
+            supported: bytes.read_u32::<BigEndian>()?.into(),
+            peer: bytes.read_u32::<BigEndian>()?.into(),
+        })
+    }
+}
+
+/// Spanning tree state of a port
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum StpState {
+    /// Not learning or relaying frames
+    #[default]
+    Listen,
+    /// Learning but not relaying frames
+    Learn,
+    /// Learning and relaying frames
+    Forward,
+    /// Not part of the spanning tree
+    Block,
+}
+
+/// Bitmap of port configuration flags (`ofp_port_config`)
+///
+/// Switches report the current configuration in port descriptions; the controller
+/// changes it with port modification messages.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PortConfig {
+    /// Port is administratively down
+    pub port_down: bool,
+    /// Disable 802.1D spanning tree on the port
+    pub no_stp: bool,
+    /// Drop all packets except 802.1D spanning tree packets
+    pub no_recv: bool,
+    /// Drop received 802.1D spanning tree packets
+    pub no_recv_stp: bool,
+    /// Do not include the port when flooding
+    pub no_flood: bool,
+    /// Drop packets forwarded to the port
+    pub no_fwd: bool,
+    /// Do not send packet-in messages for the port
+    pub no_packet_in: bool,
+}
+
+impl From<u32> for PortConfig {
+    fn from(value: u32) -> Self {
+        Self {
+            port_down: value & 1 == 1,
+            no_stp: value >> 1 & 1 == 1,
+            no_recv: value >> 2 & 1 == 1,
+            no_recv_stp: value >> 3 & 1 == 1,
+            no_flood: value >> 4 & 1 == 1,
+            no_fwd: value >> 5 & 1 == 1,
+            no_packet_in: value >> 6 & 1 == 1,
+        }
+    }
+}
+
+impl From<PortConfig> for u32 {
+    fn from(value: PortConfig) -> Self {
+        (value.port_down as u32)
+            | (value.no_stp as u32) << 1
+            | (value.no_recv as u32) << 2
+            | (value.no_recv_stp as u32) << 3
+            | (value.no_flood as u32) << 4
+            | (value.no_fwd as u32) << 5
+            | (value.no_packet_in as u32) << 6
+    }
+}
+
+/// Bitmap of port state flags (`ofp_port_state`)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PortState {
+    /// No physical link present
+    pub link_down: bool,
+    /// Spanning tree state
+    pub stp: StpState,
+}
+
+impl From<u32> for PortState {
+    fn from(value: u32) -> Self {
+        Self {
+            link_down: value & 1 == 1,
+            stp: match value >> 8 & 3 {
+                0 => StpState::Listen,
+                1 => StpState::Learn,
+                2 => StpState::Forward,
+                _ => StpState::Block,
+            },
+        }
+    }
+}
+
+impl From<PortState> for u32 {
+    fn from(value: PortState) -> Self {
+        let stp = match value.stp {
+            StpState::Listen => 0,
+            StpState::Learn => 1,
+            StpState::Forward => 2,
+            StpState::Block => 3,
+        };
+        (value.link_down as u32) | stp << 8
+    }
+}
+
+/// Bitmap of port features (`ofp_port_features`)
+///
+/// Used for the current, advertised, supported and peer features of a port.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PortFeatures {
+    /// 10 Mb half-duplex rate support
+    pub rate_10mb_hd: bool,
+    /// 10 Mb full-duplex rate support
+    pub rate_10mb_fd: bool,
+    /// 100 Mb half-duplex rate support
+    pub rate_100mb_hd: bool,
+    /// 100 Mb full-duplex rate support
+    pub rate_100mb_fd: bool,
+    /// 1 Gb half-duplex rate support
+    pub rate_1gb_hd: bool,
+    /// 1 Gb full-duplex rate support
+    pub rate_1gb_fd: bool,
+    /// 10 Gb full-duplex rate support
+    pub rate_10gb_fd: bool,
+    /// Copper medium
+    pub copper: bool,
+    /// Fiber medium
+    pub fiber: bool,
+    /// Auto-negotiation
+    pub autoneg: bool,
+    /// Pause
+    pub pause: bool,
+    /// Asymmetric pause
+    pub pause_asym: bool,
+}
+
+impl From<u32> for PortFeatures {
+    fn from(value: u32) -> Self {
+        Self {
+            rate_10mb_hd: value & 1 == 1,
+            rate_10mb_fd: value >> 1 & 1 == 1,
+            rate_100mb_hd: value >> 2 & 1 == 1,
+            rate_100mb_fd: value >> 3 & 1 == 1,
+            rate_1gb_hd: value >> 4 & 1 == 1,
+            rate_1gb_fd: value >> 5 & 1 == 1,
+            rate_10gb_fd: value >> 6 & 1 == 1,
+            copper: value >> 7 & 1 == 1,
+            fiber: value >> 8 & 1 == 1,
+            autoneg: value >> 9 & 1 == 1,
+            pause: value >> 10 & 1 == 1,
+            pause_asym: value >> 11 & 1 == 1,
+        }
+    }
+}
+
+impl From<PortFeatures> for u32 {
+    fn from(value: PortFeatures) -> Self {
+        (value.rate_10mb_hd as u32)
+            | (value.rate_10mb_fd as u32) << 1
+            | (value.rate_100mb_hd as u32) << 2
+            | (value.rate_100mb_fd as u32) << 3
+            | (value.rate_1gb_hd as u32) << 4
+            | (value.rate_1gb_fd as u32) << 5
+            | (value.rate_10gb_fd as u32) << 6
+            | (value.copper as u32) << 7
+            | (value.fiber as u32) << 8
+            | (value.autoneg as u32) << 9
+            | (value.pause as u32) << 10
+            | (value.pause_asym as u32) << 11
+    }
+}
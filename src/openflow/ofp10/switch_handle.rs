@@ -0,0 +1,336 @@
+//! OpenFlow 1.0 Switch Handle
+//!
+//! This module provides the per-connection handle used to talk to a switch.
+//! The handle owns no socket itself: outgoing messages are marshaled and pushed
+//! into an mpsc channel that is drained by a dedicated writer task. Because of
+//! that, a handle can be cloned freely and used from timers, other switches'
+//! callbacks or any other task for as long as the connection is alive.
+
+use std::{
+    io::{Error, ErrorKind},
+    net::SocketAddr,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use tokio::{sync::mpsc::Sender, time::timeout};
+
+use crate::openflow::{framing::OFP_HEADER_SIZE, PendingRequests};
+
+use super::{
+    BarrierRequestEvent, DatapathRegistry, ErrorEvent, FeaturesReplyEvent, GetConfigRequestEvent,
+    MessageMarshal, Msg, OfpMsgEvent, Openflow10, OpenflowHeader, PortStatusEvent, SwitchConfig,
+};
+
+/// Error returned by requests awaited through `SwitchHandle::request`
+pub type RequestError = crate::openflow::RequestError<ErrorEvent>;
+
+/// Reply received for a request awaited through `SwitchHandle::request`
+pub struct Reply {
+    /// Type of the reply message
+    pub message: Msg,
+    /// Transaction ID shared by the request and the reply
+    pub xid: u32,
+    /// Body of the reply, header excluded
+    pub payload: Vec<u8>,
+}
+
+/// Cloneable handle to a single switch connection
+///
+/// The datapath id is only known once the switch has answered the features
+/// request, so it is shared between all clones of the handle and filled in
+/// when the features reply arrives. Every handle also carries the registry
+/// of the listener that accepted it, so any callback can reach other switches.
+#[derive(Clone)]
+pub struct SwitchHandle {
+    /// Datapath id reported in the features reply
+    datapath_id: Arc<OnceLock<u64>>,
+    /// Address of the switch side of the TCP connection
+    peer_addr: SocketAddr,
+    /// OpenFlow version used on this connection
+    version: u8,
+    /// Queue drained by the connection's writer task
+    sender: Sender<Vec<u8>>,
+    /// Registry shared by all connections of the listener
+    registry: DatapathRegistry,
+    /// Transaction ids and requests awaiting a reply on this connection
+    requests: PendingRequests,
+}
+
+impl SwitchHandle {
+    /// Creates a new handle for a connection
+    ///
+    /// # Arguments
+    /// * `peer_addr` - Address of the switch
+    /// * `version` - Negotiated OpenFlow version
+    /// * `sender` - Channel consumed by the writer task of the connection
+    /// * `registry` - Registry shared by all connections of the listener
+    pub fn new(
+        peer_addr: SocketAddr,
+        version: u8,
+        sender: Sender<Vec<u8>>,
+        registry: DatapathRegistry,
+    ) -> Self {
+        Self {
+            datapath_id: Arc::new(OnceLock::new()),
+            peer_addr,
+            version,
+            sender,
+            registry,
+            requests: PendingRequests::new(),
+        }
+    }
+
+    /// Returns the datapath id of the switch, if the features reply was received
+    pub fn datapath_id(&self) -> Option<u64> {
+        self.datapath_id.get().copied()
+    }
+
+    /// Records the datapath id reported by the switch
+    ///
+    /// The id can only be set once per connection; later calls are ignored.
+    pub fn set_datapath_id(&self, datapath_id: u64) {
+        let _ = self.datapath_id.set(datapath_id);
+    }
+
+    /// Returns the address of the switch
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.peer_addr
+    }
+
+    /// Returns the OpenFlow version negotiated on this connection
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the registry of all datapaths connected to the same listener
+    pub fn registry(&self) -> &DatapathRegistry {
+        &self.registry
+    }
+
+    /// Returns true if both handles refer to the same connection
+    pub fn same_connection(&self, other: &SwitchHandle) -> bool {
+        Arc::ptr_eq(&self.datapath_id, &other.datapath_id)
+    }
+
+    /// Returns a transaction id that is not used by any other request on this connection
+    pub fn next_xid(&self) -> u32 {
+        self.requests.next_xid()
+    }
+
+    /// Hands a received message to the request waiting for its xid
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID of the message
+    /// * `message` - Type of the message
+    /// * `payload` - Body of the message, header excluded
+    ///
+    /// # Returns
+    /// True if the message was consumed by a waiting request
+    pub fn complete_request(&self, xid: u32, message: u8, payload: Vec<u8>) -> bool {
+        self.requests.complete(xid, message, payload)
+    }
+
+    /// Hands a received message to the request waiting for it, if there is one
+    ///
+    /// Called by the reader of the connection before a message is queued for the
+    /// callbacks, so that replies arrive while a callback awaits them.
+    ///
+    /// # Arguments
+    /// * `message` - One complete message, header included
+    ///
+    /// # Returns
+    /// True if the message was consumed and must not be passed to the callbacks
+    pub(crate) fn take_reply(&self, message: &[u8]) -> bool {
+        if message.len() < OFP_HEADER_SIZE {
+            return false;
+        }
+        let msg = Msg::from(message[1]);
+        let xid = u32::from_be_bytes([message[4], message[5], message[6], message[7]]);
+        if msg.is_unsolicited() || !self.requests.is_pending(xid) {
+            return false;
+        }
+        self.complete_request(xid, msg.to_int(), message[OFP_HEADER_SIZE..].to_vec())
+    }
+
+    /// Fails every request still waiting on this connection with `RequestError::Disconnected`
+    pub fn close_requests(&self) {
+        self.requests.close();
+    }
+
+    /// Returns true while the connection to the switch is open
+    pub fn is_connected(&self) -> bool {
+        !self.sender.is_closed()
+    }
+
+    /// Queues already marshaled bytes for sending
+    ///
+    /// # Arguments
+    /// * `bytes` - A complete OpenFlow message, header included
+    pub async fn send_raw(&self, bytes: Vec<u8>) -> Result<(), Error> {
+        self.sender
+            .send(bytes)
+            .await
+            .map_err(|_| Error::new(ErrorKind::NotConnected, "switch connection is closed"))
+    }
+
+    /// Marshals a message with its OpenFlow header and queues it for sending
+    ///
+    /// # Arguments
+    /// * `msg` - The message to send
+    /// * `xid` - Transaction ID
+    pub async fn send_msg<MSM: MessageMarshal>(&self, msg: MSM, xid: u32) -> Result<(), Error> {
+        let ofp = Openflow10::new();
+        let mut header_bytes: Vec<u8> = Vec::new();
+        let mut body_bytes: Vec<u8> = Vec::new();
+
+        msg.marshal(&mut body_bytes);
+        let ofp_header = ofp.header(msg.msg_usize() as u8, body_bytes.len() as u16, xid);
+        ofp_header.marshal(&mut header_bytes);
+        header_bytes.append(&mut body_bytes);
+        self.send_raw(header_bytes).await
+    }
+
+    /// Sends a message and waits for the reply carrying the same xid
+    ///
+    /// The xid is allocated by the handle. A reply that completes the request is not
+    /// passed to the controller callbacks. Replies are picked up by the reader of the
+    /// connection, so a request may be awaited from within a callback.
+    ///
+    /// # Arguments
+    /// * `msg` - The request to send
+    /// * `wait` - How long to wait for the reply
+    ///
+    /// # Returns
+    /// The reply, or `RequestError::Switch` if the switch answered with an error message
+    pub async fn request<MSM: MessageMarshal>(
+        &self,
+        msg: MSM,
+        wait: Duration,
+    ) -> Result<Reply, RequestError> {
+        let xid = self.next_xid();
+        let receiver = self.requests.register(xid);
+        if self.send_msg(msg, xid).await.is_err() {
+            self.requests.cancel(xid);
+            return Err(RequestError::Disconnected);
+        }
+        let (message, payload) = match timeout(wait, receiver).await {
+            Ok(Ok(Ok(reply))) => reply,
+            Ok(Ok(Err(error))) => return Err(RequestError::Io(error)),
+            Ok(Err(_)) => return Err(RequestError::Disconnected),
+            Err(_) => {
+                self.requests.cancel(xid);
+                return Err(RequestError::Timeout);
+            }
+        };
+
+        match Msg::from(message) {
+            Msg::Error => Err(RequestError::Switch(ErrorEvent::parse(&payload)?)),
+            message => Ok(Reply {
+                message,
+                xid,
+                payload,
+            }),
+        }
+    }
+
+    /// Requests the features of the switch and waits for the reply
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn features(&self, wait: Duration) -> Result<FeaturesReplyEvent, RequestError> {
+        let reply = self.request(Openflow10::new().fetures_req(), wait).await?;
+        Ok(FeaturesReplyEvent::parse(&reply.payload)?)
+    }
+
+    /// Reads the configuration of the switch
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn switch_config(&self, wait: Duration) -> Result<SwitchConfig, RequestError> {
+        let reply = self.request(GetConfigRequestEvent::new(), wait).await?;
+        Ok(SwitchConfig::parse(&reply.payload)?)
+    }
+
+    /// Sends a barrier request and waits for the barrier reply
+    ///
+    /// Once this returns, the switch has processed every message sent before the barrier.
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn barrier(&self, wait: Duration) -> Result<(), RequestError> {
+        self.request(BarrierRequestEvent::new(), wait).await?;
+        Ok(())
+    }
+
+    /// Sends a batch of messages followed by a barrier and waits until they are applied
+    ///
+    /// Every message is sent with its own xid. The call resolves once the barrier reply
+    /// arrives, or early as soon as the switch answers any message of the batch with an
+    /// error. Other replies to messages of the batch are dropped.
+    ///
+    /// Messages of different types can be sent together as `Box<dyn MessageMarshal + Send>`.
+    ///
+    /// # Arguments
+    /// * `msgs` - Messages to send, in order
+    /// * `wait` - How long to wait for the whole batch to be applied
+    ///
+    /// # Returns
+    /// `RequestError::Switch` with the first error the switch reported for the batch
+    pub async fn send_batch<MSM, I>(&self, msgs: I, wait: Duration) -> Result<(), RequestError>
+    where
+        MSM: MessageMarshal,
+        I: IntoIterator<Item = MSM>,
+    {
+        let msgs: Vec<(u32, MSM)> = msgs.into_iter().map(|msg| (self.next_xid(), msg)).collect();
+        let barrier_xid = self.next_xid();
+        let mut xids: Vec<u32> = msgs.iter().map(|(xid, _)| *xid).collect();
+        xids.push(barrier_xid);
+        let mut replies = self.requests.register_batch(&xids);
+        let cancel = || xids.iter().for_each(|xid| self.requests.cancel(*xid));
+
+        for (xid, msg) in msgs {
+            if self.send_msg(msg, xid).await.is_err() {
+                cancel();
+                return Err(RequestError::Disconnected);
+            }
+        }
+        if self
+            .send_msg(BarrierRequestEvent::new(), barrier_xid)
+            .await
+            .is_err()
+        {
+            cancel();
+            return Err(RequestError::Disconnected);
+        }
+
+        let result = timeout(wait, async {
+            loop {
+                match replies.recv().await {
+                    Some((xid, _)) if xid == barrier_xid => return Ok(()),
+                    Some((_, Ok((message, payload)))) => {
+                        if let Msg::Error = Msg::from(message) {
+                            return Err(RequestError::Switch(ErrorEvent::parse(&payload)?));
+                        }
+                    }
+                    Some((_, Err(error))) => return Err(RequestError::Io(error)),
+                    None => return Err(RequestError::Disconnected),
+                }
+            }
+        })
+        .await;
+        cancel();
+        result.unwrap_or(Err(RequestError::Timeout))
+    }
+
+    /// Applies a port status message to the registry entry of this switch
+    ///
+    /// # Arguments
+    /// * `status` - A port status message received on this connection
+    pub(crate) fn record_port_status(&self, status: &PortStatusEvent) {
+        if let Some(datapath_id) = self.datapath_id() {
+            self.registry
+                .update(datapath_id, |dp| dp.apply_port_status(status));
+        }
+    }
+}
@@ -1,8 +1,12 @@
-use super::{ControllerFrame10, OfpMsgEvent};
-use crate::openflow::ofp10::HelloEvent;
+use super::{ControllerFrame10, DatapathRegistry, OfpMsgEvent, Openflow10, SwitchHandle};
+use crate::openflow::{handshake, spawn_writer, MessageFramer};
+use std::{io::Error, net::SocketAddr};
 use tokio::{
-    io::AsyncReadExt,
-    net::{TcpListener, TcpStream},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+    sync::mpsc::unbounded_channel,
 };
 
 /// Handles incoming TCP connections for OpenFlow v1.0 protocol
@@ -20,18 +24,18 @@ pub async fn tcp_listener_handler(
     // Bind to the specified address and start listening for connections
     let listener = TcpListener::bind(address).await?;
 
+    // All connections accepted by this listener share one datapath registry
+    let registry = controller.registry().unwrap_or_default();
+
     // Continuously accept new connections
     loop {
-        let (mut stream, _) = listener.accept().await?;
-        if let Ok(addr) = stream.peer_addr() {
-            println!("server has connection from {}", addr);
-        }
+        let (stream, addr) = listener.accept().await?;
+        println!("server has connection from {}", addr);
 
         // Clone the controller for this connection and spawn a new task to handle it
         let mut ctrl = controller.clone();
-        tokio::spawn(async move {
-            processing(&mut ctrl, &mut stream).await;
-        });
+        let registry = registry.clone();
+        tokio::spawn(async move { processing(&mut ctrl, stream, addr, registry).await });
     }
 }
 
@@ -40,29 +44,91 @@ pub async fn tcp_listener_handler(
 /// # Arguments
 /// * `ctrl` - The OpenFlow controller implementation
 /// * `stream` - The TCP stream for the connection
-async fn processing(ctrl: &mut (impl ControllerFrame10 + Clone + Sync), stream: &mut TcpStream) {
-    // Send initial Hello message to establish the connection
-    ctrl.send_msg(HelloEvent::new(), 0, stream).await;
+/// * `addr` - Address of the switch
+/// * `registry` - Registry shared by all connections of the listener
+async fn processing(
+    ctrl: &mut (impl ControllerFrame10 + Clone + Sync),
+    stream: TcpStream,
+    addr: SocketAddr,
+    registry: DatapathRegistry,
+) -> Result<(), Error> {
+    let (mut reader, mut writer) = stream.into_split();
 
-    // Get the size of OpenFlow header and create a buffer for reading messages
-    let ofp_size = ctrl.ofp().header_size();
-    let mut buffer = vec![0u8; ofp_size];
+    // Accumulate bytes until whole messages are available
+    let mut framer = MessageFramer::new();
 
-    // Main message processing loop
-    loop {
-        match stream.read(&mut buffer).await {
-            Ok(v) if v > 0 => {
-                // Process the received OpenFlow message
-                ctrl.request_handler(&mut buffer, stream).await;
-            }
-            Ok(_) => {
+    // Exchange Hello messages and refuse switches that do not speak this version
+    let version = Openflow10::ofp_version() as u8;
+    let hello_xid = match handshake(&mut reader, &mut writer, &mut framer, &[version]).await {
+        Ok((_, xid)) => xid,
+        Err(e) => {
+            println!("hello exchange with {} failed: {}", addr, e);
+            return Ok(());
+        }
+    };
+    serve(ctrl, reader, writer, framer, addr, registry, hello_xid).await
+}
+
+/// Serves a connection once the OpenFlow v1.0 protocol version has been negotiated
+///
+/// # Arguments
+/// * `ctrl` - The OpenFlow controller instance handling this connection
+/// * `reader` - The read half of the connection
+/// * `writer` - The write half of the connection
+/// * `framer` - Framer of the connection, possibly holding messages that arrived early
+/// * `addr` - Address of the switch
+/// * `registry` - Registry shared by all connections of the listener
+/// * `hello_xid` - Transaction ID of the switch's Hello
+///
+/// # Returns
+/// * `Result<(), std::io::Error>` - The read error that ended the connection, if any
+pub(crate) async fn serve(
+    ctrl: &mut (impl ControllerFrame10 + Clone + Sync),
+    mut reader: OwnedReadHalf,
+    writer: OwnedWriteHalf,
+    mut framer: MessageFramer,
+    addr: SocketAddr,
+    registry: DatapathRegistry,
+    hello_xid: u32,
+) -> Result<(), Error> {
+    // Hand the write half to a dedicated task so the handle can be used from anywhere
+    let (sender, writer_task) = spawn_writer(writer);
+    let switch = SwitchHandle::new(addr, Openflow10::ofp_version() as u8, sender, registry);
+
+    // The Hello of the switch was consumed by the handshake
+    ctrl.hello_handler(hello_xid, &switch).await;
+
+    // Read on a task of its own so that replies reach requests awaited by callbacks.
+    // The queue is unbounded: a bounded one could fill up while a callback waits
+    // for a reply, leaving the reader unable to deliver it.
+    let (inbox, mut messages) = unbounded_channel();
+    let reader_switch = switch.clone();
+    let reader_task = tokio::spawn(async move {
+        loop {
+            match framer.read_message(&mut reader).await {
+                Ok(Some(message)) => {
+                    if !reader_switch.take_reply(&message) && inbox.send(message).is_err() {
+                        return Ok(());
+                    }
+                }
                 // Connection closed by peer
-                break;
-            }
-            Err(_) => {
-                println!("cannot read packet");
-                break;
+                Ok(None) => return Ok(()),
+                Err(e) => return Err(e),
             }
         }
+    });
+
+    // Main message processing loop, ends once the reader is done
+    while let Some(mut message) = messages.recv().await {
+        ctrl.request_handler(&mut message, &switch).await;
+    }
+    let result = reader_task.await.unwrap_or_else(|e| Err(Error::other(e)));
+    writer_task.abort();
+    switch.close_requests();
+
+    // Forget the datapath and let the controller know it is gone
+    if let Some(datapath) = switch.registry().remove(&switch) {
+        ctrl.switch_disconnected_handler(datapath).await;
     }
+    result
 }
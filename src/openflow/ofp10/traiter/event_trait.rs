@@ -13,7 +13,10 @@
 use std::io::Error;
 
 use crate::openflow::ofp10::{
-    events::{Action, FeaturesReqEvent, HelloEvent, PacketOutEvent, Payload},
+    events::{
+        Action, BarrierRequestEvent, FeaturesReqEvent, GetConfigRequestEvent, HelloEvent,
+        PacketOutEvent, Payload, SetConfigEvent, SwitchConfig, VendorEvent,
+    },
     ofp_header::OfpHeader,
     Msg,
 };
@@ -49,6 +52,28 @@ pub trait MessageMarshal {
     fn size_of(&self) -> usize;
 }
 
+/// Boxed messages marshal like the message they hold
+///
+/// This allows sending messages of different types together, e.g. as a batch of
+/// `Box<dyn MessageMarshal + Send>`.
+impl<T: MessageMarshal + ?Sized> MessageMarshal for Box<T> {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        (**self).marshal(bytes)
+    }
+
+    fn msg_code(&self) -> Msg {
+        (**self).msg_code()
+    }
+
+    fn msg_usize(&self) -> usize {
+        (**self).msg_usize()
+    }
+
+    fn size_of(&self) -> usize {
+        (**self).size_of()
+    }
+}
+
 /// Trait for working with OpenFlow message events
 ///
 /// This trait provides functionality for creating and managing OpenFlow
@@ -122,6 +147,37 @@ pub trait OfpMsgEvent {
     /// A new FeaturesReqEvent instance
     fn fetures_req(&self) -> FeaturesReqEvent;
 
+    /// Creates a new barrier request event
+    ///
+    /// # Returns
+    /// A new BarrierRequestEvent instance
+    fn barrier_req(&self) -> BarrierRequestEvent;
+
+    /// Creates a new get config request event
+    ///
+    /// # Returns
+    /// A new GetConfigRequestEvent instance
+    fn get_config_req(&self) -> GetConfigRequestEvent;
+
+    /// Creates a new set config event
+    ///
+    /// # Arguments
+    /// * `config` - Switch configuration to apply
+    ///
+    /// # Returns
+    /// A new SetConfigEvent instance
+    fn set_config(&self, config: SwitchConfig) -> SetConfigEvent;
+
+    /// Creates a new vendor event
+    ///
+    /// # Arguments
+    /// * `vendor` - Vendor id
+    /// * `data` - Vendor defined body
+    ///
+    /// # Returns
+    /// A new VendorEvent instance
+    fn vendor(&self, vendor: u32, data: Vec<u8>) -> VendorEvent;
+
     /// Creates a new packet out event
     ///
     /// # Arguments
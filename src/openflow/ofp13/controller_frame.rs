@@ -5,7 +5,7 @@
 //! message parsing, event handling, and TCP communication functionality.
 //!
 //! The controller frame is responsible for:
-//! - Managing TCP connections with switches
+//! - Managing connections with switches through `SwitchHandle`
 //! - Parsing and handling OpenFlow messages
 //! - Processing various OpenFlow events
 //! - Sending responses and commands to switches
@@ -25,13 +25,14 @@ use std::future::Future;
 
 use super::{
     events::{echo_reply::EchoReplyEvent, EchoRequestEvent},
-    tcp_listener_handler, FeaturesReplyEvent, MessageMarshal, OfpMsgEvent, Openflow13,
-    OpenflowHeader,
+    tcp_listener_handler, AggregateStats, AsyncConfig, ExperimenterEvent, FeaturesReplyEvent,
+    FlowStats, GroupDesc, GroupFeatures, GroupStats, MessageMarshal, MeterConfig, MeterFeatures,
+    MeterStats, MultipartReplyBody, MultipartReplyEvent, OfpMsgEvent, Openflow13, OpenflowHeader,
+    Port, PortStats, QueueStats, SwitchConfig, SwitchDescription, TableFeatures, TableStats,
 };
-use crate::openflow::ofp13::{ErrorEvent, Msg, PacketInEvent};
-use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
-    net::TcpStream,
+use crate::openflow::ofp13::{
+    Datapath, DatapathRegistry, ErrorEvent, FlowRemovedEvent, Msg, PacketInEvent, PortStatusEvent,
+    RoleReplyEvent, SwitchHandle,
 };
 
 /// Trait defining the controller frame functionality for OpenFlow 1.3
@@ -46,17 +47,52 @@ pub trait ControllerFrame13: Send {
         Openflow13::new()
     }
 
+    /// Returns the registry shared by all connections of this controller
+    ///
+    /// By default every listener creates its own registry, reachable from callbacks
+    /// through `SwitchHandle::registry`. Override this to hand the listener a registry
+    /// that is also used outside of the callbacks, e.g. by timers or an external API.
+    fn registry(&self) -> Option<DatapathRegistry> {
+        None
+    }
+
+    /// Returns true to fetch switch and port descriptions after the handshake
+    ///
+    /// When enabled, description and port description requests are sent as soon as
+    /// the datapath is registered. Their replies are stored in the registry entry of
+    /// the datapath and then passed to `desc_handler` and `port_desc_handler`.
+    fn discover_on_connect(&self) -> bool {
+        false
+    }
+
+    /// Returns the asynchronous message configuration to push after the handshake
+    ///
+    /// When set, a set async message is sent as soon as the datapath is registered,
+    /// e.g. to stop packet-in messages a controller has no use for. The switch keeps
+    /// its default configuration when this returns `None`.
+    fn async_config(&self) -> Option<AsyncConfig> {
+        None
+    }
+
+    /// Returns the switch configuration to push after the handshake
+    ///
+    /// When set, a set config message is sent as soon as the datapath is registered,
+    /// e.g. to raise `miss_send_len` so that packet-in messages carry whole packets.
+    fn switch_config(&self) -> Option<SwitchConfig> {
+        None
+    }
+
     /// Handles incoming packet-in events
     ///
     /// # Arguments
     /// * `xid` - Transaction ID
     /// * `packetin` - The packet-in event to handle
-    /// * `stream` - TCP stream for communication
+    /// * `switch` - Handle of the switch that sent the message
     fn packet_in_handler(
         &mut self,
         xid: u32,
         packetin: PacketInEvent,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl Future<Output = ()> + Send;
 
     /// Creates a new instance of the controller frame
@@ -96,12 +132,12 @@ pub trait ControllerFrame13: Send {
     /// Handles incoming OpenFlow messages
     ///
     /// # Arguments
-    /// * `buf` - Buffer containing the message data
-    /// * `stream` - TCP stream for communication
+    /// * `buf` - Buffer containing one complete message, header included
+    /// * `switch` - Handle of the switch that sent the message
     fn request_handler(
         &mut self,
         buf: &mut Vec<u8>,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl Future<Output = ()> + Send
     where
         Self: Sync,
@@ -112,28 +148,100 @@ pub trait ControllerFrame13: Send {
                 Some(header) => header,
                 None => return,
             };
-            let mut payload = vec![0u8; pkt_size];
-            let _ = stream.read(&mut payload).await;
+            let header_size = ofp.header_size();
+            if buf.len() < header_size + pkt_size {
+                return;
+            }
+            let mut payload = buf[header_size..header_size + pkt_size].to_vec();
             let message = ofp.msg_parse(message);
+
+            // Multipart replies are handled once all of their parts arrived
+            if let Msg::MultipartReply = message {
+                match switch.assemble_multipart(xid, payload) {
+                    Ok(Some(reply)) => payload = reply,
+                    Ok(None) => return,
+                    Err(error) => {
+                        switch.fail_request(xid, error);
+                        return;
+                    }
+                }
+            }
+
+            // Replies awaited through `SwitchHandle::request` skip the callbacks
+            if !message.is_unsolicited()
+                && switch.complete_request(xid, message.to_int(), payload.clone())
+            {
+                return;
+            }
+
             match message {
-                Msg::Hello => self.hello_handler(xid, stream).await,
+                Msg::Hello => self.hello_handler(xid, switch).await,
                 Msg::Error => {
                     if let Ok(error) = ErrorEvent::parse(&payload) {
                         self.error_handler(error)
                     }
                 }
                 Msg::EchoRequest => {
-                    self.echo_request_handler(xid, EchoRequestEvent::new(payload), stream)
+                    self.echo_request_handler(xid, EchoRequestEvent::new(payload), switch)
                         .await
                 }
                 Msg::FeaturesReply => {
                     if let Ok(features) = FeaturesReplyEvent::parse(&payload) {
-                        self.switch_features_handler(xid, features, stream).await
+                        switch.set_datapath_id(features.datapath_id);
+                        let datapath = Datapath::new(switch.clone(), features.clone());
+                        switch.registry().insert(datapath.clone());
+                        if let Some(config) = self.switch_config() {
+                            let _ = switch
+                                .send_msg(self.ofp().set_config(config), switch.next_xid())
+                                .await;
+                        }
+                        if let Some(config) = self.async_config() {
+                            let _ = switch
+                                .send_msg(self.ofp().set_async(config), switch.next_xid())
+                                .await;
+                        }
+                        if self.discover_on_connect() {
+                            let ofp = self.ofp();
+                            let _ = switch.send_msg(ofp.desc_req(), switch.next_xid()).await;
+                            let _ = switch
+                                .send_msg(ofp.port_desc_req(), switch.next_xid())
+                                .await;
+                        }
+                        self.switch_connected_handler(datapath).await;
+                        self.switch_features_handler(xid, features, switch).await
                     }
                 }
                 Msg::PacketIn => {
                     if let Ok(pkt_in) = PacketInEvent::parse(&payload) {
-                        self.packet_in_handler(xid, pkt_in, stream).await
+                        self.packet_in_handler(xid, pkt_in, switch).await
+                    }
+                }
+                Msg::FlowRemove => {
+                    if let Ok(flow_removed) = FlowRemovedEvent::parse(&payload) {
+                        self.flow_removed_handler(xid, flow_removed, switch).await
+                    }
+                }
+                Msg::PortStatus => {
+                    if let Ok(port_status) = PortStatusEvent::parse(&payload) {
+                        switch.record_port_status(&port_status);
+                        self.port_status_handler(xid, port_status, switch).await
+                    }
+                }
+                Msg::RoleReply => {
+                    if let Ok(role) = RoleReplyEvent::parse(&payload) {
+                        switch.record_role(&role);
+                        self.role_reply_handler(xid, role, switch).await
+                    }
+                }
+                Msg::Experimenter => {
+                    if let Ok(message) = ExperimenterEvent::parse(&payload) {
+                        self.experimenter_handler(xid, message, switch).await
+                    }
+                }
+                Msg::MultipartReply => {
+                    if let Ok(reply) = MultipartReplyEvent::parse(&payload) {
+                        switch.record_multipart(&reply);
+                        self.multipart_reply_handler(xid, reply, switch).await
                     }
                 }
                 _ => (),
@@ -141,31 +249,25 @@ pub trait ControllerFrame13: Send {
         }
     }
 
-    /// Sends an OpenFlow message over the TCP stream
+    /// Sends an OpenFlow message to a switch
+    ///
+    /// Send errors are ignored here; use `SwitchHandle::send_msg` directly to observe them.
     ///
     /// # Arguments
     /// * `msg` - The message to send
     /// * `xid` - Transaction ID
-    /// * `stream` - TCP stream for communication
+    /// * `switch` - Handle of the destination switch
     fn send_msg<MSM: MessageMarshal + std::marker::Send>(
         &self,
         msg: MSM,
         xid: u32,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl Future<Output = ()> + Send
     where
         Self: Sync,
     {
         async move {
-            let ofp = self.ofp();
-            let mut header_bytes: Vec<u8> = Vec::new();
-            let mut body_bytes: Vec<u8> = Vec::new();
-
-            msg.marshal(&mut body_bytes);
-            let ofp_header = ofp.header(msg.msg_usize() as u8, body_bytes.len() as u16, xid);
-            ofp_header.marshal(&mut header_bytes);
-            header_bytes.append(&mut body_bytes);
-            let _ = stream.write_all(&header_bytes).await;
+            let _ = switch.send_msg(msg, xid).await;
         }
     }
 
@@ -173,13 +275,13 @@ pub trait ControllerFrame13: Send {
     ///
     /// # Arguments
     /// * `xid` - Transaction ID
-    /// * `stream` - TCP stream for communication
-    fn hello_handler(&self, xid: u32, stream: &mut TcpStream) -> impl Future<Output = ()> + Send
+    /// * `switch` - Handle of the switch that sent the message
+    fn hello_handler(&self, xid: u32, switch: &SwitchHandle) -> impl Future<Output = ()> + Send
     where
         Self: Sync,
     {
         async move {
-            self.send_msg(self.ofp().fetures_req(), xid, stream).await;
+            self.send_msg(self.ofp().fetures_req(), xid, switch).await;
         }
     }
 
@@ -196,18 +298,18 @@ pub trait ControllerFrame13: Send {
     /// # Arguments
     /// * `xid` - Transaction ID
     /// * `echo` - The echo request event to handle
-    /// * `stream` - TCP stream for communication
+    /// * `switch` - Handle of the switch that sent the message
     fn echo_request_handler(
         &self,
         xid: u32,
         echo: EchoRequestEvent,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl Future<Output = ()> + Send
     where
         Self: Sync,
     {
         async move {
-            self.send_msg(EchoReplyEvent::new(echo.payload), xid, stream)
+            self.send_msg(EchoReplyEvent::new(echo.payload), xid, switch)
                 .await;
         }
     }
@@ -217,14 +319,401 @@ pub trait ControllerFrame13: Send {
     /// # Arguments
     /// * `xid` - Transaction ID
     /// * `features_reply` - The features reply event to handle
-    /// * `stream` - TCP stream for communication
+    /// * `switch` - Handle of the switch that sent the message
     #[allow(unused)]
     fn switch_features_handler(
         &self,
         xid: u32,
         features_reply: FeaturesReplyEvent,
-        stream: &mut TcpStream,
+        switch: &SwitchHandle,
     ) -> impl std::future::Future<Output = ()> + Send {
         async {}
     }
+
+    /// Handles a switch that completed the handshake
+    ///
+    /// Called once the features reply arrived and the datapath was added to the registry.
+    ///
+    /// # Arguments
+    /// * `datapath` - The newly registered datapath
+    #[allow(unused)]
+    fn switch_connected_handler(&mut self, datapath: Datapath) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles a switch whose connection was closed
+    ///
+    /// Called after the datapath was removed from the registry.
+    ///
+    /// # Arguments
+    /// * `datapath` - The last known state of the datapath
+    #[allow(unused)]
+    fn switch_disconnected_handler(
+        &mut self,
+        datapath: Datapath,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles OpenFlow Flow-Removed messages
+    ///
+    /// Switches only send these for flow entries installed with the `send_flow_rem` flag.
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `flow_removed` - The flow-removed event to handle
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn flow_removed_handler(
+        &mut self,
+        xid: u32,
+        flow_removed: FlowRemovedEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles OpenFlow Port-Status messages
+    ///
+    /// The port table of the switch in the registry is already updated when this is called.
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `port_status` - The port-status event to handle
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn port_status_handler(
+        &mut self,
+        xid: u32,
+        port_status: PortStatusEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles role replies that were not awaited through `SwitchHandle::request_role`
+    ///
+    /// The reported role and generation id are already recorded on the handle when
+    /// this is called.
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `role` - The role reply reported by the switch
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn role_reply_handler(
+        &mut self,
+        xid: u32,
+        role: RoleReplyEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles experimenter messages that were not awaited through `SwitchHandle::request`
+    ///
+    /// The body is passed unchanged; decode it with an `ExperimenterRegistry` holding
+    /// the codecs of the experimenters the controller knows.
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `message` - The experimenter message
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn experimenter_handler(
+        &mut self,
+        xid: u32,
+        message: ExperimenterEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles multipart replies that were not awaited through `SwitchHandle::request`
+    ///
+    /// The default implementation dispatches the reply to the handler of its type.
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `reply` - The complete multipart reply
+    /// * `switch` - Handle of the switch that sent the message
+    fn multipart_reply_handler(
+        &mut self,
+        xid: u32,
+        reply: MultipartReplyEvent,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            match reply.body {
+                MultipartReplyBody::Desc(desc) => self.desc_handler(xid, desc, switch).await,
+                MultipartReplyBody::PortDesc(ports) => {
+                    self.port_desc_handler(xid, ports, switch).await
+                }
+                MultipartReplyBody::Flow(stats) => {
+                    self.flow_stats_handler(xid, stats, switch).await
+                }
+                MultipartReplyBody::Aggregate(stats) => {
+                    self.aggregate_stats_handler(xid, stats, switch).await
+                }
+                MultipartReplyBody::Table(stats) => {
+                    self.table_stats_handler(xid, stats, switch).await
+                }
+                MultipartReplyBody::PortStats(stats) => {
+                    self.port_stats_handler(xid, stats, switch).await
+                }
+                MultipartReplyBody::Queue(stats) => {
+                    self.queue_stats_handler(xid, stats, switch).await
+                }
+                MultipartReplyBody::Group(stats) => {
+                    self.group_stats_handler(xid, stats, switch).await
+                }
+                MultipartReplyBody::GroupDesc(groups) => {
+                    self.group_desc_handler(xid, groups, switch).await
+                }
+                MultipartReplyBody::GroupFeatures(features) => {
+                    self.group_features_handler(xid, features, switch).await
+                }
+                MultipartReplyBody::Meter(stats) => {
+                    self.meter_stats_handler(xid, stats, switch).await
+                }
+                MultipartReplyBody::MeterConfig(meters) => {
+                    self.meter_config_handler(xid, meters, switch).await
+                }
+                MultipartReplyBody::MeterFeatures(features) => {
+                    self.meter_features_handler(xid, features, switch).await
+                }
+                MultipartReplyBody::TableFeatures(tables) => {
+                    self.table_features_handler(xid, tables, switch).await
+                }
+                MultipartReplyBody::Raw(_) => (),
+            }
+        }
+    }
+
+    /// Handles the description of a switch
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `desc` - Manufacturer, hardware and software description of the switch
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn desc_handler(
+        &mut self,
+        xid: u32,
+        desc: SwitchDescription,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles the description of the ports of a switch
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `ports` - Every port of the switch
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn port_desc_handler(
+        &mut self,
+        xid: u32,
+        ports: Vec<Port>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles individual flow statistics
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `stats` - Statistics of every reported flow entry
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn flow_stats_handler(
+        &mut self,
+        xid: u32,
+        stats: Vec<FlowStats>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles aggregate flow statistics
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `stats` - Summed counters of the selected flows
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn aggregate_stats_handler(
+        &mut self,
+        xid: u32,
+        stats: AggregateStats,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles flow table statistics
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `stats` - Counters of every flow table
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn table_stats_handler(
+        &mut self,
+        xid: u32,
+        stats: Vec<TableStats>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles port statistics
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `stats` - Counters of every reported port
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn port_stats_handler(
+        &mut self,
+        xid: u32,
+        stats: Vec<PortStats>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles queue statistics
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `stats` - Counters of every reported queue
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn queue_stats_handler(
+        &mut self,
+        xid: u32,
+        stats: Vec<QueueStats>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles group statistics
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `stats` - Counters of every reported group
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn group_stats_handler(
+        &mut self,
+        xid: u32,
+        stats: Vec<GroupStats>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles the description of the groups of a switch
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `groups` - Type, id and buckets of every group
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn group_desc_handler(
+        &mut self,
+        xid: u32,
+        groups: Vec<GroupDesc>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles the group features of a switch
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `features` - Supported group types, capabilities and limits
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn group_features_handler(
+        &mut self,
+        xid: u32,
+        features: GroupFeatures,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles meter statistics
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `stats` - Counters of every reported meter
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn meter_stats_handler(
+        &mut self,
+        xid: u32,
+        stats: Vec<MeterStats>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles the configuration of the meters of a switch
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `meters` - Flags, id and bands of every reported meter
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn meter_config_handler(
+        &mut self,
+        xid: u32,
+        meters: Vec<MeterConfig>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles the meter features of a switch
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `features` - Supported band types, capabilities and limits
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn meter_features_handler(
+        &mut self,
+        xid: u32,
+        features: MeterFeatures,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Handles the features of the flow tables of a switch
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID
+    /// * `tables` - Supported matches, instructions and actions of every table
+    /// * `switch` - Handle of the switch that sent the message
+    #[allow(unused)]
+    fn table_features_handler(
+        &mut self,
+        xid: u32,
+        tables: Vec<TableFeatures>,
+        switch: &SwitchHandle,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
 }
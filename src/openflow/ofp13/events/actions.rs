@@ -5,21 +5,21 @@
 //! and various packet processing operations.
 
 use crate::{openflow::ofp13::PseudoPort, utils::MacAddr};
-use byteorder::{BigEndian, WriteBytesExt};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use std::{
-    io::Error,
+    io::{Cursor, Error, ErrorKind, Read},
     net::{Ipv4Addr, Ipv6Addr},
 };
 
 use super::flow_mod::{
     instructions::InstructActions,
-    match_fields::{OxmHeader, OxmMatchFields},
+    match_fields::{read_value, write_exact, OxmClass, OxmHeader, OxmMatchFields, PbbIsid},
 };
 
 /// Represents the different types of actions supported in OpenFlow v1.3
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 #[repr(u16)]
-enum ActionType {
+pub enum ActionType {
     Output = 0,      // Output to switch port.
     CopyTtlOut = 11, // Copy TTL "outwards" -- from next-to-outermost to outermost
     CopyTtlIn = 12,  // Copy TTL "inwards" -- from outermost to next-to-outermost
@@ -41,12 +41,46 @@ enum ActionType {
 }
 
 impl ActionType {
+    /// Marshals the action type into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the type to
     pub fn marshal(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
         bytes.write_u16::<BigEndian>(self.clone().into())?;
         Ok(())
     }
 }
 
+impl TryFrom<u16> for ActionType {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Output),
+            11 => Ok(Self::CopyTtlOut),
+            12 => Ok(Self::CopyTtlIn),
+            15 => Ok(Self::SetMplsTtl),
+            16 => Ok(Self::DecMplsTtl),
+            17 => Ok(Self::PushVlan),
+            18 => Ok(Self::PopVlan),
+            19 => Ok(Self::PushMpls),
+            20 => Ok(Self::PopMpls),
+            21 => Ok(Self::SetQueue),
+            22 => Ok(Self::Group),
+            23 => Ok(Self::SetNwTtl),
+            24 => Ok(Self::DecNwTtl),
+            25 => Ok(Self::SetField),
+            26 => Ok(Self::PushPbb),
+            27 => Ok(Self::PopPbb),
+            0xffff => Ok(Self::Experimenter),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown action type {}", value),
+            )),
+        }
+    }
+}
+
 impl From<ActionType> for u16 {
     fn from(value: ActionType) -> Self {
         value as u16
@@ -68,120 +102,286 @@ impl From<ControllerMaxLen> for u16 {
 }
 
 /// Represents fields that can be set in packet headers
-#[derive(Clone)]
+///
+/// Set-field actions carry a single OXM TLV without a mask.
+#[derive(Clone, Debug, PartialEq)]
 pub enum SetField {
     InPort(PseudoPort), // Ingress port. This may be a physical or switch-defined logical port.
-    EthDst(MacAddr),    // Ethernet source address. Can use arbitrary bitmask
-    EthSrc(MacAddr),    // Ethernet destination address. Can use arbitrary bitmask
-    EthTyp(u16),        // Ethernet type of the OpenFlow packet payload, after VLAN tags.
-    IpProto(u8),        // IPv4 or IPv6 protocol number
-    Ipv4Src(Ipv4Addr),  // IPv4 source address. Can use subnet mask or arbitrary bitmask
-    Ipv4Dst(Ipv4Addr),  // IPv4 destination address. Can use subnet mask or arbitrary bitmask
-    Ipv6Src(Ipv6Addr),  // IPv6 source address. Can use subnet mask or arbitrary bitmask
-    Ipv6Dst(Ipv6Addr),  // IPv6 destination address. Can use subnet mask or arbitrary bitmask
-    TcpSrc(u16),        // TCP source port
-    TcpDst(u16),        // TCP destination port
-    UdpSrc(u16),        // UDP source port
-    UdpDst(u16),        // UDP destination port
+    /// Metadata passed between tables
+    Metadata(u64),
+    EthDst(MacAddr), // Ethernet source address. Can use arbitrary bitmask
+    EthSrc(MacAddr), // Ethernet destination address. Can use arbitrary bitmask
+    EthTyp(u16),     // Ethernet type of the OpenFlow packet payload, after VLAN tags.
+    /// VLAN id, `MatchFields::VLAN_PRESENT` must be set
+    VlanVid(u16),
+    /// VLAN priority
+    VlanPcp(u8),
+    /// Diff Serv Code Point
+    IpDscp(u8),
+    /// ECN bits
+    IpEcn(u8),
+    IpProto(u8),       // IPv4 or IPv6 protocol number
+    Ipv4Src(Ipv4Addr), // IPv4 source address. Can use subnet mask or arbitrary bitmask
+    Ipv4Dst(Ipv4Addr), // IPv4 destination address. Can use subnet mask or arbitrary bitmask
+    TcpSrc(u16),       // TCP source port
+    TcpDst(u16),       // TCP destination port
+    UdpSrc(u16),       // UDP source port
+    UdpDst(u16),       // UDP destination port
+    /// SCTP source port
+    SctpSrc(u16),
+    /// SCTP destination port
+    SctpDst(u16),
+    /// ICMP type
+    Icmpv4Type(u8),
+    /// ICMP code
+    Icmpv4Code(u8),
+    /// ARP opcode
+    ArpOp(u16),
+    /// ARP source IPv4 address
+    ArpSpa(Ipv4Addr),
+    /// ARP target IPv4 address
+    ArpTpa(Ipv4Addr),
+    /// ARP source hardware address
+    ArpSha(MacAddr),
+    /// ARP target hardware address
+    ArpTha(MacAddr),
+    Ipv6Src(Ipv6Addr), // IPv6 source address. Can use subnet mask or arbitrary bitmask
+    Ipv6Dst(Ipv6Addr), // IPv6 destination address. Can use subnet mask or arbitrary bitmask
+    /// IPv6 flow label
+    Ipv6Flabel(u32),
+    /// ICMPv6 type
+    Icmpv6Type(u8),
+    /// ICMPv6 code
+    Icmpv6Code(u8),
+    /// Target address of an IPv6 neighbor discovery message
+    Ipv6NdTarget(Ipv6Addr),
+    /// Source link-layer address of an IPv6 neighbor solicitation
+    Ipv6NdSll(MacAddr),
+    /// Target link-layer address of an IPv6 neighbor advertisement
+    Ipv6NdTll(MacAddr),
+    /// MPLS label
+    MplsLabel(u32),
+    /// MPLS traffic class
+    MplsTc(u8),
+    /// MPLS bottom of stack bit
+    MplsBos(u8),
+    /// PBB service instance id, 24 bits
+    PbbIsid(u32),
+    /// Metadata of a logical port, e.g. a tunnel key
+    TunnelId(u64),
 }
 
 impl SetField {
+    /// Parses a set-field OXM TLV, header included
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the OXM header
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let oxm_class = bytes.read_u16::<BigEndian>()?;
+        let oxm_field = bytes.read_u8()?;
+        let hasmask = oxm_field & 1 == 1;
+        let oxm_length = bytes.read_u8()? as usize;
+        if oxm_class != OxmClass::OpenflowBasic as u16 || hasmask {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "unsupported set-field oxm class {:#x} (masked: {})",
+                    oxm_class, hasmask
+                ),
+            ));
+        }
+        let mut value = vec![0u8; oxm_length];
+        bytes.read_exact(&mut value)?;
+        let value = &value[..];
+        let field = match OxmMatchFields::from(oxm_field >> 1) {
+            OxmMatchFields::InPort => SetField::InPort(PseudoPort::new(read_value(value)?, None)),
+            OxmMatchFields::METADATA => SetField::Metadata(read_value(value)?),
+            OxmMatchFields::EthDst => SetField::EthDst(read_value(value)?),
+            OxmMatchFields::EthSrc => SetField::EthSrc(read_value(value)?),
+            OxmMatchFields::EthType => SetField::EthTyp(read_value(value)?),
+            OxmMatchFields::VlanVid => SetField::VlanVid(read_value(value)?),
+            OxmMatchFields::VlanPcp => SetField::VlanPcp(read_value(value)?),
+            OxmMatchFields::IpDscp => SetField::IpDscp(read_value(value)?),
+            OxmMatchFields::IpEcn => SetField::IpEcn(read_value(value)?),
+            OxmMatchFields::IpProto => SetField::IpProto(read_value(value)?),
+            OxmMatchFields::Ipv4Src => SetField::Ipv4Src(read_value(value)?),
+            OxmMatchFields::Ipv4Dst => SetField::Ipv4Dst(read_value(value)?),
+            OxmMatchFields::TcpSrc => SetField::TcpSrc(read_value(value)?),
+            OxmMatchFields::TcpDst => SetField::TcpDst(read_value(value)?),
+            OxmMatchFields::UdpSrc => SetField::UdpSrc(read_value(value)?),
+            OxmMatchFields::UdpDst => SetField::UdpDst(read_value(value)?),
+            OxmMatchFields::SctpSrc => SetField::SctpSrc(read_value(value)?),
+            OxmMatchFields::SctpDst => SetField::SctpDst(read_value(value)?),
+            OxmMatchFields::Icmpv4Type => SetField::Icmpv4Type(read_value(value)?),
+            OxmMatchFields::Icmpv4Code => SetField::Icmpv4Code(read_value(value)?),
+            OxmMatchFields::ArpOp => SetField::ArpOp(read_value(value)?),
+            OxmMatchFields::ArpSpa => SetField::ArpSpa(read_value(value)?),
+            OxmMatchFields::ArpTpa => SetField::ArpTpa(read_value(value)?),
+            OxmMatchFields::ArpSha => SetField::ArpSha(read_value(value)?),
+            OxmMatchFields::ArpTha => SetField::ArpTha(read_value(value)?),
+            OxmMatchFields::Ipv6Src => SetField::Ipv6Src(read_value(value)?),
+            OxmMatchFields::Ipv6Dst => SetField::Ipv6Dst(read_value(value)?),
+            OxmMatchFields::Ipv6Flabel => SetField::Ipv6Flabel(read_value(value)?),
+            OxmMatchFields::Icmpv6Type => SetField::Icmpv6Type(read_value(value)?),
+            OxmMatchFields::Icmpv6Code => SetField::Icmpv6Code(read_value(value)?),
+            OxmMatchFields::Ipv6NdTarget => SetField::Ipv6NdTarget(read_value(value)?),
+            OxmMatchFields::Ipv6NdSll => SetField::Ipv6NdSll(read_value(value)?),
+            OxmMatchFields::Ipv6NdTll => SetField::Ipv6NdTll(read_value(value)?),
+            OxmMatchFields::MplsLabel => SetField::MplsLabel(read_value(value)?),
+            OxmMatchFields::MplsTc => SetField::MplsTc(read_value(value)?),
+            OxmMatchFields::MplsBos => SetField::MplsBos(read_value(value)?),
+            OxmMatchFields::PbbIsid => SetField::PbbIsid(read_value::<PbbIsid>(value)?.0),
+            OxmMatchFields::TunnelId => SetField::TunnelId(read_value(value)?),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unsupported set-field oxm field {}", oxm_field >> 1),
+                ))
+            }
+        };
+        // the value must fill the whole TLV
+        if field.size_of() != 4 + oxm_length {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "invalid length {} for set-field oxm field {}",
+                    oxm_length,
+                    oxm_field >> 1
+                ),
+            ));
+        }
+        Ok(field)
+    }
+
+    /// Marshals the OXM TLV of the field, header included
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the field to
     pub fn marshal(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
-        match &self {
+        match self {
             SetField::InPort(port) => {
                 OxmHeader::new(OxmMatchFields::InPort, 4, false).marshal(bytes)?;
                 port.marshal(bytes);
+                Ok(())
             }
-            SetField::EthDst(mac) => {
-                OxmHeader::new(OxmMatchFields::EthDst, 12, true).marshal(bytes)?;
-                mac.marshal(bytes);
-                MacAddr::from(!0).marshal(bytes);
-            }
-            SetField::EthSrc(mac) => {
-                OxmHeader::new(OxmMatchFields::EthSrc, 12, true).marshal(bytes)?;
-                mac.marshal(bytes);
-                MacAddr::from(!0).marshal(bytes);
-            }
-            SetField::EthTyp(eth) => {
-                OxmHeader::new(OxmMatchFields::EthType, 2, false).marshal(bytes)?;
-                bytes.write_u16::<BigEndian>(*eth)?;
-            }
-            SetField::IpProto(proto) => {
-                OxmHeader::new(OxmMatchFields::IpProto, 1, false).marshal(bytes)?;
-                bytes.write_u8(*proto)?;
-            }
-            SetField::Ipv4Src(ipv4) => {
-                OxmHeader::new(OxmMatchFields::Ipv4Src, 8, true).marshal(bytes)?;
-                bytes.write_u32::<BigEndian>(ipv4.clone().into())?;
-                bytes.write_u32::<BigEndian>(!0)?;
-            }
-            SetField::Ipv4Dst(ipv4) => {
-                OxmHeader::new(OxmMatchFields::Ipv4Dst, 8, true).marshal(bytes)?;
-                bytes.write_u32::<BigEndian>(ipv4.clone().into())?;
-                bytes.write_u32::<BigEndian>(!0)?;
-            }
-            SetField::Ipv6Src(ipv6) => {
-                OxmHeader::new(OxmMatchFields::Ipv6Src, 32, true).marshal(bytes)?;
-                bytes.write_u128::<BigEndian>(ipv6.clone().into())?;
-                bytes.write_u128::<BigEndian>(!0)?;
-            }
-            SetField::Ipv6Dst(ipv6) => {
-                OxmHeader::new(OxmMatchFields::Ipv6Dst, 32, true).marshal(bytes)?;
-                bytes.write_u128::<BigEndian>(ipv6.clone().into())?;
-                bytes.write_u128::<BigEndian>(!0)?;
-            }
-            SetField::TcpSrc(tcp) => {
-                OxmHeader::new(OxmMatchFields::TcpSrc, 2, false).marshal(bytes)?;
-                bytes.write_u16::<BigEndian>(*tcp)?;
-            }
-            SetField::TcpDst(tcp) => {
-                OxmHeader::new(OxmMatchFields::TcpDst, 2, false).marshal(bytes)?;
-                bytes.write_u16::<BigEndian>(*tcp)?;
-            }
-            SetField::UdpSrc(udp) => {
-                OxmHeader::new(OxmMatchFields::UdpSrc, 2, false).marshal(bytes)?;
-                bytes.write_u16::<BigEndian>(*udp)?;
-            }
-            SetField::UdpDst(udp) => {
-                OxmHeader::new(OxmMatchFields::UdpDst, 2, false).marshal(bytes)?;
-                bytes.write_u16::<BigEndian>(*udp)?;
-            }
+            SetField::Metadata(v) => write_exact(bytes, OxmMatchFields::METADATA, Some(*v)),
+            SetField::EthDst(v) => write_exact(bytes, OxmMatchFields::EthDst, Some(*v)),
+            SetField::EthSrc(v) => write_exact(bytes, OxmMatchFields::EthSrc, Some(*v)),
+            SetField::EthTyp(v) => write_exact(bytes, OxmMatchFields::EthType, Some(*v)),
+            SetField::VlanVid(v) => write_exact(bytes, OxmMatchFields::VlanVid, Some(*v)),
+            SetField::VlanPcp(v) => write_exact(bytes, OxmMatchFields::VlanPcp, Some(*v)),
+            SetField::IpDscp(v) => write_exact(bytes, OxmMatchFields::IpDscp, Some(*v)),
+            SetField::IpEcn(v) => write_exact(bytes, OxmMatchFields::IpEcn, Some(*v)),
+            SetField::IpProto(v) => write_exact(bytes, OxmMatchFields::IpProto, Some(*v)),
+            SetField::Ipv4Src(v) => write_exact(bytes, OxmMatchFields::Ipv4Src, Some(*v)),
+            SetField::Ipv4Dst(v) => write_exact(bytes, OxmMatchFields::Ipv4Dst, Some(*v)),
+            SetField::TcpSrc(v) => write_exact(bytes, OxmMatchFields::TcpSrc, Some(*v)),
+            SetField::TcpDst(v) => write_exact(bytes, OxmMatchFields::TcpDst, Some(*v)),
+            SetField::UdpSrc(v) => write_exact(bytes, OxmMatchFields::UdpSrc, Some(*v)),
+            SetField::UdpDst(v) => write_exact(bytes, OxmMatchFields::UdpDst, Some(*v)),
+            SetField::SctpSrc(v) => write_exact(bytes, OxmMatchFields::SctpSrc, Some(*v)),
+            SetField::SctpDst(v) => write_exact(bytes, OxmMatchFields::SctpDst, Some(*v)),
+            SetField::Icmpv4Type(v) => write_exact(bytes, OxmMatchFields::Icmpv4Type, Some(*v)),
+            SetField::Icmpv4Code(v) => write_exact(bytes, OxmMatchFields::Icmpv4Code, Some(*v)),
+            SetField::ArpOp(v) => write_exact(bytes, OxmMatchFields::ArpOp, Some(*v)),
+            SetField::ArpSpa(v) => write_exact(bytes, OxmMatchFields::ArpSpa, Some(*v)),
+            SetField::ArpTpa(v) => write_exact(bytes, OxmMatchFields::ArpTpa, Some(*v)),
+            SetField::ArpSha(v) => write_exact(bytes, OxmMatchFields::ArpSha, Some(*v)),
+            SetField::ArpTha(v) => write_exact(bytes, OxmMatchFields::ArpTha, Some(*v)),
+            SetField::Ipv6Src(v) => write_exact(bytes, OxmMatchFields::Ipv6Src, Some(*v)),
+            SetField::Ipv6Dst(v) => write_exact(bytes, OxmMatchFields::Ipv6Dst, Some(*v)),
+            SetField::Ipv6Flabel(v) => write_exact(bytes, OxmMatchFields::Ipv6Flabel, Some(*v)),
+            SetField::Icmpv6Type(v) => write_exact(bytes, OxmMatchFields::Icmpv6Type, Some(*v)),
+            SetField::Icmpv6Code(v) => write_exact(bytes, OxmMatchFields::Icmpv6Code, Some(*v)),
+            SetField::Ipv6NdTarget(v) => write_exact(bytes, OxmMatchFields::Ipv6NdTarget, Some(*v)),
+            SetField::Ipv6NdSll(v) => write_exact(bytes, OxmMatchFields::Ipv6NdSll, Some(*v)),
+            SetField::Ipv6NdTll(v) => write_exact(bytes, OxmMatchFields::Ipv6NdTll, Some(*v)),
+            SetField::MplsLabel(v) => write_exact(bytes, OxmMatchFields::MplsLabel, Some(*v)),
+            SetField::MplsTc(v) => write_exact(bytes, OxmMatchFields::MplsTc, Some(*v)),
+            SetField::MplsBos(v) => write_exact(bytes, OxmMatchFields::MplsBos, Some(*v)),
+            SetField::PbbIsid(v) => write_exact(bytes, OxmMatchFields::PbbIsid, Some(PbbIsid(*v))),
+            SetField::TunnelId(v) => write_exact(bytes, OxmMatchFields::TunnelId, Some(*v)),
         }
-        Ok(())
+    }
+
+    /// Returns the size of the OXM TLV in bytes, header included
+    pub fn size_of(&self) -> usize {
+        let mut bytes = Vec::new();
+        let _ = self.marshal(&mut bytes);
+        bytes.len()
     }
 }
 
 pub type Buffer = u16;
 /// Represents an OpenFlow v1.3 action
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 #[repr(u8)]
 pub enum Action {
-    Oputput(PseudoPort),
-    CopyTtlOut,     // Copy TTL "outwards" -- from next-to-outermost to outermost
-    CopyTtlIn,      // Copy TTL "inwards" -- from outermost to next-to-outermost
-    SetMplsTtl(u8), // MPLS TTL
+    Oputput(PseudoPort, Buffer), // Output port and max_len sent to the controller
+    CopyTtlOut,                  // Copy TTL "outwards" -- from next-to-outermost to outermost
+    CopyTtlIn,                   // Copy TTL "inwards" -- from outermost to next-to-outermost
+    SetMplsTtl(u8),              // MPLS TTL
 
     DecMplsTtl, // Decrement MPLS TTL
     PushVlan(u16),
     PushMpls(u16),
     PushPbb(u16),
 
-    PopVlan(u16),
-    PopMpls(u16),
-    PopPbb(u16),
+    PopVlan,
+    PopMpls(u16), // Ethertype of the payload
+    PopPbb,
 
     SetQueue(u32),      // Set queue id when outputting to a port
     Group(u32),         // Apply group.
     SetNwTtl(u8),       // IP TTL.
     DecNwTtl = 24,      // Decrement IP TTL.
     SetField(SetField), // Set a header field using OXM TLV format.
-    Experimenter(u32),
+    Experimenter(ExperimenterAction),
+}
+
+/// Experimenter action, kept as raw bytes
+///
+/// Use `ExperimenterRegistry::decode_action` to decode the body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExperimenterAction {
+    /// Experimenter id
+    pub experimenter: u32,
+    /// Experimenter defined body, padded to a multiple of 8 bytes on the wire
+    ///
+    /// Parsed actions keep their padding in the body.
+    pub data: Vec<u8>,
+}
+
+impl ExperimenterAction {
+    /// Creates a new experimenter action
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id
+    /// * `data` - Experimenter defined body
+    pub fn new(experimenter: u32, data: Vec<u8>) -> Self {
+        Self { experimenter, data }
+    }
 }
 
 impl Action {
+    /// Creates an output action with the max_len matching the port
+    ///
+    /// The max_len of a controller port is its length, capped to `0xffff`; other
+    /// ports get `0xffff`, i.e. no buffering.
+    ///
+    /// # Arguments
+    /// * `port` - The port to output to
+    pub fn output(port: PseudoPort) -> Self {
+        let max_len = match port {
+            PseudoPort::Controller(len) => len.min(ControllerMaxLen::NoBuffer as u64) as u16,
+            _ => ControllerMaxLen::NoBuffer.into(),
+        };
+        Action::Oputput(port, max_len)
+    }
+
     fn action_type(&self) -> ActionType {
         match &self {
-            Action::Oputput(_) => ActionType::Output,
+            Action::Oputput(..) => ActionType::Output,
             Action::CopyTtlOut => ActionType::CopyTtlOut,
             Action::CopyTtlIn => ActionType::CopyTtlIn,
             Action::SetMplsTtl(_) => ActionType::SetMplsTtl,
@@ -189,9 +389,9 @@ impl Action {
             Action::PushVlan(_) => ActionType::PushVlan,
             Action::PushMpls(_) => ActionType::PushMpls,
             Action::PushPbb(_) => ActionType::PushPbb,
-            Action::PopVlan(_) => ActionType::PopVlan,
+            Action::PopVlan => ActionType::PopVlan,
             Action::PopMpls(_) => ActionType::PopMpls,
-            Action::PopPbb(_) => ActionType::PopPbb,
+            Action::PopPbb => ActionType::PopPbb,
             Action::SetQueue(_) => ActionType::SetQueue,
             Action::Group(_) => ActionType::Group,
             Action::SetNwTtl(_) => ActionType::SetNwTtl,
@@ -200,13 +400,98 @@ impl Action {
             Action::Experimenter(_) => ActionType::Experimenter,
         }
     }
+    /// Parses a single action, header included
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the action header
+    ///
+    /// # Returns
+    /// The parsed action, or an error for malformed or unsupported actions
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let typ = bytes.read_u16::<BigEndian>()?;
+        let len = bytes.read_u16::<BigEndian>()? as usize;
+        // set-field and experimenter actions have a variable length
+        let valid = match typ {
+            t if t == ActionType::Output as u16 => len == 16,
+            t if t == ActionType::SetField as u16 || t == ActionType::Experimenter as u16 => {
+                len >= 8
+            }
+            _ => len == 8,
+        };
+        if !valid || !len.is_multiple_of(8) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid length {} for action type {}", len, typ),
+            ));
+        }
+        let mut body = vec![0u8; len - 4];
+        bytes.read_exact(&mut body)?;
+        let mut body = Cursor::new(body);
+
+        let action = match typ {
+            t if t == ActionType::Output as u16 => {
+                let port = body.read_u32::<BigEndian>()?;
+                let max_len = body.read_u16::<BigEndian>()?;
+                Action::Oputput(PseudoPort::new(port, Some(max_len as u64)), max_len)
+            }
+            t if t == ActionType::CopyTtlOut as u16 => Action::CopyTtlOut,
+            t if t == ActionType::CopyTtlIn as u16 => Action::CopyTtlIn,
+            t if t == ActionType::SetMplsTtl as u16 => Action::SetMplsTtl(body.read_u8()?),
+            t if t == ActionType::DecMplsTtl as u16 => Action::DecMplsTtl,
+            t if t == ActionType::PushVlan as u16 => {
+                Action::PushVlan(body.read_u16::<BigEndian>()?)
+            }
+            t if t == ActionType::PushMpls as u16 => {
+                Action::PushMpls(body.read_u16::<BigEndian>()?)
+            }
+            t if t == ActionType::PushPbb as u16 => Action::PushPbb(body.read_u16::<BigEndian>()?),
+            t if t == ActionType::PopVlan as u16 => Action::PopVlan,
+            t if t == ActionType::PopMpls as u16 => Action::PopMpls(body.read_u16::<BigEndian>()?),
+            t if t == ActionType::PopPbb as u16 => Action::PopPbb,
+            t if t == ActionType::SetQueue as u16 => {
+                Action::SetQueue(body.read_u32::<BigEndian>()?)
+            }
+            t if t == ActionType::Group as u16 => Action::Group(body.read_u32::<BigEndian>()?),
+            t if t == ActionType::SetNwTtl as u16 => Action::SetNwTtl(body.read_u8()?),
+            t if t == ActionType::DecNwTtl as u16 => Action::DecNwTtl,
+            t if t == ActionType::SetField as u16 => Action::SetField(SetField::parse(&mut body)?),
+            t if t == ActionType::Experimenter as u16 => {
+                let experimenter = body.read_u32::<BigEndian>()?;
+                let mut data = Vec::new();
+                body.read_to_end(&mut data)?;
+                Action::Experimenter(ExperimenterAction::new(experimenter, data))
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unsupported action type {}", typ),
+                ))
+            }
+        };
+        Ok(action)
+    }
+
+    /// Parses a list of actions filling the whole buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the first action
+    /// * `len` - Total length of the action list in bytes
+    pub fn parse_list(bytes: &mut Cursor<Vec<u8>>, len: usize) -> Result<Vec<Self>, Error> {
+        let end = bytes.position() + len as u64;
+        let mut actions = Vec::new();
+        while bytes.position() < end {
+            actions.push(Action::parse(bytes)?);
+        }
+        Ok(actions)
+    }
+
     pub fn marshal(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
         match &self {
-            Action::Oputput(port) => {
+            Action::Oputput(port, max_len) => {
                 self.action_type().marshal(bytes)?;
                 bytes.write_u16::<BigEndian>(16)?; // len
                 port.marshal(bytes);
-                bytes.write_u16::<BigEndian>(ControllerMaxLen::NoBuffer.into())?;
+                bytes.write_u16::<BigEndian>(*max_len)?;
                 // padding 48bit
                 bytes.write_u32::<BigEndian>(0)?;
                 bytes.write_u16::<BigEndian>(0)?;
@@ -228,10 +513,11 @@ impl Action {
                 // padding 16 bit
                 bytes.write_u16::<BigEndian>(0)?;
             }
-            Action::PopVlan(ethertype) | Action::PopMpls(ethertype) | Action::PopPbb(ethertype) => {
+            Action::PopMpls(ethertype) => {
                 self.action_type().marshal(bytes)?;
                 bytes.write_u16::<BigEndian>(8)?;
                 bytes.write_u16::<BigEndian>(*ethertype)?;
+                // padding 16 bit
                 bytes.write_u16::<BigEndian>(0)?;
             }
             Action::SetQueue(queue_id) => {
@@ -256,16 +542,28 @@ impl Action {
                 let mut field_bytes: Vec<u8> = Vec::new();
                 omx_field.marshal(&mut field_bytes)?;
 
+                // the action is padded to a multiple of 8 bytes
+                let len = (4 + field_bytes.len()).div_ceil(8) * 8;
+                field_bytes.resize(len - 4, 0);
+
                 self.action_type().marshal(bytes)?;
-                bytes.write_u16::<BigEndian>(4 + field_bytes.len() as u16)?;
+                bytes.write_u16::<BigEndian>(len as u16)?;
                 bytes.append(&mut field_bytes);
             }
-            Action::Experimenter(exper_id) => {
+            Action::Experimenter(action) => {
+                let len = (8 + action.data.len()).div_ceil(8) * 8;
                 self.action_type().marshal(bytes)?;
-                bytes.write_u16::<BigEndian>(8)?;
-                bytes.write_u32::<BigEndian>(*exper_id)?;
+                bytes.write_u16::<BigEndian>(len as u16)?;
+                bytes.write_u32::<BigEndian>(action.experimenter)?;
+                bytes.extend_from_slice(&action.data);
+                bytes.resize(bytes.len() + len - 8 - action.data.len(), 0);
             }
-            Action::DecMplsTtl | Action::DecNwTtl | Action::CopyTtlOut | Action::CopyTtlIn => {
+            Action::DecMplsTtl
+            | Action::DecNwTtl
+            | Action::CopyTtlOut
+            | Action::CopyTtlIn
+            | Action::PopVlan
+            | Action::PopPbb => {
                 self.action_type().marshal(bytes)?;
                 bytes.write_u16::<BigEndian>(8)?;
                 // padding 32 bit
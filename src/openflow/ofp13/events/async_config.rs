@@ -0,0 +1,260 @@
+//! OpenFlow v1.3 Asynchronous Message Configuration
+//!
+//! This module implements the Get Async Request, Get Async Reply and Set Async messages
+//! used in OpenFlow v1.3 protocol. They select which packet-in, port-status and
+//! flow-removed messages a switch sends to a controller, separately for the master or
+//! equal role and for the slave role.
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Error};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+/// Packet-in reasons enabled in an asynchronous message mask
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PacketInReasons {
+    /// No matching flow entry (table-miss)
+    pub no_match: bool,
+    /// Output to controller action
+    pub action: bool,
+    /// Packet has an invalid TTL
+    pub invalid_ttl: bool,
+}
+
+impl From<u32> for PacketInReasons {
+    fn from(value: u32) -> Self {
+        Self {
+            no_match: value & 1 == 1,
+            action: value >> 1 & 1 == 1,
+            invalid_ttl: value >> 2 & 1 == 1,
+        }
+    }
+}
+
+impl From<PacketInReasons> for u32 {
+    fn from(value: PacketInReasons) -> Self {
+        (value.no_match as u32) | (value.action as u32) << 1 | (value.invalid_ttl as u32) << 2
+    }
+}
+
+/// Port-status reasons enabled in an asynchronous message mask
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PortStatusReasons {
+    /// A port was added
+    pub add: bool,
+    /// A port was removed
+    pub delete: bool,
+    /// Some attribute of a port has changed
+    pub modify: bool,
+}
+
+impl From<u32> for PortStatusReasons {
+    fn from(value: u32) -> Self {
+        Self {
+            add: value & 1 == 1,
+            delete: value >> 1 & 1 == 1,
+            modify: value >> 2 & 1 == 1,
+        }
+    }
+}
+
+impl From<PortStatusReasons> for u32 {
+    fn from(value: PortStatusReasons) -> Self {
+        (value.add as u32) | (value.delete as u32) << 1 | (value.modify as u32) << 2
+    }
+}
+
+/// Flow-removed reasons enabled in an asynchronous message mask
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FlowRemovedReasons {
+    /// Flow idle time exceeded idle_timeout
+    pub idle_timeout: bool,
+    /// Time exceeded hard_timeout
+    pub hard_timeout: bool,
+    /// Evicted by a DELETE flow mod
+    pub delete: bool,
+    /// Group was removed
+    pub group_delete: bool,
+}
+
+impl From<u32> for FlowRemovedReasons {
+    fn from(value: u32) -> Self {
+        Self {
+            idle_timeout: value & 1 == 1,
+            hard_timeout: value >> 1 & 1 == 1,
+            delete: value >> 2 & 1 == 1,
+            group_delete: value >> 3 & 1 == 1,
+        }
+    }
+}
+
+impl From<FlowRemovedReasons> for u32 {
+    fn from(value: FlowRemovedReasons) -> Self {
+        (value.idle_timeout as u32)
+            | (value.hard_timeout as u32) << 1
+            | (value.delete as u32) << 2
+            | (value.group_delete as u32) << 3
+    }
+}
+
+/// Asynchronous messages sent to a controller holding a given role
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AsyncMasks {
+    /// Packet-in reasons to send
+    pub packet_in: PacketInReasons,
+    /// Port-status reasons to send
+    pub port_status: PortStatusReasons,
+    /// Flow-removed reasons to send
+    pub flow_removed: FlowRemovedReasons,
+}
+
+/// Asynchronous message configuration of a connection (`ofp_async_config`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsyncConfig {
+    /// Messages sent while the controller is master or equal
+    pub master: AsyncMasks,
+    /// Messages sent while the controller is slave
+    pub slave: AsyncMasks,
+}
+
+impl AsyncConfig {
+    /// Size of the configuration on the wire
+    pub const SIZE: usize = 24;
+
+    /// Parses an asynchronous configuration from a get async reply body
+    ///
+    /// # Arguments
+    /// * `payload` - The byte buffer containing the reply body
+    ///
+    /// # Returns
+    /// Result containing either the parsed AsyncConfig or an error
+    pub fn parse(payload: &[u8]) -> Result<Self, Error> {
+        let mut bytes = Cursor::new(payload);
+        let mut master = AsyncMasks::default();
+        let mut slave = AsyncMasks::default();
+        master.packet_in = bytes.read_u32::<BigEndian>()?.into();
+        slave.packet_in = bytes.read_u32::<BigEndian>()?.into();
+        master.port_status = bytes.read_u32::<BigEndian>()?.into();
+        slave.port_status = bytes.read_u32::<BigEndian>()?.into();
+        master.flow_removed = bytes.read_u32::<BigEndian>()?.into();
+        slave.flow_removed = bytes.read_u32::<BigEndian>()?.into();
+        Ok(Self { master, slave })
+    }
+
+    /// Marshals the configuration into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the configuration to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u32::<BigEndian>(self.master.packet_in.clone().into());
+        let _ = bytes.write_u32::<BigEndian>(self.slave.packet_in.clone().into());
+        let _ = bytes.write_u32::<BigEndian>(self.master.port_status.clone().into());
+        let _ = bytes.write_u32::<BigEndian>(self.slave.port_status.clone().into());
+        let _ = bytes.write_u32::<BigEndian>(self.master.flow_removed.clone().into());
+        let _ = bytes.write_u32::<BigEndian>(self.slave.flow_removed.clone().into());
+    }
+}
+
+/// The configuration a switch starts with
+///
+/// Master and equal controllers receive every message except invalid TTL packet-ins,
+/// slave controllers only receive port-status messages.
+impl Default for AsyncConfig {
+    fn default() -> Self {
+        let port_status = PortStatusReasons::from(0b111);
+        Self {
+            master: AsyncMasks {
+                packet_in: PacketInReasons::from(0b11),
+                port_status: port_status.clone(),
+                flow_removed: FlowRemovedReasons::from(0b1111),
+            },
+            slave: AsyncMasks {
+                port_status,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Get async request message structure
+pub struct GetAsyncRequestEvent {}
+
+impl GetAsyncRequestEvent {
+    /// Creates a new get async request
+    ///
+    /// # Returns
+    /// * `GetAsyncRequestEvent` - The new get async request
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for GetAsyncRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for GetAsyncRequestEvent {
+    /// Returns the message type as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::GetAsyncRequest as usize
+    }
+
+    /// Returns the size of the message in bytes
+    fn size_of(&self) -> usize {
+        0
+    }
+
+    /// Returns the message type
+    fn msg_code(&self) -> Msg {
+        Msg::GetAsyncRequest
+    }
+
+    /// Marshals the message into a byte buffer, the request has no body
+    fn marshal(&self, _: &mut Vec<u8>) {}
+}
+
+/// Set async message structure
+pub struct SetAsyncEvent {
+    /// Configuration to apply to the connection
+    pub config: AsyncConfig,
+}
+
+impl SetAsyncEvent {
+    /// Creates a new set async message
+    ///
+    /// # Arguments
+    /// * `config` - Configuration to apply to the connection
+    ///
+    /// # Returns
+    /// * `SetAsyncEvent` - The new set async message
+    pub fn new(config: AsyncConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl MessageMarshal for SetAsyncEvent {
+    /// Returns the message type as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::SetAsync as usize
+    }
+
+    /// Returns the size of the message in bytes
+    fn size_of(&self) -> usize {
+        AsyncConfig::SIZE
+    }
+
+    /// Returns the message type
+    fn msg_code(&self) -> Msg {
+        Msg::SetAsync
+    }
+
+    /// Marshals the message into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the message to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        self.config.marshal(bytes);
+    }
+}
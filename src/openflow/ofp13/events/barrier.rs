@@ -0,0 +1,63 @@
+//! OpenFlow v1.3 Barrier Request Message Implementation
+//!
+//! This module implements the Barrier Request message type used in OpenFlow v1.3 protocol.
+//! A switch must finish processing every message received before a barrier request
+//! before it answers with a barrier reply, which lets the controller know that earlier
+//! messages have been applied.
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+/// Represents an OpenFlow v1.3 Barrier Request message
+///
+/// The message has no payload. The matching barrier reply has no payload either and
+/// carries the xid of the request.
+pub struct BarrierRequestEvent {}
+
+impl BarrierRequestEvent {
+    /// Creates a new Barrier Request message
+    ///
+    /// # Returns
+    /// A new BarrierRequestEvent instance
+    pub fn new() -> Self {
+        BarrierRequestEvent {}
+    }
+}
+
+impl Default for BarrierRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implements message marshaling for BarrierRequestEvent
+impl MessageMarshal for BarrierRequestEvent {
+    /// Marshals the Barrier Request message into a byte vector
+    ///
+    /// # Arguments
+    /// * `_` - The target byte vector (unused as Barrier Request has no payload)
+    fn marshal(&self, _: &mut Vec<u8>) {}
+
+    /// Returns the OpenFlow message code for Barrier Request
+    ///
+    /// # Returns
+    /// The Msg::BarrierRequest enum variant
+    fn msg_code(&self) -> Msg {
+        Msg::BarrierRequest
+    }
+
+    /// Returns the size of the Barrier Request message
+    ///
+    /// # Returns
+    /// 0 as Barrier Request message has no payload
+    fn size_of(&self) -> usize {
+        0
+    }
+
+    /// Returns the message code as a usize
+    ///
+    /// # Returns
+    /// The numeric value of the Barrier Request message code
+    fn msg_usize(&self) -> usize {
+        Msg::BarrierRequest as usize
+    }
+}
@@ -17,6 +17,7 @@ use byteorder::{BigEndian, ReadBytesExt};
 ///
 /// Contains information about the error type and any additional payload data
 /// associated with the error.
+#[derive(Debug)]
 pub struct ErrorEvent {
     /// The type and code of the error
     pub error_type: ErrorType,
@@ -0,0 +1,88 @@
+//! OpenFlow v1.3 Experimenter Message Implementation
+//!
+//! This module implements the Experimenter message type used in OpenFlow v1.3 protocol.
+//! Experimenter messages carry extensions outside of the specification, identified by
+//! an experimenter id and an experimenter defined type. The body is kept as raw bytes;
+//! `ExperimenterRegistry` decodes it for registered experimenters.
+
+use std::io::{Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+/// Represents an OpenFlow v1.3 Experimenter message
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExperimenterEvent {
+    /// Experimenter id
+    pub experimenter: u32,
+    /// Experimenter defined message type
+    pub exp_type: u32,
+    /// Experimenter defined body
+    pub data: Vec<u8>,
+}
+
+impl ExperimenterEvent {
+    /// Creates a new Experimenter message
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id
+    /// * `exp_type` - Experimenter defined message type
+    /// * `data` - Experimenter defined body
+    ///
+    /// # Returns
+    /// A new ExperimenterEvent instance
+    pub fn new(experimenter: u32, exp_type: u32, data: Vec<u8>) -> Self {
+        Self {
+            experimenter,
+            exp_type,
+            data,
+        }
+    }
+
+    /// Parses an Experimenter message
+    ///
+    /// # Arguments
+    /// * `bytes` - The message body, header excluded
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 8 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("experimenter message too short: {}", bytes.len()),
+            ));
+        }
+        let mut header = &bytes[..8];
+        Ok(Self {
+            experimenter: header.read_u32::<BigEndian>()?,
+            exp_type: header.read_u32::<BigEndian>()?,
+            data: bytes[8..].to_vec(),
+        })
+    }
+}
+
+impl MessageMarshal for ExperimenterEvent {
+    /// Marshals the Experimenter message into a byte vector
+    ///
+    /// # Arguments
+    /// * `bytes` - The target byte vector to write the message data to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u32::<BigEndian>(self.experimenter);
+        let _ = bytes.write_u32::<BigEndian>(self.exp_type);
+        bytes.extend_from_slice(&self.data);
+    }
+
+    /// Returns the OpenFlow message code for Experimenter
+    fn msg_code(&self) -> Msg {
+        Msg::Experimenter
+    }
+
+    /// Returns the message code as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::Experimenter as usize
+    }
+
+    /// Returns the size of the Experimenter message body
+    fn size_of(&self) -> usize {
+        8 + self.data.len()
+    }
+}
@@ -12,6 +12,7 @@ use byteorder::{BigEndian, ReadBytesExt};
 ///
 /// Contains information about the switch's capabilities and configuration,
 /// including datapath ID, buffer count, number of tables, and various capabilities.
+#[derive(Clone, Debug)]
 pub struct FeaturesReplyEvent {
     /// Unique identifier for the datapath (switch)
     pub datapath_id: u64,
@@ -60,6 +61,7 @@ impl FeaturesReplyEvent {
 ///
 /// Contains boolean flags indicating which features and statistics
 /// are supported by the switch.
+#[derive(Clone, Debug)]
 pub struct Capabilities {
     /// Support for flow statistics
     pub flow_stats: bool,
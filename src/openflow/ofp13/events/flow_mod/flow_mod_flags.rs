@@ -6,6 +6,7 @@
 use byteorder::{BigEndian, WriteBytesExt};
 
 /// Flags for flow modification operations
+#[derive(Clone, Debug)]
 pub struct FlowModFlags {
     /// Send flow removed message when flow expires or is deleted
     pub send_flow_rem: bool,
@@ -3,17 +3,20 @@
 //! This module implements the handler for flow modification messages in OpenFlow v1.3.
 //! It provides functionality to add, modify, and delete flow entries in the switch's flow tables.
 
+use std::io::Error;
+
 use byteorder::{BigEndian, WriteBytesExt};
 
 use crate::openflow::ofp13::{
-    events::{actions::ToInstruction, Action},
+    events::{multipart::TABLE_ALL, Action, OfpGroup},
     ofp_port::OfpPort,
     MessageMarshal, Msg, PseudoPort,
 };
 
-use super::{instructions::Instrucion, FlowModCommand, FlowModFlags, MatchFields};
+use super::{FlowModCommand, FlowModFlags, InstructionSet, MatchFields};
 
 /// Timeout configuration for flow entries
+#[derive(Clone, Debug)]
 pub enum Timeout {
     /// Flow entry never expires
     Permanent,
@@ -69,13 +72,13 @@ pub struct FlowModEvent {
     /// Optional output port
     out_port: Option<PseudoPort>,
     /// Optional output group
-    out_group: Option<PseudoPort>,
+    out_group: Option<u32>,
     /// Flow modification flags
     flags: FlowModFlags,
     /// Match fields for the flow entry
     match_fields: MatchFields,
     /// Instructions to apply to matching packets
-    instruction: Instrucion,
+    instructions: InstructionSet,
 }
 
 impl FlowModEvent {
@@ -96,25 +99,230 @@ impl FlowModEvent {
         actions: Vec<Action>,
         table_id: u8,
         buffer_id: Option<u32>,
+    ) -> Self {
+        let mut flow = Self::new(
+            FlowModCommand::Add,
+            priority,
+            match_fileds,
+            InstructionSet::new().apply_actions(actions),
+            table_id,
+        );
+        flow.buffer_id = buffer_id;
+        flow
+    }
+
+    /// Creates a new flow modification event for modifying flows
+    ///
+    /// Replaces the instructions of every flow in the table matching the fields,
+    /// regardless of its priority. Counters and timeouts are kept.
+    ///
+    /// # Arguments
+    /// * `match_fields` - Fields selecting the flows to modify
+    /// * `actions` - New actions of the flows
+    /// * `table_id` - ID of the table holding the flows
+    pub fn modify_flow(match_fields: MatchFields, actions: Vec<Action>, table_id: u8) -> Self {
+        Self::new(
+            FlowModCommand::Modify,
+            0,
+            match_fields,
+            InstructionSet::new().apply_actions(actions),
+            table_id,
+        )
+    }
+
+    /// Creates a new flow modification event for modifying a single flow
+    ///
+    /// Only the flow with exactly the same match fields and priority is modified.
+    ///
+    /// # Arguments
+    /// * `priority` - Priority of the flow
+    /// * `match_fields` - Match fields of the flow
+    /// * `actions` - New actions of the flow
+    /// * `table_id` - ID of the table holding the flow
+    pub fn modify_flow_strict(
+        priority: u16,
+        match_fields: MatchFields,
+        actions: Vec<Action>,
+        table_id: u8,
+    ) -> Self {
+        Self::new(
+            FlowModCommand::ModifyStrict,
+            priority,
+            match_fields,
+            InstructionSet::new().apply_actions(actions),
+            table_id,
+        )
+    }
+
+    /// Creates a new flow modification event for deleting flows
+    ///
+    /// Deletes every flow matching the fields, regardless of its priority.
+    ///
+    /// # Arguments
+    /// * `match_fields` - Fields selecting the flows to delete
+    /// * `table_id` - ID of the table holding the flows, or `TABLE_ALL`
+    pub fn delete_flow(match_fields: MatchFields, table_id: u8) -> Self {
+        Self::new(
+            FlowModCommand::Delete,
+            0,
+            match_fields,
+            InstructionSet::new(),
+            table_id,
+        )
+    }
+
+    /// Creates a new flow modification event for deleting a single flow
+    ///
+    /// Only the flow with exactly the same match fields and priority is deleted.
+    ///
+    /// # Arguments
+    /// * `priority` - Priority of the flow
+    /// * `match_fields` - Match fields of the flow
+    /// * `table_id` - ID of the table holding the flow, or `TABLE_ALL`
+    pub fn delete_flow_strict(priority: u16, match_fields: MatchFields, table_id: u8) -> Self {
+        Self::new(
+            FlowModCommand::DeleteStrict,
+            priority,
+            match_fields,
+            InstructionSet::new(),
+            table_id,
+        )
+    }
+
+    /// Creates a new flow modification event deleting every flow of every table
+    pub fn delete_all() -> Self {
+        Self::delete_flow(MatchFields::match_all(), TABLE_ALL)
+    }
+
+    /// Creates a new flow modification event with default settings
+    ///
+    /// # Arguments
+    /// * `command` - Command to apply
+    /// * `priority` - Priority of the flow entry
+    /// * `match_fields` - Match fields for the flow entry
+    /// * `instructions` - Instructions to apply to matching packets
+    /// * `table_id` - ID of the table to modify
+    fn new(
+        command: FlowModCommand,
+        priority: u16,
+        match_fields: MatchFields,
+        instructions: InstructionSet,
+        table_id: u8,
     ) -> Self {
         Self {
             cookie: 0,
             cookie_mask: 0,
             table_id,
-            command: FlowModCommand::Add,
+            command,
             idle_timeout: Timeout::Permanent,
             hard_timeout: Timeout::Permanent,
             priority,
-            buffer_id,
+            buffer_id: None,
             out_port: None,
             out_group: None,
             flags: FlowModFlags::all_false(),
-            match_fields: match_fileds,
-            instruction: Instrucion::InstructActions(actions.to_instruct()),
+            match_fields,
+            instructions,
         }
     }
 }
 
+impl FlowModEvent {
+    /// Sets the idle timeout of the flow
+    ///
+    /// # Arguments
+    /// * `timeout` - Time without matching packets after which the flow expires
+    pub fn idle_timeout(mut self, timeout: Timeout) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Sets the hard timeout of the flow
+    ///
+    /// # Arguments
+    /// * `timeout` - Time after which the flow expires, matching packets or not
+    pub fn hard_timeout(mut self, timeout: Timeout) -> Self {
+        self.hard_timeout = timeout;
+        self
+    }
+
+    /// Sets the cookie of the flow
+    ///
+    /// For modify and delete commands the cookie also restricts the affected flows
+    /// to those whose cookie matches under the mask.
+    ///
+    /// # Arguments
+    /// * `cookie` - Opaque controller-issued identifier
+    /// * `cookie_mask` - Bits of the cookie that must match; 0 disables the filter
+    pub fn cookie(mut self, cookie: u64, cookie_mask: u64) -> Self {
+        self.cookie = cookie;
+        self.cookie_mask = cookie_mask;
+        self
+    }
+
+    /// Restricts delete commands to flows outputting to a port
+    ///
+    /// # Arguments
+    /// * `out_port` - Required output port
+    pub fn out_port(mut self, out_port: PseudoPort) -> Self {
+        self.out_port = Some(out_port);
+        self
+    }
+
+    /// Restricts delete commands to flows outputting to a group
+    ///
+    /// # Arguments
+    /// * `out_group` - Required output group
+    pub fn out_group(mut self, out_group: u32) -> Self {
+        self.out_group = Some(out_group);
+        self
+    }
+
+    /// Sets the flags of the flow
+    ///
+    /// # Arguments
+    /// * `flags` - Flow modification flags
+    pub fn flags(mut self, flags: FlowModFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Applies the flow to a packet buffered by the switch
+    ///
+    /// # Arguments
+    /// * `buffer_id` - ID of the buffered packet
+    pub fn buffer_id(mut self, buffer_id: u32) -> Self {
+        self.buffer_id = Some(buffer_id);
+        self
+    }
+
+    /// Applies a meter to packets matching the flow
+    ///
+    /// # Arguments
+    /// * `meter_id` - ID of the meter, created with a `MeterModEvent`
+    pub fn meter(mut self, meter_id: u32) -> Self {
+        self.instructions = self.instructions.meter(meter_id);
+        self
+    }
+
+    /// Replaces the instructions of the flow
+    ///
+    /// # Arguments
+    /// * `instructions` - Instructions to apply to matching packets
+    pub fn instructions(mut self, instructions: InstructionSet) -> Self {
+        self.instructions = instructions;
+        self
+    }
+
+    /// Checks the instructions of the flow against the rules of the specification
+    ///
+    /// Switches reject flows breaking these rules with a bad instruction error;
+    /// validating first reports the problem without a round trip.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.instructions.validate(self.table_id)
+    }
+}
+
 impl MessageMarshal for FlowModEvent {
     /// Returns the message type as a usize
     fn msg_usize(&self) -> usize {
@@ -153,17 +361,17 @@ impl MessageMarshal for FlowModEvent {
                 let _ = bytes.write_u32::<BigEndian>(OfpPort::Any as u32);
             }
         }
-        match self.out_group.as_ref() {
-            Some(p) => p.marshal(bytes),
-            None => {
-                let _ = bytes.write_u32::<BigEndian>(OfpPort::Any as u32);
-            }
-        }
+        let _ = bytes.write_u32::<BigEndian>(self.out_group.unwrap_or(OfpGroup::Any as u32));
 
         self.flags.marshal(bytes);
         // padding
         let _ = bytes.write_u16::<BigEndian>(0);
         let _ = self.match_fields.marshal(bytes);
-        self.instruction.marshal(bytes);
+        self.instructions.marshal(bytes);
+    }
+
+    /// Checks the instructions of the flow, see `FlowModEvent::validate`
+    fn validate(&self) -> Result<(), Error> {
+        FlowModEvent::validate(self)
     }
 }
@@ -0,0 +1,255 @@
+//! OpenFlow v1.3 Instruction Sets
+//!
+//! This module implements the instruction set of a flow entry. A flow entry holds
+//! at most one instruction of each type, and the switch executes them in a fixed
+//! order: meter, apply-actions, clear-actions, write-actions, write-metadata and
+//! finally goto-table. `InstructionSet` keeps its instructions in that order.
+
+use std::io::{Error, ErrorKind};
+
+use crate::openflow::ofp13::Action;
+
+use super::instructions::{
+    GotoTable, Instrucion, InstructActions, InstructExperimenter, InstructMeter, InstructType,
+    WriteMetadata,
+};
+
+/// Position of an instruction type in the execution order of the specification
+///
+/// Experimenter instructions have no defined position; they are placed right
+/// before goto-table, which always has to come last.
+fn execution_order(typ: &InstructType) -> u8 {
+    match typ {
+        InstructType::Meter => 0,
+        InstructType::ApplyActions => 1,
+        InstructType::ClearActions => 2,
+        InstructType::WriteActions => 3,
+        InstructType::WriteMetadata => 4,
+        InstructType::Experimenter => 5,
+        InstructType::GotoTable => 6,
+    }
+}
+
+/// Ordered set of the instructions of a flow entry
+///
+/// Adding an instruction replaces any instruction of the same type, so the set
+/// never contains duplicates built through its methods.
+#[derive(Clone, Debug, Default)]
+pub struct InstructionSet {
+    /// Instructions sorted by execution order
+    instructions: Vec<Instrucion>,
+}
+
+impl InstructionSet {
+    /// Creates a new, empty instruction set
+    ///
+    /// A flow entry without instructions drops matching packets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a meter to matching packets
+    ///
+    /// # Arguments
+    /// * `meter_id` - ID of the meter
+    pub fn meter(mut self, meter_id: u32) -> Self {
+        self.insert(Instrucion::InstructMeter(InstructMeter::new(meter_id)));
+        self
+    }
+
+    /// Applies actions immediately
+    ///
+    /// # Arguments
+    /// * `actions` - Actions applied in list order
+    pub fn apply_actions(mut self, actions: Vec<Action>) -> Self {
+        self.insert(actions_instruction(InstructActions::APPLY, actions));
+        self
+    }
+
+    /// Clears the action set of the packet
+    pub fn clear_actions(mut self) -> Self {
+        self.insert(actions_instruction(InstructActions::CLEAR, Vec::new()));
+        self
+    }
+
+    /// Merges actions into the action set of the packet
+    ///
+    /// # Arguments
+    /// * `actions` - Actions executed when the packet leaves the pipeline
+    pub fn write_actions(mut self, actions: Vec<Action>) -> Self {
+        self.insert(actions_instruction(InstructActions::WRITE, actions));
+        self
+    }
+
+    /// Writes masked metadata
+    ///
+    /// # Arguments
+    /// * `metadata` - Metadata value to write
+    /// * `meta_mask` - Bits of the metadata to write
+    pub fn write_metadata(mut self, metadata: u64, meta_mask: u64) -> Self {
+        self.insert(Instrucion::WriteMetadata(WriteMetadata::new(
+            metadata, meta_mask,
+        )));
+        self
+    }
+
+    /// Continues processing in another table
+    ///
+    /// # Arguments
+    /// * `table_id` - ID of the next table, which must be greater than the current one
+    pub fn goto_table(mut self, table_id: u8) -> Self {
+        self.insert(Instrucion::GotoTable(GotoTable::new(table_id)));
+        self
+    }
+
+    /// Adds an experimenter instruction
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id
+    /// * `data` - Experimenter defined data
+    pub fn experimenter(mut self, experimenter: u32, data: Vec<u8>) -> Self {
+        self.insert(Instrucion::Experimenter(InstructExperimenter::new(
+            experimenter,
+            data,
+        )));
+        self
+    }
+
+    /// Adds an instruction at its position in the execution order
+    ///
+    /// # Arguments
+    /// * `instruction` - The instruction to add
+    ///
+    /// # Returns
+    /// The replaced instruction of the same type, if any
+    pub fn insert(&mut self, instruction: Instrucion) -> Option<Instrucion> {
+        let typ = instruction.instruct_type();
+        let replaced = self.remove(&typ);
+        let order = execution_order(&typ);
+        let index = self
+            .instructions
+            .iter()
+            .position(|i| execution_order(&i.instruct_type()) > order)
+            .unwrap_or(self.instructions.len());
+        self.instructions.insert(index, instruction);
+        replaced
+    }
+
+    /// Removes the instruction of a type
+    ///
+    /// # Arguments
+    /// * `typ` - Type of the instruction to remove
+    pub fn remove(&mut self, typ: &InstructType) -> Option<Instrucion> {
+        let index = self
+            .instructions
+            .iter()
+            .position(|i| i.instruct_type() == *typ)?;
+        Some(self.instructions.remove(index))
+    }
+
+    /// Returns the instruction of a type
+    ///
+    /// # Arguments
+    /// * `typ` - Type of the instruction
+    pub fn get(&self, typ: &InstructType) -> Option<&Instrucion> {
+        self.instructions.iter().find(|i| i.instruct_type() == *typ)
+    }
+
+    /// Returns the instructions in execution order
+    pub fn iter(&self) -> impl Iterator<Item = &Instrucion> {
+        self.instructions.iter()
+    }
+
+    /// Returns the number of instructions
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Returns true if the set holds no instruction
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Checks the set against the rules of the specification
+    ///
+    /// Every instruction type may appear once, clear-actions carries no actions
+    /// and goto-table may only jump forward. `SwitchHandle` runs this check on
+    /// every flow mod it sends.
+    ///
+    /// # Arguments
+    /// * `table_id` - ID of the table the flow entry is added to
+    pub fn validate(&self, table_id: u8) -> Result<(), Error> {
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let typ = instruction.instruct_type();
+            if self.instructions[..index]
+                .iter()
+                .any(|i| i.instruct_type() == typ)
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("duplicate {:?} instruction", typ),
+                ));
+            }
+            match instruction {
+                Instrucion::InstructActions(instruct)
+                    if typ == InstructType::ClearActions && !instruct.actions.is_empty() =>
+                {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "clear-actions instruction must not carry actions",
+                    ));
+                }
+                Instrucion::GotoTable(goto) if goto.table_id() <= table_id => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "goto-table {} does not follow table {}",
+                            goto.table_id(),
+                            table_id
+                        ),
+                    ));
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Marshals the instructions in execution order
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the instructions to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) {
+        for instruction in &self.instructions {
+            instruction.marshal(bytes);
+        }
+    }
+}
+
+impl TryFrom<Vec<Instrucion>> for InstructionSet {
+    type Error = Error;
+
+    /// Builds a set from a list of instructions, sorting them into execution order
+    ///
+    /// Fails if the list holds two instructions of the same type.
+    fn try_from(value: Vec<Instrucion>) -> Result<Self, Self::Error> {
+        let mut set = Self::new();
+        for instruction in value {
+            let typ = instruction.instruct_type();
+            if set.insert(instruction).is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("duplicate {:?} instruction", typ),
+                ));
+            }
+        }
+        Ok(set)
+    }
+}
+
+/// Builds a write, apply or clear actions instruction
+fn actions_instruction(typ: InstructType, actions: Vec<Action>) -> Instrucion {
+    let mut instruct = InstructActions::new(typ);
+    instruct.actions = actions;
+    Instrucion::InstructActions(instruct)
+}
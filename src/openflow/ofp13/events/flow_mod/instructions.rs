@@ -3,7 +3,9 @@
 //! This module defines the instructions that can be applied to packets
 //! matching a flow entry in the OpenFlow switch's flow tables.
 
-use byteorder::{BigEndian, WriteBytesExt};
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
 use crate::openflow::ofp13::Action;
 
@@ -17,7 +19,7 @@ pub trait InstructTrait {
 }
 
 /// Types of instructions that can be applied to matching packets
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 #[repr(u16)]
 pub enum InstructType {
     /// Jump to another table
@@ -46,6 +48,26 @@ impl InstructType {
     }
 }
 
+impl TryFrom<u16> for InstructType {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::GotoTable),
+            2 => Ok(Self::WriteMetadata),
+            3 => Ok(Self::WriteActions),
+            4 => Ok(Self::ApplyActions),
+            5 => Ok(Self::ClearActions),
+            6 => Ok(Self::Meter),
+            0xffff => Ok(Self::Experimenter),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown instruction type {}", value),
+            )),
+        }
+    }
+}
+
 impl From<InstructType> for u16 {
     fn from(value: InstructType) -> Self {
         value as u16
@@ -53,6 +75,7 @@ impl From<InstructType> for u16 {
 }
 
 /// Instruction to jump to another table
+#[derive(Clone, Debug)]
 pub struct GotoTable {
     /// Type of instruction
     typ: InstructType,
@@ -77,6 +100,11 @@ impl GotoTable {
             table_id,
         }
     }
+
+    /// Returns the ID of the table to jump to
+    pub fn table_id(&self) -> u8 {
+        self.table_id
+    }
 }
 
 impl InstructTrait for GotoTable {
@@ -91,6 +119,7 @@ impl InstructTrait for GotoTable {
 }
 
 /// Instruction to write metadata to the packet
+#[derive(Clone, Debug)]
 pub struct WriteMetadata {
     /// Type of instruction
     typ: InstructType,
@@ -119,6 +148,16 @@ impl WriteMetadata {
             meta_mask,
         }
     }
+
+    /// Returns the metadata value to write
+    pub fn metadata(&self) -> u64 {
+        self.metadata
+    }
+
+    /// Returns the metadata mask
+    pub fn meta_mask(&self) -> u64 {
+        self.meta_mask
+    }
 }
 
 impl InstructTrait for WriteMetadata {
@@ -134,6 +173,7 @@ impl InstructTrait for WriteMetadata {
 }
 
 /// Instruction to apply actions to the packet
+#[derive(Clone, Debug)]
 pub struct InstructActions {
     /// Type of instruction
     typ: InstructType,
@@ -165,6 +205,11 @@ impl InstructActions {
             actions: Vec::new(),
         }
     }
+
+    /// Returns whether the actions are written, applied or cleared
+    pub fn instruct_type(&self) -> InstructType {
+        self.typ.clone()
+    }
 }
 
 impl InstructTrait for InstructActions {
@@ -182,6 +227,7 @@ impl InstructTrait for InstructActions {
 }
 
 /// Instruction to apply a meter to the packet
+#[derive(Clone, Debug)]
 pub struct InstructMeter {
     /// Type of instruction
     typ: InstructType,
@@ -206,6 +252,11 @@ impl InstructMeter {
             meter_id,
         }
     }
+
+    /// Returns the ID of the meter applied by the instruction
+    pub fn meter_id(&self) -> u32 {
+        self.meter_id
+    }
 }
 
 impl InstructTrait for InstructMeter {
@@ -216,7 +267,42 @@ impl InstructTrait for InstructMeter {
     }
 }
 
+/// Experimenter instruction
+#[derive(Clone, Debug)]
+pub struct InstructExperimenter {
+    /// Experimenter id
+    pub experimenter: u32,
+    /// Experimenter defined data, padded to a multiple of 8 bytes on the wire
+    pub data: Vec<u8>,
+}
+
+impl InstructExperimenter {
+    /// Creates a new experimenter instruction
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id
+    /// * `data` - Experimenter defined data
+    ///
+    /// # Returns
+    /// * `InstructExperimenter` - The new instruction instance
+    pub fn new(experimenter: u32, data: Vec<u8>) -> Self {
+        Self { experimenter, data }
+    }
+}
+
+impl InstructTrait for InstructExperimenter {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let len = (8 + self.data.len()).next_multiple_of(8);
+        InstructType::Experimenter.marshal(bytes);
+        let _ = bytes.write_u16::<BigEndian>(len as u16);
+        let _ = bytes.write_u32::<BigEndian>(self.experimenter);
+        bytes.extend_from_slice(&self.data);
+        bytes.resize(bytes.len() + len - 8 - self.data.len(), 0);
+    }
+}
+
 /// Enum of all possible flow modification instructions
+#[derive(Clone, Debug)]
 pub enum Instrucion {
     /// Jump to another table
     GotoTable(GotoTable),
@@ -226,9 +312,92 @@ pub enum Instrucion {
     InstructActions(InstructActions),
     /// Apply a meter to the packet
     InstructMeter(InstructMeter),
+    /// Experimenter defined instruction
+    Experimenter(InstructExperimenter),
 }
 
 impl Instrucion {
+    /// Parses a single instruction, header included
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the instruction header
+    ///
+    /// # Returns
+    /// The parsed instruction, or an error for malformed or unsupported instructions
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let typ = InstructType::try_from(bytes.read_u16::<BigEndian>()?)?;
+        let len = bytes.read_u16::<BigEndian>()?;
+        let fixed_len = match typ {
+            InstructType::GotoTable | InstructType::ClearActions | InstructType::Meter => Some(8),
+            InstructType::WriteMetadata => Some(24),
+            _ => None,
+        };
+        let valid = match fixed_len {
+            Some(fixed_len) => len == fixed_len,
+            None => len >= 8 && len.is_multiple_of(8),
+        };
+        if !valid {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid length {} for instruction {:?}", len, typ),
+            ));
+        }
+        let instruction = match typ {
+            InstructType::GotoTable => {
+                let table_id = bytes.read_u8()?;
+                // padding
+                bytes.read_u24::<BigEndian>()?;
+                Instrucion::GotoTable(GotoTable::new(table_id))
+            }
+            InstructType::WriteMetadata => {
+                // padding
+                bytes.read_u32::<BigEndian>()?;
+                let metadata = bytes.read_u64::<BigEndian>()?;
+                let meta_mask = bytes.read_u64::<BigEndian>()?;
+                Instrucion::WriteMetadata(WriteMetadata::new(metadata, meta_mask))
+            }
+            InstructType::WriteActions
+            | InstructType::ApplyActions
+            | InstructType::ClearActions => {
+                // padding
+                bytes.read_u32::<BigEndian>()?;
+                let mut instruct = InstructActions::new(typ);
+                instruct.actions = Action::parse_list(bytes, len as usize - 8)?;
+                Instrucion::InstructActions(instruct)
+            }
+            InstructType::Meter => {
+                Instrucion::InstructMeter(InstructMeter::new(bytes.read_u32::<BigEndian>()?))
+            }
+            InstructType::Experimenter => {
+                let experimenter = bytes.read_u32::<BigEndian>()?;
+                let mut data = vec![0; len as usize - 8];
+                bytes.read_exact(&mut data)?;
+                Instrucion::Experimenter(InstructExperimenter::new(experimenter, data))
+            }
+        };
+        Ok(instruction)
+    }
+
+    /// Parses a list of instructions
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the first instruction
+    /// * `len` - Total length of the instruction list in bytes
+    pub fn parse_list(bytes: &mut Cursor<Vec<u8>>, len: usize) -> Result<Vec<Self>, Error> {
+        let end = bytes.position() + len as u64;
+        let mut instructions = Vec::new();
+        while bytes.position() < end {
+            instructions.push(Instrucion::parse(bytes)?);
+        }
+        if bytes.position() != end {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("instructions overrun their length {}", len),
+            ));
+        }
+        Ok(instructions)
+    }
+
     /// Marshals the instruction into a byte buffer
     ///
     /// # Arguments
@@ -239,6 +408,18 @@ impl Instrucion {
             Instrucion::WriteMetadata(v) => v.marshal(bytes),
             Instrucion::InstructActions(v) => v.marshal(bytes),
             Instrucion::InstructMeter(v) => v.marshal(bytes),
+            Instrucion::Experimenter(v) => v.marshal(bytes),
+        }
+    }
+
+    /// Returns the type of the instruction
+    pub fn instruct_type(&self) -> InstructType {
+        match &self {
+            Instrucion::GotoTable(_) => InstructType::GotoTable,
+            Instrucion::WriteMetadata(_) => InstructType::WriteMetadata,
+            Instrucion::InstructActions(v) => v.instruct_type(),
+            Instrucion::InstructMeter(_) => InstructType::Meter,
+            Instrucion::Experimenter(_) => InstructType::Experimenter,
         }
     }
 }
@@ -4,7 +4,7 @@
 //! It implements the OpenFlow Extensible Match (OXM) format for flexible packet matching.
 
 use std::{
-    io::{BufRead, Cursor, Error},
+    io::{BufRead, Cursor, Error, ErrorKind, Read},
     mem::transmute,
     net::{Ipv4Addr, Ipv6Addr},
 };
@@ -51,12 +51,21 @@ impl OfpMatch {
     /// * `Result<(), Error>` - Success or error status
     pub fn marshal(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
         bytes.write_u16::<BigEndian>(self.typ.clone().into())?;
-        bytes.write_u16::<BigEndian>(self.length + (self.oxm_fields.len() as u16))?;
+        let length = self.length + (self.oxm_fields.len() as u16);
+        bytes.write_u16::<BigEndian>(length)?;
         bytes.append(&mut self.oxm_fields.clone());
-        // padding
-        bytes.write_u32::<BigEndian>(0)?;
+        // padding to a multiple of 8 bytes
+        bytes.resize(bytes.len() + Self::padding(length), 0);
         Ok(())
     }
+
+    /// Returns the number of padding bytes following a match of the given length
+    ///
+    /// # Arguments
+    /// * `length` - Value of the match length field (excluding padding)
+    pub fn padding(length: u16) -> usize {
+        (8 - (length as usize % 8)) % 8
+    }
 }
 
 #[derive(Clone)]
@@ -174,7 +183,82 @@ impl From<OxmClass> for u16 {
     }
 }
 
-#[derive(Clone)]
+/// OXM field of a class other than OpenFlow basic, kept as raw bytes
+///
+/// Fields of the experimenter class start their payload with the experimenter id.
+/// Use `ExperimenterRegistry::decode_oxm` to decode them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OxmExtension {
+    /// Match class of the field
+    pub class: u16,
+    /// Field number within the class, without the hasmask bit
+    pub field: u8,
+    /// True when the payload ends with a mask
+    pub hasmask: bool,
+    /// Payload of the field: experimenter id (experimenter class only), value and mask
+    pub payload: Vec<u8>,
+}
+
+impl OxmExtension {
+    /// Creates a new OXM field
+    ///
+    /// # Arguments
+    /// * `class` - Match class of the field
+    /// * `field` - Field number within the class
+    /// * `hasmask` - True when the payload ends with a mask
+    /// * `payload` - Payload of the field
+    pub fn new(class: u16, field: u8, hasmask: bool, payload: Vec<u8>) -> Self {
+        Self {
+            class,
+            field,
+            hasmask,
+            payload,
+        }
+    }
+
+    /// Creates a field of the experimenter class
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id
+    /// * `field` - Field number defined by the experimenter
+    /// * `hasmask` - True when `data` ends with a mask
+    /// * `data` - Value and mask of the field
+    pub fn experimenter_field(experimenter: u32, field: u8, hasmask: bool, data: &[u8]) -> Self {
+        let mut payload = experimenter.to_be_bytes().to_vec();
+        payload.extend_from_slice(data);
+        Self::new(OxmClass::Experimenter as u16, field, hasmask, payload)
+    }
+
+    /// Returns the experimenter id of a field of the experimenter class
+    pub fn experimenter(&self) -> Option<u32> {
+        if self.class != OxmClass::Experimenter as u16 || self.payload.len() < 4 {
+            return None;
+        }
+        (&self.payload[..4]).read_u32::<BigEndian>().ok()
+    }
+
+    /// Returns the value and mask of the field, experimenter id excluded
+    pub fn data(&self) -> &[u8] {
+        match self.experimenter() {
+            Some(_) => &self.payload[4..],
+            None => &self.payload,
+        }
+    }
+
+    /// Marshals the field, header included
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the field to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
+        bytes.write_u16::<BigEndian>(self.class)?;
+        bytes.write_u8(self.field << 1 | self.hasmask as u8)?;
+        bytes.write_u8(self.payload.len() as u8)?;
+        bytes.extend_from_slice(&self.payload);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 #[repr(u8)]
 /* OXM Flow match field types for OpenFlow basic class. */
 pub enum OxmMatchFields {
@@ -264,7 +348,7 @@ pub enum OxmMatchFields {
 
 impl From<u8> for OxmMatchFields {
     fn from(value: u8) -> Self {
-        if value < 44 {
+        if value <= Self::Ipv6Exthdr as u8 {
             unsafe { transmute(value) }
         } else {
             Self::Unparse
@@ -280,25 +364,34 @@ impl From<OxmMatchFields> for u8 {
 
 // Required match fields.
 /// Required match fields for flow entries
+#[derive(Clone, Debug, PartialEq)]
 pub struct MatchFields {
     /// Ingress port (physical or logical)
     pub in_port: Option<u32>, // Ingress port. This may be a physical or switch-defined logical port.
+    /// Physical port underneath a logical ingress port
+    pub in_phy_port: Option<u32>,
+    /// Metadata passed between tables
+    pub metadata: Option<u64>,
     /// Ethernet destination address with optional bitmask
     pub eth_dst: Option<MacAddr>, // Ethernet source address. Can use arbitrary bitmask
     /// Ethernet source address with optional bitmask
     pub eth_src: Option<MacAddr>, // Ethernet destination address. Can use arbitrary bitmask
     /// Ethernet type of the OpenFlow packet payload, after VLAN tags
     pub eth_typ: Option<u16>, // Ethernet type of the OpenFlow packet payload, after VLAN tags.
+    /// VLAN id, `VLAN_PRESENT` is set for tagged packets and `VLAN_NONE` matches untagged ones
+    pub vlan_vid: Option<u16>,
+    /// VLAN priority
+    pub vlan_pcp: Option<u8>,
+    /// Diff Serv Code Point, the 6 upper bits of the ToS field
+    pub ip_dscp: Option<u8>,
+    /// ECN bits, the 2 lower bits of the ToS field
+    pub ip_ecn: Option<u8>,
     /// IPv4 or IPv6 protocol number
     pub ip_proto: Option<u8>, // IPv4 or IPv6 protocol number
     /// IPv4 source address with optional mask
     pub ipv4_src: Option<Ipv4Addr>, // IPv4 source address. Can use subnet mask or arbitrary bitmask
     /// IPv4 destination address with optional mask
     pub ipv4_dst: Option<Ipv4Addr>, // IPv4 destination address. Can use subnet mask or arbitrary bitmask
-    /// IPv6 source address with optional mask
-    pub ipv6_src: Option<Ipv6Addr>, // IPv6 source address. Can use subnet mask or arbitrary bitmask
-    /// IPv6 destination address with optional mask
-    pub ipv6_dst: Option<Ipv6Addr>, // IPv6 destination address. Can use subnet mask or arbitrary bitmask
     /// TCP source port
     pub tcp_src: Option<u16>, // TCP source port
     /// TCP destination port
@@ -307,9 +400,125 @@ pub struct MatchFields {
     pub udp_src: Option<u16>, // UDP source port
     /// UDP destination port
     pub udp_dst: Option<u16>, // UDP destination port
+    /// SCTP source port
+    pub sctp_src: Option<u16>,
+    /// SCTP destination port
+    pub sctp_dst: Option<u16>,
+    /// ICMP type
+    pub icmpv4_type: Option<u8>,
+    /// ICMP code
+    pub icmpv4_code: Option<u8>,
+    /// ARP opcode
+    pub arp_op: Option<u16>,
+    /// ARP source IPv4 address
+    pub arp_spa: Option<Ipv4Addr>,
+    /// ARP target IPv4 address
+    pub arp_tpa: Option<Ipv4Addr>,
+    /// ARP source hardware address
+    pub arp_sha: Option<MacAddr>,
+    /// ARP target hardware address
+    pub arp_tha: Option<MacAddr>,
+    /// IPv6 source address with optional mask
+    pub ipv6_src: Option<Ipv6Addr>, // IPv6 source address. Can use subnet mask or arbitrary bitmask
+    /// IPv6 destination address with optional mask
+    pub ipv6_dst: Option<Ipv6Addr>, // IPv6 destination address. Can use subnet mask or arbitrary bitmask
+    /// IPv6 flow label, 20 bits
+    pub ipv6_flabel: Option<u32>,
+    /// ICMPv6 type
+    pub icmpv6_type: Option<u8>,
+    /// ICMPv6 code
+    pub icmpv6_code: Option<u8>,
+    /// Target address of an IPv6 neighbor discovery message
+    pub ipv6_nd_target: Option<Ipv6Addr>,
+    /// Source link-layer address option of an IPv6 neighbor solicitation
+    pub ipv6_nd_sll: Option<MacAddr>,
+    /// Target link-layer address option of an IPv6 neighbor advertisement
+    pub ipv6_nd_tll: Option<MacAddr>,
+    /// MPLS label, 20 bits
+    pub mpls_label: Option<u32>,
+    /// MPLS traffic class, 3 bits
+    pub mpls_tc: Option<u8>,
+    /// MPLS bottom of stack bit
+    pub mpls_bos: Option<u8>,
+    /// PBB service instance id, 24 bits
+    pub pbb_isid: Option<u32>,
+    /// Metadata of a logical port, e.g. a tunnel key
+    pub tunnel_id: Option<u64>,
+    /// IPv6 extension header pseudo-field (`OFPIEH_*` flags)
+    pub ipv6_exthdr: Option<u16>,
+    /// Bitmasks of the maskable fields, fields without a mask are matched exactly
+    pub masks: MatchMasks,
+    /// Fields of other OXM classes, e.g. experimenter fields
+    pub extensions: Vec<OxmExtension>,
+}
+
+/// Bitmasks of the maskable match fields
+///
+/// A set bit means the corresponding bit of the field must match. A mask is only used
+/// when the field itself is set; an all-ones mask is the same as no mask.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchMasks {
+    /// Mask of `metadata`
+    pub metadata: Option<u64>,
+    /// Mask of `eth_dst`, e.g. `01:00:00:00:00:00` for multicast addresses
+    pub eth_dst: Option<MacAddr>,
+    /// Mask of `eth_src`
+    pub eth_src: Option<MacAddr>,
+    /// Mask of `vlan_vid`
+    pub vlan_vid: Option<u16>,
+    /// Mask of `ipv4_src`
+    pub ipv4_src: Option<Ipv4Addr>,
+    /// Mask of `ipv4_dst`
+    pub ipv4_dst: Option<Ipv4Addr>,
+    /// Mask of `arp_spa`
+    pub arp_spa: Option<Ipv4Addr>,
+    /// Mask of `arp_tpa`
+    pub arp_tpa: Option<Ipv4Addr>,
+    /// Mask of `arp_sha`
+    pub arp_sha: Option<MacAddr>,
+    /// Mask of `arp_tha`
+    pub arp_tha: Option<MacAddr>,
+    /// Mask of `ipv6_src`
+    pub ipv6_src: Option<Ipv6Addr>,
+    /// Mask of `ipv6_dst`
+    pub ipv6_dst: Option<Ipv6Addr>,
+    /// Mask of `ipv6_flabel`
+    pub ipv6_flabel: Option<u32>,
+    /// Mask of `pbb_isid`
+    pub pbb_isid: Option<u32>,
+    /// Mask of `tunnel_id`
+    pub tunnel_id: Option<u64>,
+    /// Mask of `ipv6_exthdr`
+    pub ipv6_exthdr: Option<u16>,
+}
+
+impl MatchMasks {
+    /// Returns the IPv4 mask of a prefix length, e.g. `255.0.0.0` for 8
+    ///
+    /// # Arguments
+    /// * `prefix` - Prefix length in bits, capped at 32
+    pub fn ipv4_prefix(prefix: u8) -> Ipv4Addr {
+        let prefix = prefix.min(32) as u32;
+        Ipv4Addr::from(u32::MAX.checked_shl(32 - prefix).unwrap_or(0))
+    }
+
+    /// Returns the IPv6 mask of a prefix length, e.g. `ffff:ffff:ffff:ffff::` for 64
+    ///
+    /// # Arguments
+    /// * `prefix` - Prefix length in bits, capped at 128
+    pub fn ipv6_prefix(prefix: u8) -> Ipv6Addr {
+        let prefix = prefix.min(128) as u32;
+        Ipv6Addr::from(u128::MAX.checked_shl(128 - prefix).unwrap_or(0))
+    }
 }
 
 impl MatchFields {
+    /// Bit set in `vlan_vid` for packets with a VLAN tag (`OFPVID_PRESENT`)
+    pub const VLAN_PRESENT: u16 = 0x1000;
+
+    /// Value of `vlan_vid` matching packets without a VLAN tag (`OFPVID_NONE`)
+    pub const VLAN_NONE: u16 = 0x0000;
+
     /// Creates a new match fields structure with no fields set
     ///
     /// # Returns
@@ -317,22 +526,102 @@ impl MatchFields {
     pub fn match_all() -> Self {
         Self {
             in_port: None,
+            in_phy_port: None,
+            metadata: None,
             eth_dst: None,
             eth_src: None,
             eth_typ: None,
+            vlan_vid: None,
+            vlan_pcp: None,
+            ip_dscp: None,
+            ip_ecn: None,
             ip_proto: None,
             ipv4_src: None,
             ipv4_dst: None,
-            ipv6_src: None,
-            ipv6_dst: None,
             tcp_src: None,
             tcp_dst: None,
             udp_src: None,
             udp_dst: None,
+            sctp_src: None,
+            sctp_dst: None,
+            icmpv4_type: None,
+            icmpv4_code: None,
+            arp_op: None,
+            arp_spa: None,
+            arp_tpa: None,
+            arp_sha: None,
+            arp_tha: None,
+            ipv6_src: None,
+            ipv6_dst: None,
+            ipv6_flabel: None,
+            icmpv6_type: None,
+            icmpv6_code: None,
+            ipv6_nd_target: None,
+            ipv6_nd_sll: None,
+            ipv6_nd_tll: None,
+            mpls_label: None,
+            mpls_tc: None,
+            mpls_bos: None,
+            pbb_isid: None,
+            tunnel_id: None,
+            ipv6_exthdr: None,
+            masks: MatchMasks::default(),
+            extensions: Vec::new(),
         }
     }
+
+    /// Matches IPv4 source addresses within a prefix, e.g. `10.0.0.0/8`
+    ///
+    /// # Arguments
+    /// * `addr` - Network address
+    /// * `prefix` - Prefix length in bits
+    pub fn ipv4_src_prefix(&mut self, addr: Ipv4Addr, prefix: u8) {
+        self.ipv4_src = Some(addr);
+        self.masks.ipv4_src = Some(MatchMasks::ipv4_prefix(prefix));
+    }
+
+    /// Matches IPv4 destination addresses within a prefix, e.g. `10.0.0.0/8`
+    ///
+    /// # Arguments
+    /// * `addr` - Network address
+    /// * `prefix` - Prefix length in bits
+    pub fn ipv4_dst_prefix(&mut self, addr: Ipv4Addr, prefix: u8) {
+        self.ipv4_dst = Some(addr);
+        self.masks.ipv4_dst = Some(MatchMasks::ipv4_prefix(prefix));
+    }
+
+    /// Matches IPv6 source addresses within a prefix, e.g. `2001:db8::/64`
+    ///
+    /// # Arguments
+    /// * `addr` - Network address
+    /// * `prefix` - Prefix length in bits
+    pub fn ipv6_src_prefix(&mut self, addr: Ipv6Addr, prefix: u8) {
+        self.ipv6_src = Some(addr);
+        self.masks.ipv6_src = Some(MatchMasks::ipv6_prefix(prefix));
+    }
+
+    /// Matches IPv6 destination addresses within a prefix, e.g. `2001:db8::/64`
+    ///
+    /// # Arguments
+    /// * `addr` - Network address
+    /// * `prefix` - Prefix length in bits
+    pub fn ipv6_dst_prefix(&mut self, addr: Ipv6Addr, prefix: u8) {
+        self.ipv6_dst = Some(addr);
+        self.masks.ipv6_dst = Some(MatchMasks::ipv6_prefix(prefix));
+    }
+
+    /// Matches every packet with a VLAN tag, whatever its VLAN id
+    pub fn vlan_present(&mut self) {
+        self.vlan_vid = Some(Self::VLAN_PRESENT);
+        self.masks.vlan_vid = Some(Self::VLAN_PRESENT);
+    }
+
     /// Marshals the match fields into a byte buffer
     ///
+    /// Fields are written in the order of their OXM field number, which puts every
+    /// prerequisite (e.g. `eth_typ` before `ipv4_src`) before the fields depending on it.
+    /// Values of masked fields are cleared outside of their mask.
+    ///
     /// # Arguments
     /// * `bytes` - The buffer to write the fields to
     ///
@@ -340,198 +629,413 @@ impl MatchFields {
     /// * `Result<(), Error>` - Success or error status
     pub fn marshal(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
         let mut ofp_match = OfpMatch::new();
-        let mut ofp_byte = ofp_match.oxm_fields.as_mut();
+        let oxm = &mut ofp_match.oxm_fields;
+        let masks = &self.masks;
 
-        if let Some(in_port) = &self.in_port {
-            let header = OxmHeader::new(OxmMatchFields::InPort, 4, false);
-            header.marshal(&mut ofp_byte)?;
-            ofp_byte.write_u32::<BigEndian>(*in_port)?;
-        }
-        if let Some(eth_dst) = &self.eth_dst {
-            let header = OxmHeader::new(OxmMatchFields::EthDst, 12, true);
-            header.marshal(&mut ofp_byte)?;
-            eth_dst.marshal(&mut ofp_byte);
-            // mac mask
-            MacAddr::from(!0).marshal(&mut ofp_byte);
-        }
-        if let Some(eth_src) = &self.eth_src {
-            let header = OxmHeader::new(OxmMatchFields::EthSrc, 12, true);
-            header.marshal(&mut ofp_byte)?;
-            eth_src.marshal(&mut ofp_byte);
-            // mac mask
-            MacAddr::from(!0).marshal(&mut ofp_byte);
-        }
-        if let Some(eth_typ) = &self.eth_typ {
-            OxmHeader::new(OxmMatchFields::EthType, 2, false).marshal(&mut ofp_byte)?;
-            ofp_byte.write_u16::<BigEndian>(*eth_typ)?;
-        }
-        if let Some(ip_proto) = &self.ip_proto {
-            OxmHeader::new(OxmMatchFields::IpProto, 1, false).marshal(&mut ofp_byte)?;
-            ofp_byte.write_u8(*ip_proto)?;
-        }
-        if let Some(ipv4_src) = &self.ipv4_src {
-            OxmHeader::new(OxmMatchFields::Ipv4Src, 8, true).marshal(&mut ofp_byte)?;
-            bytes.write_u32::<BigEndian>(ipv4_src.clone().into())?;
-            bytes.write_u32::<BigEndian>(!0)?;
-        }
-        if let Some(ipv4_dst) = &self.ipv4_dst {
-            OxmHeader::new(OxmMatchFields::Ipv4Dst, 8, true).marshal(&mut ofp_byte)?;
-            bytes.write_u32::<BigEndian>(ipv4_dst.clone().into())?;
-            bytes.write_u32::<BigEndian>(!0)?;
-        }
-        if let Some(ipv6_src) = &self.ipv6_src {
-            OxmHeader::new(OxmMatchFields::Ipv6Src, 32, true).marshal(&mut ofp_byte)?;
-            ofp_byte.write_u128::<BigEndian>(ipv6_src.clone().into())?;
-            ofp_byte.write_u128::<BigEndian>(!0)?;
-        }
-        if let Some(ipv6_dst) = &self.ipv6_dst {
-            OxmHeader::new(OxmMatchFields::Ipv6Dst, 32, true).marshal(&mut ofp_byte)?;
-            ofp_byte.write_u128::<BigEndian>(ipv6_dst.clone().into())?;
-            ofp_byte.write_u128::<BigEndian>(!0)?;
-        }
-        if let Some(tcp_src) = &self.tcp_src {
-            OxmHeader::new(OxmMatchFields::TcpSrc, 2, false);
-            ofp_byte.write_u16::<BigEndian>(*tcp_src)?;
-        }
-        if let Some(tcp_dst) = &self.tcp_dst {
-            OxmHeader::new(OxmMatchFields::TcpDst, 2, false);
-            ofp_byte.write_u16::<BigEndian>(*tcp_dst)?;
-        }
-        if let Some(udp_src) = &self.udp_src {
-            OxmHeader::new(OxmMatchFields::UdpSrc, 2, false);
-            ofp_byte.write_u16::<BigEndian>(*udp_src)?;
-        }
-        if let Some(udp_dst) = &self.udp_dst {
-            OxmHeader::new(OxmMatchFields::UdpDst, 2, false);
-            ofp_byte.write_u16::<BigEndian>(*udp_dst)?;
+        write_exact(oxm, OxmMatchFields::InPort, self.in_port)?;
+        write_exact(oxm, OxmMatchFields::InPhyPort, self.in_phy_port)?;
+        write_masked(oxm, OxmMatchFields::METADATA, self.metadata, masks.metadata)?;
+        write_masked(oxm, OxmMatchFields::EthDst, self.eth_dst, masks.eth_dst)?;
+        write_masked(oxm, OxmMatchFields::EthSrc, self.eth_src, masks.eth_src)?;
+        write_exact(oxm, OxmMatchFields::EthType, self.eth_typ)?;
+        write_masked(oxm, OxmMatchFields::VlanVid, self.vlan_vid, masks.vlan_vid)?;
+        write_exact(oxm, OxmMatchFields::VlanPcp, self.vlan_pcp)?;
+        write_exact(oxm, OxmMatchFields::IpDscp, self.ip_dscp)?;
+        write_exact(oxm, OxmMatchFields::IpEcn, self.ip_ecn)?;
+        write_exact(oxm, OxmMatchFields::IpProto, self.ip_proto)?;
+        write_masked(oxm, OxmMatchFields::Ipv4Src, self.ipv4_src, masks.ipv4_src)?;
+        write_masked(oxm, OxmMatchFields::Ipv4Dst, self.ipv4_dst, masks.ipv4_dst)?;
+        write_exact(oxm, OxmMatchFields::TcpSrc, self.tcp_src)?;
+        write_exact(oxm, OxmMatchFields::TcpDst, self.tcp_dst)?;
+        write_exact(oxm, OxmMatchFields::UdpSrc, self.udp_src)?;
+        write_exact(oxm, OxmMatchFields::UdpDst, self.udp_dst)?;
+        write_exact(oxm, OxmMatchFields::SctpSrc, self.sctp_src)?;
+        write_exact(oxm, OxmMatchFields::SctpDst, self.sctp_dst)?;
+        write_exact(oxm, OxmMatchFields::Icmpv4Type, self.icmpv4_type)?;
+        write_exact(oxm, OxmMatchFields::Icmpv4Code, self.icmpv4_code)?;
+        write_exact(oxm, OxmMatchFields::ArpOp, self.arp_op)?;
+        write_masked(oxm, OxmMatchFields::ArpSpa, self.arp_spa, masks.arp_spa)?;
+        write_masked(oxm, OxmMatchFields::ArpTpa, self.arp_tpa, masks.arp_tpa)?;
+        write_masked(oxm, OxmMatchFields::ArpSha, self.arp_sha, masks.arp_sha)?;
+        write_masked(oxm, OxmMatchFields::ArpTha, self.arp_tha, masks.arp_tha)?;
+        write_masked(oxm, OxmMatchFields::Ipv6Src, self.ipv6_src, masks.ipv6_src)?;
+        write_masked(oxm, OxmMatchFields::Ipv6Dst, self.ipv6_dst, masks.ipv6_dst)?;
+        write_masked(
+            oxm,
+            OxmMatchFields::Ipv6Flabel,
+            self.ipv6_flabel,
+            masks.ipv6_flabel,
+        )?;
+        write_exact(oxm, OxmMatchFields::Icmpv6Type, self.icmpv6_type)?;
+        write_exact(oxm, OxmMatchFields::Icmpv6Code, self.icmpv6_code)?;
+        write_exact(oxm, OxmMatchFields::Ipv6NdTarget, self.ipv6_nd_target)?;
+        write_exact(oxm, OxmMatchFields::Ipv6NdSll, self.ipv6_nd_sll)?;
+        write_exact(oxm, OxmMatchFields::Ipv6NdTll, self.ipv6_nd_tll)?;
+        write_exact(oxm, OxmMatchFields::MplsLabel, self.mpls_label)?;
+        write_exact(oxm, OxmMatchFields::MplsTc, self.mpls_tc)?;
+        write_exact(oxm, OxmMatchFields::MplsBos, self.mpls_bos)?;
+        write_masked(
+            oxm,
+            OxmMatchFields::PbbIsid,
+            self.pbb_isid.map(PbbIsid),
+            masks.pbb_isid.map(PbbIsid),
+        )?;
+        write_masked(
+            oxm,
+            OxmMatchFields::TunnelId,
+            self.tunnel_id,
+            masks.tunnel_id,
+        )?;
+        write_masked(
+            oxm,
+            OxmMatchFields::Ipv6Exthdr,
+            self.ipv6_exthdr,
+            masks.ipv6_exthdr,
+        )?;
+        for extension in &self.extensions {
+            extension.marshal(oxm)?;
         }
         ofp_match.marshal(bytes)?;
         Ok(())
     }
 
+    /// Parses a match structure, padding included
+    ///
+    /// Fields of other OXM classes are kept unchanged in `extensions`.
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the match
+    ///
+    /// # Returns
+    /// * `Result<MatchFields, Error>` - The parsed fields or an error if the match is malformed
     pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<MatchFields, Error> {
         let mut matcher = MatchFields::match_all();
 
         let _typ: MatchType = bytes.read_u16::<BigEndian>()?.into();
         let length = bytes.read_u16::<BigEndian>()?;
-        let mut pkt_len = length - 4;
-        while pkt_len > 0 {
-            let _oxm_class = bytes.read_u16::<BigEndian>()?;
-            let oxm_field = bytes.read_u8()?;
+        if length < 4 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("match too short: {}", length),
+            ));
+        }
+        let mut oxm_fields = vec![0; length as usize - 4];
+        bytes.read_exact(&mut oxm_fields)?;
+        bytes.consume(OfpMatch::padding(length));
+
+        let mut oxm_fields = &oxm_fields[..];
+        while !oxm_fields.is_empty() {
+            let oxm_class = oxm_fields.read_u16::<BigEndian>()?;
+            let oxm_field = oxm_fields.read_u8()?;
             let hash_mask = oxm_field & 1 == 1;
-            let oxm_field: OxmMatchFields = (oxm_field >> 1).into();
-            let oxm_length = bytes.read_u8()?;
-            match oxm_field {
-                OxmMatchFields::InPort => {
-                    let port = bytes.read_u32::<BigEndian>()?;
-                    let _mask = if hash_mask {
-                        Some(bytes.read_u32::<BigEndian>())
-                    } else {
-                        None
-                    };
-                    matcher.in_port = Some(port);
-                }
-                OxmMatchFields::EthDst => {
-                    let mut mac = [0u8; 6];
-                    for i in 0..6 {
-                        mac[i] = bytes.read_u8()?;
-                    }
-                    if hash_mask {
-                        bytes.consume(6);
-                    }
-                    matcher.eth_dst = Some(MacAddr::new(mac));
-                }
-                OxmMatchFields::EthSrc => {
-                    let mut mac = [0u8; 6];
-                    for i in 0..6 {
-                        mac[i] = bytes.read_u8()?;
-                    }
-                    if hash_mask {
-                        bytes.consume(6);
-                    }
-                    matcher.eth_src = Some(MacAddr::new(mac));
-                }
-                OxmMatchFields::EthType => {
-                    let eth_typ = bytes.read_u16::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(2);
-                    }
-                    matcher.eth_typ = Some(eth_typ);
-                }
-                OxmMatchFields::IpProto => {
-                    let proto = bytes.read_u8()?;
-                    if hash_mask {
-                        bytes.consume(1);
-                    }
-                    matcher.ip_proto = Some(proto);
-                }
-                OxmMatchFields::Ipv4Src => {
-                    let ip = bytes.read_u32::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(4);
-                    }
-                    matcher.ipv4_src = Some(Ipv4Addr::from(ip));
-                }
-                OxmMatchFields::Ipv4Dst => {
-                    let ip = bytes.read_u32::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(4);
-                    }
-                    matcher.ipv4_dst = Some(Ipv4Addr::from(ip));
-                }
-                OxmMatchFields::Ipv6Src => {
-                    let ipv6 = bytes.read_u128::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(16);
-                    }
-                    matcher.ipv6_src = Some(Ipv6Addr::from(ipv6));
-                }
-                OxmMatchFields::Ipv6Dst => {
-                    let ipv6 = bytes.read_u128::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(16);
-                    }
-                    matcher.ipv6_dst = Some(Ipv6Addr::from(ipv6));
-                }
-                OxmMatchFields::TcpSrc => {
-                    let tcp = bytes.read_u16::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(2);
-                    }
-                    matcher.tcp_src = Some(tcp);
-                }
-                OxmMatchFields::TcpDst => {
-                    let tcp = bytes.read_u16::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(2);
-                    }
-                    matcher.tcp_dst = Some(tcp);
-                }
-                OxmMatchFields::UdpSrc => {
-                    let udp = bytes.read_u16::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(2);
-                    }
-                    matcher.udp_src = Some(udp);
-                }
-                OxmMatchFields::UdpDst => {
-                    let udp = bytes.read_u16::<BigEndian>()?;
-                    if hash_mask {
-                        bytes.consume(2);
-                    }
-                    matcher.udp_dst = Some(udp);
+            let oxm_length = oxm_fields.read_u8()? as usize;
+            if oxm_length > oxm_fields.len() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("oxm field longer than the match: {}", oxm_length),
+                ));
+            }
+            let (payload, rest) = oxm_fields.split_at(oxm_length);
+            oxm_fields = rest;
+            if oxm_class != OxmClass::OpenflowBasic as u16 {
+                matcher.extensions.push(OxmExtension::new(
+                    oxm_class,
+                    oxm_field >> 1,
+                    hash_mask,
+                    payload.to_vec(),
+                ));
+                continue;
+            }
+            // a mask follows the value and has the same size
+            let (value, mask) = if hash_mask {
+                if !oxm_length.is_multiple_of(2) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("masked oxm field of odd length: {}", oxm_length),
+                    ));
                 }
+                let (value, mask) = payload.split_at(oxm_length / 2);
+                (value, Some(mask))
+            } else {
+                (payload, None)
+            };
+            matcher.set_field((oxm_field >> 1).into(), value, mask)?;
+        }
+        Ok(matcher)
+    }
 
-                _ => {
-                    bytes.consume((oxm_length - 4) as usize);
-                }
+    /// Sets a single field from the value and mask of its OXM TLV
+    ///
+    /// # Arguments
+    /// * `field` - The OpenFlow basic match field
+    /// * `value` - Value of the field
+    /// * `mask` - Mask of the field, if present
+    fn set_field(
+        &mut self,
+        field: OxmMatchFields,
+        value: &[u8],
+        mask: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        let masks = &mut self.masks;
+        match field {
+            OxmMatchFields::InPort => self.in_port = Some(read_value(value)?),
+            OxmMatchFields::InPhyPort => self.in_phy_port = Some(read_value(value)?),
+            OxmMatchFields::METADATA => {
+                self.metadata = Some(read_value(value)?);
+                masks.metadata = read_mask(mask)?;
+            }
+            OxmMatchFields::EthDst => {
+                self.eth_dst = Some(read_value(value)?);
+                masks.eth_dst = read_mask(mask)?;
+            }
+            OxmMatchFields::EthSrc => {
+                self.eth_src = Some(read_value(value)?);
+                masks.eth_src = read_mask(mask)?;
+            }
+            OxmMatchFields::EthType => self.eth_typ = Some(read_value(value)?),
+            OxmMatchFields::VlanVid => {
+                self.vlan_vid = Some(read_value(value)?);
+                masks.vlan_vid = read_mask(mask)?;
+            }
+            OxmMatchFields::VlanPcp => self.vlan_pcp = Some(read_value(value)?),
+            OxmMatchFields::IpDscp => self.ip_dscp = Some(read_value(value)?),
+            OxmMatchFields::IpEcn => self.ip_ecn = Some(read_value(value)?),
+            OxmMatchFields::IpProto => self.ip_proto = Some(read_value(value)?),
+            OxmMatchFields::Ipv4Src => {
+                self.ipv4_src = Some(read_value(value)?);
+                masks.ipv4_src = read_mask(mask)?;
+            }
+            OxmMatchFields::Ipv4Dst => {
+                self.ipv4_dst = Some(read_value(value)?);
+                masks.ipv4_dst = read_mask(mask)?;
+            }
+            OxmMatchFields::TcpSrc => self.tcp_src = Some(read_value(value)?),
+            OxmMatchFields::TcpDst => self.tcp_dst = Some(read_value(value)?),
+            OxmMatchFields::UdpSrc => self.udp_src = Some(read_value(value)?),
+            OxmMatchFields::UdpDst => self.udp_dst = Some(read_value(value)?),
+            OxmMatchFields::SctpSrc => self.sctp_src = Some(read_value(value)?),
+            OxmMatchFields::SctpDst => self.sctp_dst = Some(read_value(value)?),
+            OxmMatchFields::Icmpv4Type => self.icmpv4_type = Some(read_value(value)?),
+            OxmMatchFields::Icmpv4Code => self.icmpv4_code = Some(read_value(value)?),
+            OxmMatchFields::ArpOp => self.arp_op = Some(read_value(value)?),
+            OxmMatchFields::ArpSpa => {
+                self.arp_spa = Some(read_value(value)?);
+                masks.arp_spa = read_mask(mask)?;
+            }
+            OxmMatchFields::ArpTpa => {
+                self.arp_tpa = Some(read_value(value)?);
+                masks.arp_tpa = read_mask(mask)?;
+            }
+            OxmMatchFields::ArpSha => {
+                self.arp_sha = Some(read_value(value)?);
+                masks.arp_sha = read_mask(mask)?;
+            }
+            OxmMatchFields::ArpTha => {
+                self.arp_tha = Some(read_value(value)?);
+                masks.arp_tha = read_mask(mask)?;
+            }
+            OxmMatchFields::Ipv6Src => {
+                self.ipv6_src = Some(read_value(value)?);
+                masks.ipv6_src = read_mask(mask)?;
+            }
+            OxmMatchFields::Ipv6Dst => {
+                self.ipv6_dst = Some(read_value(value)?);
+                masks.ipv6_dst = read_mask(mask)?;
+            }
+            OxmMatchFields::Ipv6Flabel => {
+                self.ipv6_flabel = Some(read_value(value)?);
+                masks.ipv6_flabel = read_mask(mask)?;
             }
-            // 4 is size of oxm_tlv_header
-            pkt_len = pkt_len - (oxm_length as u16 + 4);
+            OxmMatchFields::Icmpv6Type => self.icmpv6_type = Some(read_value(value)?),
+            OxmMatchFields::Icmpv6Code => self.icmpv6_code = Some(read_value(value)?),
+            OxmMatchFields::Ipv6NdTarget => self.ipv6_nd_target = Some(read_value(value)?),
+            OxmMatchFields::Ipv6NdSll => self.ipv6_nd_sll = Some(read_value(value)?),
+            OxmMatchFields::Ipv6NdTll => self.ipv6_nd_tll = Some(read_value(value)?),
+            OxmMatchFields::MplsLabel => self.mpls_label = Some(read_value(value)?),
+            OxmMatchFields::MplsTc => self.mpls_tc = Some(read_value(value)?),
+            OxmMatchFields::MplsBos => self.mpls_bos = Some(read_value(value)?),
+            OxmMatchFields::PbbIsid => {
+                self.pbb_isid = Some(read_value::<PbbIsid>(value)?.0);
+                masks.pbb_isid = read_mask::<PbbIsid>(mask)?.map(|mask| mask.0);
+            }
+            OxmMatchFields::TunnelId => {
+                self.tunnel_id = Some(read_value(value)?);
+                masks.tunnel_id = read_mask(mask)?;
+            }
+            OxmMatchFields::Ipv6Exthdr => {
+                self.ipv6_exthdr = Some(read_value(value)?);
+                masks.ipv6_exthdr = read_mask(mask)?;
+            }
+            OxmMatchFields::Unparse => (),
         }
-        if length % 8 != 0 {
-            bytes.consume(4);
+        Ok(())
+    }
+}
+
+/// Value of an OXM field in its wire format
+pub(crate) trait OxmValue: Sized {
+    /// Returns the value in network byte order
+    fn to_oxm(&self) -> Vec<u8>;
+
+    /// Reads the value from the start of the buffer
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error>;
+}
+
+impl OxmValue for u8 {
+    fn to_oxm(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error> {
+        bytes.read_u8()
+    }
+}
+
+impl OxmValue for u16 {
+    fn to_oxm(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error> {
+        bytes.read_u16::<BigEndian>()
+    }
+}
+
+impl OxmValue for u32 {
+    fn to_oxm(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error> {
+        bytes.read_u32::<BigEndian>()
+    }
+}
+
+impl OxmValue for u64 {
+    fn to_oxm(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error> {
+        bytes.read_u64::<BigEndian>()
+    }
+}
+
+impl OxmValue for Ipv4Addr {
+    fn to_oxm(&self) -> Vec<u8> {
+        self.octets().to_vec()
+    }
+
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error> {
+        Ok(Ipv4Addr::from(u32::from_oxm(bytes)?))
+    }
+}
+
+impl OxmValue for Ipv6Addr {
+    fn to_oxm(&self) -> Vec<u8> {
+        self.octets().to_vec()
+    }
+
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error> {
+        Ok(Ipv6Addr::from(bytes.read_u128::<BigEndian>()?))
+    }
+}
+
+impl OxmValue for MacAddr {
+    fn to_oxm(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(6);
+        self.marshal(&mut bytes);
+        bytes
+    }
+
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error> {
+        let mut mac = [0u8; 6];
+        bytes.read_exact(&mut mac)?;
+        Ok(MacAddr::new(mac))
+    }
+}
+
+/// PBB I-SID, the only 3 byte OXM field
+pub(crate) struct PbbIsid(pub(crate) u32);
+
+impl OxmValue for PbbIsid {
+    fn to_oxm(&self) -> Vec<u8> {
+        self.0.to_be_bytes()[1..].to_vec()
+    }
+
+    fn from_oxm(bytes: &mut &[u8]) -> Result<Self, Error> {
+        Ok(PbbIsid(bytes.read_u24::<BigEndian>()?))
+    }
+}
+
+/// Writes an OXM field of the OpenFlow basic class without a mask
+///
+/// # Arguments
+/// * `bytes` - The buffer to write the field to
+/// * `field` - The match field
+/// * `value` - Value of the field, nothing is written when `None`
+pub(crate) fn write_exact<T: OxmValue>(
+    bytes: &mut Vec<u8>,
+    field: OxmMatchFields,
+    value: Option<T>,
+) -> Result<(), Error> {
+    write_masked(bytes, field, value, None)
+}
+
+/// Writes an OXM field of the OpenFlow basic class with an optional mask
+///
+/// An all-ones mask is left out since it matches exactly like no mask.
+///
+/// # Arguments
+/// * `bytes` - The buffer to write the field to
+/// * `field` - The match field
+/// * `value` - Value of the field, nothing is written when `None`
+/// * `mask` - Mask of the field
+fn write_masked<T: OxmValue>(
+    bytes: &mut Vec<u8>,
+    field: OxmMatchFields,
+    value: Option<T>,
+    mask: Option<T>,
+) -> Result<(), Error> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+    let mut value = value.to_oxm();
+    let mask = mask
+        .map(|mask| mask.to_oxm())
+        .filter(|mask| mask.iter().any(|byte| *byte != 0xff));
+    match mask {
+        Some(mask) => {
+            // bits outside of the mask must be zero
+            value.iter_mut().zip(&mask).for_each(|(v, m)| *v &= m);
+            OxmHeader::new(field, (value.len() * 2) as u8, true).marshal(bytes)?;
+            bytes.extend_from_slice(&value);
+            bytes.extend_from_slice(&mask);
         }
-        Ok(matcher)
+        None => {
+            OxmHeader::new(field, value.len() as u8, false).marshal(bytes)?;
+            bytes.extend_from_slice(&value);
+        }
+    }
+    Ok(())
+}
+
+/// Reads the value of an OXM field, which must have exactly the size of the field type
+pub(crate) fn read_value<T: OxmValue>(mut value: &[u8]) -> Result<T, Error> {
+    let len = value.len();
+    match T::from_oxm(&mut value) {
+        Ok(field) if value.is_empty() => Ok(field),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("oxm value of invalid length: {}", len),
+        )),
+    }
+}
+
+/// Reads the mask of an OXM field, an all-ones mask is returned as `None`
+fn read_mask<T: OxmValue>(mask: Option<&[u8]>) -> Result<Option<T>, Error> {
+    match mask {
+        Some(mask) if mask.iter().any(|byte| *byte != 0xff) => Ok(Some(read_value(mask)?)),
+        _ => Ok(None),
     }
 }
@@ -6,7 +6,7 @@
 
 /// Flow modification handler implementation
 pub mod flow_mod_handler;
-pub use flow_mod_handler::FlowModEvent;
+pub use flow_mod_handler::{FlowModEvent, Timeout};
 
 /// Flow modification command definitions
 pub mod command;
@@ -14,7 +14,9 @@ pub use command::FlowModCommand;
 
 /// Match fields and match type definitions
 pub mod match_fields;
-pub use match_fields::{MatchFields, MatchType, OfpMatch};
+pub use match_fields::{
+    MatchFields, MatchMasks, MatchType, OfpMatch, OxmExtension, OxmMatchFields,
+};
 
 /// Flow modification flags definitions
 pub mod flow_mod_flags;
@@ -22,3 +24,7 @@ pub use flow_mod_flags::FlowModFlags;
 
 /// Flow modification instruction definitions
 pub mod instructions;
+
+/// Ordered instruction set of a flow entry
+pub mod instruction_set;
+pub use instruction_set::InstructionSet;
@@ -0,0 +1,108 @@
+//! OpenFlow v1.3 Flow-Removed Message Implementation
+//!
+//! This module implements the Flow-Removed message type used in OpenFlow v1.3 protocol.
+//! Flow-Removed messages are sent by the switch when a flow entry installed with the
+//! `send_flow_rem` flag expires or is deleted, and carry the final counters of the entry.
+
+use super::{MatchFields, Timeout};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::io::{Cursor, Error};
+
+/// Represents the reason why a flow entry was removed
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+pub enum FlowRemovedReason {
+    /// Flow idle time exceeded idle_timeout
+    IdleTimeout,
+    /// Time exceeded hard_timeout
+    HardTimeout,
+    /// Evicted by a DELETE flow mod
+    Delete,
+    /// Group was removed
+    GroupDelete,
+    /// Unknown reason code
+    Unknown(u8),
+}
+
+impl FlowRemovedReason {
+    /// Creates a new FlowRemovedReason from a reason code
+    ///
+    /// # Arguments
+    /// * `code` - The numeric reason code
+    ///
+    /// # Returns
+    /// The corresponding FlowRemovedReason variant
+    fn new(code: u8) -> Self {
+        match code {
+            0 => FlowRemovedReason::IdleTimeout,
+            1 => FlowRemovedReason::HardTimeout,
+            2 => FlowRemovedReason::Delete,
+            3 => FlowRemovedReason::GroupDelete,
+            t => FlowRemovedReason::Unknown(t),
+        }
+    }
+}
+
+/// Represents a Flow-Removed message in OpenFlow v1.3
+#[derive(Debug)]
+pub struct FlowRemovedEvent {
+    /// Opaque controller-issued identifier of the flow entry
+    pub cookie: u64,
+    /// Priority level of the flow entry
+    pub priority: u16,
+    /// Reason why the flow entry was removed
+    pub reason: FlowRemovedReason,
+    /// ID of the table the flow entry was in
+    pub table_id: u8,
+    /// Time the flow entry was alive in seconds
+    pub duration_sec: u32,
+    /// Time the flow entry was alive in nanoseconds beyond duration_sec
+    pub duration_nsec: u32,
+    /// Idle timeout of the original flow mod
+    pub idle_timeout: Timeout,
+    /// Hard timeout of the original flow mod
+    pub hard_timeout: Timeout,
+    /// Number of packets matched by the flow entry
+    pub packet_count: u64,
+    /// Number of bytes matched by the flow entry
+    pub byte_count: u64,
+    /// Match fields of the flow entry
+    pub match_fields: MatchFields,
+}
+
+impl FlowRemovedEvent {
+    /// Parses a flow-removed event from a byte buffer
+    ///
+    /// # Arguments
+    /// * `payload` - The byte buffer containing the flow-removed message body
+    ///
+    /// # Returns
+    /// Result containing either the parsed FlowRemovedEvent or an error
+    pub fn parse(payload: &[u8]) -> Result<FlowRemovedEvent, Error> {
+        let mut bytes = Cursor::new(payload.to_vec());
+        let cookie = bytes.read_u64::<BigEndian>()?;
+        let priority = bytes.read_u16::<BigEndian>()?;
+        let reason = FlowRemovedReason::new(bytes.read_u8()?);
+        let table_id = bytes.read_u8()?;
+        let duration_sec = bytes.read_u32::<BigEndian>()?;
+        let duration_nsec = bytes.read_u32::<BigEndian>()?;
+        let idle_timeout = Timeout::parse(bytes.read_u16::<BigEndian>()?);
+        let hard_timeout = Timeout::parse(bytes.read_u16::<BigEndian>()?);
+        let packet_count = bytes.read_u64::<BigEndian>()?;
+        let byte_count = bytes.read_u64::<BigEndian>()?;
+        let match_fields = MatchFields::parse(&mut bytes)?;
+        Ok(FlowRemovedEvent {
+            cookie,
+            priority,
+            reason,
+            table_id,
+            duration_sec,
+            duration_nsec,
+            idle_timeout,
+            hard_timeout,
+            packet_count,
+            byte_count,
+            match_fields,
+        })
+    }
+}
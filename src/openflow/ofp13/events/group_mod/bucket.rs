@@ -0,0 +1,146 @@
+//! OpenFlow v1.3 Group Buckets
+//!
+//! This module implements the action buckets of a group. Every bucket carries
+//! a list of actions; select groups weigh their buckets and fast failover
+//! groups watch a port or group to decide whether a bucket is live.
+
+use std::io::{Cursor, Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp13::{events::Action, ofp_port::OfpPort};
+
+use super::OfpGroup;
+
+/// An action bucket of a group (`ofp_bucket`)
+#[derive(Clone, Debug)]
+pub struct Bucket {
+    /// Relative weight of the bucket, only used by select groups
+    pub weight: u16,
+    /// Port whose state decides if the bucket is live, only used by fast failover groups
+    pub watch_port: u32,
+    /// Group whose state decides if the bucket is live, only used by fast failover groups
+    pub watch_group: u32,
+    /// Actions applied by the bucket
+    pub actions: Vec<Action>,
+}
+
+impl Bucket {
+    /// Size of the bucket header in bytes
+    pub const SIZE: usize = 16;
+
+    /// Creates a new bucket without weight and watches
+    ///
+    /// # Arguments
+    /// * `actions` - Actions applied by the bucket
+    pub fn new(actions: Vec<Action>) -> Self {
+        Self {
+            weight: 0,
+            watch_port: OfpPort::Any as u32,
+            watch_group: OfpGroup::Any as u32,
+            actions,
+        }
+    }
+
+    /// Sets the weight used by select groups
+    ///
+    /// # Arguments
+    /// * `weight` - Relative weight of the bucket
+    pub fn weight(mut self, weight: u16) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the port watched by fast failover groups
+    ///
+    /// # Arguments
+    /// * `watch_port` - Port whose liveness decides if the bucket is used
+    pub fn watch_port(mut self, watch_port: u32) -> Self {
+        self.watch_port = watch_port;
+        self
+    }
+
+    /// Sets the group watched by fast failover groups
+    ///
+    /// # Arguments
+    /// * `watch_group` - Group whose liveness decides if the bucket is used
+    pub fn watch_group(mut self, watch_group: u32) -> Self {
+        self.watch_group = watch_group;
+        self
+    }
+
+    /// Marshals the bucket into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the bucket to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
+        let mut action_bytes = Vec::new();
+        for action in &self.actions {
+            action.marshal(&mut action_bytes)?;
+        }
+        bytes.write_u16::<BigEndian>((Self::SIZE + action_bytes.len()) as u16)?;
+        bytes.write_u16::<BigEndian>(self.weight)?;
+        bytes.write_u32::<BigEndian>(self.watch_port)?;
+        bytes.write_u32::<BigEndian>(self.watch_group)?;
+        // padding
+        bytes.write_u32::<BigEndian>(0)?;
+        bytes.append(&mut action_bytes);
+        Ok(())
+    }
+
+    /// Parses a single bucket
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the bucket
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let start = bytes.position();
+        let length = bytes.read_u16::<BigEndian>()? as usize;
+        if length < Self::SIZE || !length.is_multiple_of(8) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid bucket length: {}", length),
+            ));
+        }
+        let weight = bytes.read_u16::<BigEndian>()?;
+        let watch_port = bytes.read_u32::<BigEndian>()?;
+        let watch_group = bytes.read_u32::<BigEndian>()?;
+        // padding
+        bytes.read_u32::<BigEndian>()?;
+        let actions = Action::parse_list(bytes, length - Self::SIZE)?;
+        if bytes.position() != start + length as u64 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("bucket actions overrun the bucket length {}", length),
+            ));
+        }
+        Ok(Self {
+            weight,
+            watch_port,
+            watch_group,
+            actions,
+        })
+    }
+
+    /// Parses a list of buckets filling the whole buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the first bucket
+    /// * `len` - Total length of the bucket list in bytes
+    pub fn parse_list(bytes: &mut Cursor<Vec<u8>>, len: usize) -> Result<Vec<Self>, Error> {
+        let end = bytes.position() + len as u64;
+        let mut buckets = Vec::new();
+        while bytes.position() < end {
+            let start = bytes.position();
+            let length = bytes.read_u16::<BigEndian>()? as u64;
+            if start + length > end {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("bucket of length {} overruns the bucket list", length),
+                ));
+            }
+            bytes.set_position(start);
+            buckets.push(Bucket::parse(bytes)?);
+        }
+        Ok(buckets)
+    }
+}
@@ -0,0 +1,89 @@
+//! OpenFlow v1.3 Group Modification Commands
+//!
+//! This module defines the commands used to modify group entries, the types of
+//! groups and the reserved group numbers.
+
+use std::io::{Error, ErrorKind};
+
+/// Commands for modifying group entries in the OpenFlow switch
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u16)]
+pub enum GroupModCommand {
+    /// Add a new group
+    Add = 0,
+    /// Modify all matching groups
+    Modify = 1,
+    /// Delete all matching groups
+    Delete = 2,
+}
+
+impl TryFrom<u16> for GroupModCommand {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Add),
+            1 => Ok(Self::Modify),
+            2 => Ok(Self::Delete),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown group mod command {}", value),
+            )),
+        }
+    }
+}
+
+impl From<GroupModCommand> for u16 {
+    fn from(value: GroupModCommand) -> Self {
+        value as u16
+    }
+}
+
+/// Types of groups
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum GroupType {
+    /// Execute all buckets, used for multicast and flooding
+    All = 0,
+    /// Execute one bucket, chosen by the switch, used for multipath
+    Select = 1,
+    /// Execute the single bucket of the group, used for indirection
+    Indirect = 2,
+    /// Execute the first live bucket
+    FastFailover = 3,
+}
+
+impl TryFrom<u8> for GroupType {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::All),
+            1 => Ok(Self::Select),
+            2 => Ok(Self::Indirect),
+            3 => Ok(Self::FastFailover),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown group type {}", value),
+            )),
+        }
+    }
+}
+
+impl From<GroupType> for u8 {
+    fn from(value: GroupType) -> Self {
+        value as u8
+    }
+}
+
+/// Reserved group numbers
+#[repr(u32)]
+#[derive(Debug)]
+pub enum OfpGroup {
+    /// Last usable group number (0xffffff00)
+    Max = 0xffffff00,
+    /// Represents all groups for group delete commands
+    All = 0xfffffffc,
+    /// Wildcard group used only for flow stats requests
+    Any = 0xffffffff,
+}
@@ -0,0 +1,118 @@
+//! OpenFlow v1.3 Group Modification Handler
+//!
+//! This module implements the group modification message of OpenFlow v1.3.
+//! It provides functionality to add, modify and delete groups in the switch's group table.
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+use super::{Bucket, GroupModCommand, GroupType, OfpGroup};
+
+/// Group modification event structure
+pub struct GroupModEvent {
+    /// Command to apply (add, modify, delete)
+    pub command: GroupModCommand,
+    /// Type of the group
+    pub group_type: GroupType,
+    /// ID of the group
+    pub group_id: u32,
+    /// Action buckets of the group
+    pub buckets: Vec<Bucket>,
+}
+
+impl GroupModEvent {
+    /// Creates a new group modification event
+    ///
+    /// # Arguments
+    /// * `command` - Command to apply
+    /// * `group_type` - Type of the group
+    /// * `group_id` - ID of the group
+    /// * `buckets` - Action buckets of the group
+    ///
+    /// # Returns
+    /// * `GroupModEvent` - The new group modification event
+    pub fn new(
+        command: GroupModCommand,
+        group_type: GroupType,
+        group_id: u32,
+        buckets: Vec<Bucket>,
+    ) -> Self {
+        Self {
+            command,
+            group_type,
+            group_id,
+            buckets,
+        }
+    }
+
+    /// Creates an event adding a new group
+    ///
+    /// # Arguments
+    /// * `group_type` - Type of the group
+    /// * `group_id` - ID of the group
+    /// * `buckets` - Action buckets of the group
+    pub fn add_group(group_type: GroupType, group_id: u32, buckets: Vec<Bucket>) -> Self {
+        Self::new(GroupModCommand::Add, group_type, group_id, buckets)
+    }
+
+    /// Creates an event replacing the type and buckets of an existing group
+    ///
+    /// # Arguments
+    /// * `group_type` - New type of the group
+    /// * `group_id` - ID of the group
+    /// * `buckets` - New action buckets of the group
+    pub fn modify_group(group_type: GroupType, group_id: u32, buckets: Vec<Bucket>) -> Self {
+        Self::new(GroupModCommand::Modify, group_type, group_id, buckets)
+    }
+
+    /// Creates an event deleting a group and every flow entry forwarding to it
+    ///
+    /// # Arguments
+    /// * `group_id` - ID of the group, or `OfpGroup::All` for every group
+    pub fn delete_group(group_id: u32) -> Self {
+        Self::new(
+            GroupModCommand::Delete,
+            GroupType::All,
+            group_id,
+            Vec::new(),
+        )
+    }
+
+    /// Creates an event deleting every group
+    pub fn delete_all() -> Self {
+        Self::delete_group(OfpGroup::All as u32)
+    }
+}
+
+impl MessageMarshal for GroupModEvent {
+    /// Returns the message type as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::GroupMod as usize
+    }
+
+    /// Returns the size of the message in bytes
+    fn size_of(&self) -> usize {
+        8
+    }
+
+    /// Returns the message type
+    fn msg_code(&self) -> Msg {
+        Msg::GroupMod
+    }
+
+    /// Marshals the message into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the message to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u16::<BigEndian>(self.command.clone().into());
+        let _ = bytes.write_u8(self.group_type.clone().into());
+        // padding
+        let _ = bytes.write_u8(0);
+        let _ = bytes.write_u32::<BigEndian>(self.group_id);
+        for bucket in &self.buckets {
+            let _ = bucket.marshal(bytes);
+        }
+    }
+}
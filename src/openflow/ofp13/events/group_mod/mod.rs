@@ -0,0 +1,17 @@
+//! OpenFlow v1.3 Group Modification Implementation
+//!
+//! This module implements the group modification functionality for OpenFlow v1.3 protocol.
+//! Groups let several flow entries share a set of action buckets, which is used for
+//! flooding, multipath load sharing, indirection and fast failover forwarding.
+
+/// Group modification handler implementation
+pub mod group_mod_handler;
+pub use group_mod_handler::GroupModEvent;
+
+/// Group modification command and group type definitions
+pub mod command;
+pub use command::{GroupModCommand, GroupType, OfpGroup};
+
+/// Action bucket definitions
+pub mod bucket;
+pub use bucket::Bucket;
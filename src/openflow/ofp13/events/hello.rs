@@ -2,23 +2,58 @@
 //!
 //! This module implements the Hello message type used in OpenFlow v1.3 protocol.
 //! The Hello message is the first message exchanged between the controller and switch
-//! to establish a connection.
+//! to establish a connection. Its body lists the supported protocol versions in a
+//! version bitmap element.
 
-use crate::openflow::ofp13::{MessageMarshal, Msg};
+use std::io::Error;
+
+use crate::openflow::{
+    hello::{Hello, HelloElement},
+    ofp13::{MessageMarshal, Msg},
+};
 
 /// Represents an OpenFlow v1.3 Hello message
 ///
 /// The Hello message is used to establish a connection between the controller and switch.
-/// It has no payload and is the first message sent in the OpenFlow protocol handshake.
-pub struct HelloEvent {}
+/// It is the first message sent in the OpenFlow protocol handshake.
+pub struct HelloEvent {
+    /// Elements of the message body
+    pub elements: Vec<HelloElement>,
+}
 
 impl HelloEvent {
-    /// Creates a new Hello message
+    /// Creates a new Hello message advertising OpenFlow v1.3 only
     ///
     /// # Returns
     /// A new HelloEvent instance
     pub fn new() -> Self {
-        HelloEvent {}
+        Self::with_versions(&[0x04])
+    }
+
+    /// Creates a new Hello message advertising several protocol versions
+    ///
+    /// # Arguments
+    /// * `versions` - Supported protocol versions, e.g. `[0x01, 0x04]`
+    pub fn with_versions(versions: &[u8]) -> Self {
+        HelloEvent {
+            elements: Hello::new(versions).elements,
+        }
+    }
+
+    /// Parses the body of a Hello message
+    ///
+    /// # Arguments
+    /// * `bytes` - The message body, header excluded
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(HelloEvent {
+            elements: HelloElement::parse_all(bytes)?,
+        })
+    }
+}
+
+impl Default for HelloEvent {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -27,8 +62,12 @@ impl MessageMarshal for HelloEvent {
     /// Marshals the Hello message into a byte vector
     ///
     /// # Arguments
-    /// * `_` - The target byte vector (unused as Hello has no payload)
-    fn marshal(&self, _: &mut Vec<u8>) {}
+    /// * `bytes` - The target byte vector
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        for element in &self.elements {
+            element.marshal(bytes);
+        }
+    }
 
     /// Returns the OpenFlow message code for Hello
     ///
@@ -41,9 +80,11 @@ impl MessageMarshal for HelloEvent {
     /// Returns the size of the Hello message
     ///
     /// # Returns
-    /// 0 as Hello message has no payload
+    /// The size of every element, padding included
     fn size_of(&self) -> usize {
-        0
+        let mut bytes = Vec::new();
+        self.marshal(&mut bytes);
+        bytes.len()
     }
 
     /// Returns the message code as a usize
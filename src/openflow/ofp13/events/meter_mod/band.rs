@@ -0,0 +1,196 @@
+//! OpenFlow v1.3 Meter Bands
+//!
+//! This module implements the bands of a meter. A band applies once the rate
+//! measured by the meter exceeds the rate of the band; the band with the highest
+//! rate below the measured rate is used.
+
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+/// A band of a meter (`ofp_meter_band_header`)
+#[derive(Clone, Debug, PartialEq)]
+pub enum MeterBand {
+    /// Drop packets above the rate
+    Drop {
+        /// Rate above which packets are dropped
+        rate: u32,
+        /// Size of bursts
+        burst_size: u32,
+    },
+    /// Raise the drop precedence of the DSCP field of packets above the rate
+    DscpRemark {
+        /// Rate above which packets are remarked
+        rate: u32,
+        /// Size of bursts
+        burst_size: u32,
+        /// Number of drop precedence levels to add
+        prec_level: u8,
+    },
+    /// Experimenter band
+    Experimenter {
+        /// Rate above which the band applies
+        rate: u32,
+        /// Size of bursts
+        burst_size: u32,
+        /// Experimenter id
+        experimenter: u32,
+        /// Experimenter defined data following the id, a multiple of 8 bytes
+        data: Vec<u8>,
+    },
+}
+
+impl MeterBand {
+    /// Size of a band in bytes, experimenter data excluded
+    pub const SIZE: usize = 16;
+
+    /// Returns the length of the band in bytes
+    pub fn size_of(&self) -> usize {
+        match self {
+            MeterBand::Experimenter { data, .. } => Self::SIZE + data.len(),
+            _ => Self::SIZE,
+        }
+    }
+
+    /// Returns the band type code
+    pub fn band_type(&self) -> u16 {
+        match self {
+            MeterBand::Drop { .. } => 1,
+            MeterBand::DscpRemark { .. } => 2,
+            MeterBand::Experimenter { .. } => 0xffff,
+        }
+    }
+
+    /// Checks that the experimenter data keeps the band a multiple of 8 bytes long
+    pub fn validate(&self) -> Result<(), Error> {
+        if !self.size_of().is_multiple_of(8) || self.size_of() > u16::MAX as usize {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid meter band length {}", self.size_of()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Marshals the band into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the band to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
+        self.validate()?;
+        bytes.write_u16::<BigEndian>(self.band_type())?;
+        bytes.write_u16::<BigEndian>(self.size_of() as u16)?;
+        match self {
+            MeterBand::Drop { rate, burst_size } => {
+                bytes.write_u32::<BigEndian>(*rate)?;
+                bytes.write_u32::<BigEndian>(*burst_size)?;
+                // padding
+                bytes.write_u32::<BigEndian>(0)?;
+            }
+            MeterBand::DscpRemark {
+                rate,
+                burst_size,
+                prec_level,
+            } => {
+                bytes.write_u32::<BigEndian>(*rate)?;
+                bytes.write_u32::<BigEndian>(*burst_size)?;
+                bytes.write_u8(*prec_level)?;
+                // padding
+                bytes.write_u24::<BigEndian>(0)?;
+            }
+            MeterBand::Experimenter {
+                rate,
+                burst_size,
+                experimenter,
+                data,
+            } => {
+                bytes.write_u32::<BigEndian>(*rate)?;
+                bytes.write_u32::<BigEndian>(*burst_size)?;
+                bytes.write_u32::<BigEndian>(*experimenter)?;
+                bytes.extend_from_slice(data);
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a single band
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the band
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let typ = bytes.read_u16::<BigEndian>()?;
+        let len = bytes.read_u16::<BigEndian>()? as usize;
+        // Only experimenter bands may carry data beyond the common fields
+        let valid = match typ {
+            0xffff => len >= Self::SIZE && len.is_multiple_of(8),
+            _ => len == Self::SIZE,
+        };
+        if !valid {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid meter band length {}", len),
+            ));
+        }
+        let rate = bytes.read_u32::<BigEndian>()?;
+        let burst_size = bytes.read_u32::<BigEndian>()?;
+        let band = match typ {
+            1 => {
+                // padding
+                bytes.read_u32::<BigEndian>()?;
+                MeterBand::Drop { rate, burst_size }
+            }
+            2 => {
+                let prec_level = bytes.read_u8()?;
+                // padding
+                bytes.read_u24::<BigEndian>()?;
+                MeterBand::DscpRemark {
+                    rate,
+                    burst_size,
+                    prec_level,
+                }
+            }
+            0xffff => {
+                let experimenter = bytes.read_u32::<BigEndian>()?;
+                let mut data = vec![0; len - Self::SIZE];
+                bytes.read_exact(&mut data)?;
+                MeterBand::Experimenter {
+                    rate,
+                    burst_size,
+                    experimenter,
+                    data,
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unknown meter band type {}", typ),
+                ))
+            }
+        };
+        Ok(band)
+    }
+
+    /// Parses a list of bands filling the whole buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the first band
+    /// * `len` - Total length of the band list in bytes
+    pub fn parse_list(bytes: &mut Cursor<Vec<u8>>, len: usize) -> Result<Vec<Self>, Error> {
+        let end = bytes.position() + len as u64;
+        let mut bands = Vec::new();
+        while bytes.position() < end {
+            let start = bytes.position();
+            bytes.read_u16::<BigEndian>()?;
+            let length = bytes.read_u16::<BigEndian>()? as u64;
+            if start + length > end {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("meter band of length {} overruns the band list", length),
+                ));
+            }
+            bytes.set_position(start);
+            bands.push(MeterBand::parse(bytes)?);
+        }
+        Ok(bands)
+    }
+}
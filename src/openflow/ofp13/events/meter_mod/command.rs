@@ -0,0 +1,105 @@
+//! OpenFlow v1.3 Meter Modification Commands
+//!
+//! This module defines the commands used to modify meter entries, the meter
+//! configuration flags and the reserved meter numbers.
+
+use std::io::{Error, ErrorKind};
+
+/// Commands for modifying meter entries in the OpenFlow switch
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u16)]
+pub enum MeterModCommand {
+    /// Add a new meter
+    Add = 0,
+    /// Modify the specified meter
+    Modify = 1,
+    /// Delete the specified meter
+    Delete = 2,
+}
+
+impl TryFrom<u16> for MeterModCommand {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Add),
+            1 => Ok(Self::Modify),
+            2 => Ok(Self::Delete),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown meter mod command {}", value),
+            )),
+        }
+    }
+}
+
+impl From<MeterModCommand> for u16 {
+    fn from(value: MeterModCommand) -> Self {
+        value as u16
+    }
+}
+
+/// Meter configuration flags
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeterFlags {
+    /// Rates are in kilobits per second
+    pub kbps: bool,
+    /// Rates are in packets per second
+    pub pktps: bool,
+    /// Use the burst sizes of the bands
+    pub burst: bool,
+    /// Collect statistics
+    pub stats: bool,
+}
+
+impl MeterFlags {
+    /// Flags for a meter measuring kilobits per second
+    pub fn kbps() -> Self {
+        Self {
+            kbps: true,
+            ..Self::default()
+        }
+    }
+
+    /// Flags for a meter measuring packets per second
+    pub fn pktps() -> Self {
+        Self {
+            pktps: true,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<u16> for MeterFlags {
+    fn from(value: u16) -> Self {
+        Self {
+            kbps: value & 1 == 1,
+            pktps: value >> 1 & 1 == 1,
+            burst: value >> 2 & 1 == 1,
+            stats: value >> 3 & 1 == 1,
+        }
+    }
+}
+
+impl From<MeterFlags> for u16 {
+    fn from(value: MeterFlags) -> Self {
+        (value.kbps as u16)
+            | (value.pktps as u16) << 1
+            | (value.burst as u16) << 2
+            | (value.stats as u16) << 3
+    }
+}
+
+/// Reserved meter numbers
+#[repr(u32)]
+#[derive(Debug)]
+pub enum OfpMeter {
+    /// Last usable meter number (0xffff0000)
+    Max = 0xffff0000,
+    /// Meter for the slow datapath
+    SlowPath = 0xfffffffd,
+    /// Meter for the controller connection
+    Controller = 0xfffffffe,
+    /// Represents all meters for statistics requests and delete commands
+    All = 0xffffffff,
+}
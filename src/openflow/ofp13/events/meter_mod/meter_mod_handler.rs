@@ -0,0 +1,139 @@
+//! OpenFlow v1.3 Meter Modification Handler
+//!
+//! This module implements the meter modification message of OpenFlow v1.3.
+//! It provides functionality to add, modify and delete meters in the switch's meter table.
+
+use std::io::Error;
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+use super::{MeterBand, MeterFlags, MeterModCommand, OfpMeter};
+
+/// Meter modification event structure
+pub struct MeterModEvent {
+    /// Command to apply (add, modify, delete)
+    pub command: MeterModCommand,
+    /// Meter configuration flags
+    pub flags: MeterFlags,
+    /// ID of the meter
+    pub meter_id: u32,
+    /// Bands of the meter
+    pub bands: Vec<MeterBand>,
+}
+
+impl MeterModEvent {
+    /// Creates a new meter modification event
+    ///
+    /// # Arguments
+    /// * `command` - Command to apply
+    /// * `flags` - Meter configuration flags
+    /// * `meter_id` - ID of the meter
+    /// * `bands` - Bands of the meter
+    ///
+    /// # Returns
+    /// * `MeterModEvent` - The new meter modification event
+    pub fn new(
+        command: MeterModCommand,
+        flags: MeterFlags,
+        meter_id: u32,
+        bands: Vec<MeterBand>,
+    ) -> Self {
+        Self {
+            command,
+            flags,
+            meter_id,
+            bands,
+        }
+    }
+
+    /// Creates an event adding a new meter
+    ///
+    /// # Arguments
+    /// * `flags` - Meter configuration flags
+    /// * `meter_id` - ID of the meter
+    /// * `bands` - Bands of the meter
+    pub fn add_meter(flags: MeterFlags, meter_id: u32, bands: Vec<MeterBand>) -> Self {
+        Self::new(MeterModCommand::Add, flags, meter_id, bands)
+    }
+
+    /// Creates an event replacing the flags and bands of an existing meter
+    ///
+    /// # Arguments
+    /// * `flags` - New meter configuration flags
+    /// * `meter_id` - ID of the meter
+    /// * `bands` - New bands of the meter
+    pub fn modify_meter(flags: MeterFlags, meter_id: u32, bands: Vec<MeterBand>) -> Self {
+        Self::new(MeterModCommand::Modify, flags, meter_id, bands)
+    }
+
+    /// Creates an event deleting a meter and every flow entry using it
+    ///
+    /// # Arguments
+    /// * `meter_id` - ID of the meter, or `OfpMeter::All` for every meter
+    pub fn delete_meter(meter_id: u32) -> Self {
+        Self::new(
+            MeterModCommand::Delete,
+            MeterFlags::default(),
+            meter_id,
+            Vec::new(),
+        )
+    }
+
+    /// Creates an event deleting every meter
+    pub fn delete_all() -> Self {
+        Self::delete_meter(OfpMeter::All as u32)
+    }
+
+    /// Creates an event adding a meter that drops traffic above a rate
+    ///
+    /// # Arguments
+    /// * `meter_id` - ID of the meter
+    /// * `rate_kbps` - Rate limit in kilobits per second
+    pub fn rate_limit(meter_id: u32, rate_kbps: u32) -> Self {
+        Self::add_meter(
+            MeterFlags::kbps(),
+            meter_id,
+            vec![MeterBand::Drop {
+                rate: rate_kbps,
+                burst_size: 0,
+            }],
+        )
+    }
+}
+
+impl MessageMarshal for MeterModEvent {
+    /// Returns the message type as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::MeterMod as usize
+    }
+
+    /// Returns the size of the message in bytes
+    fn size_of(&self) -> usize {
+        8
+    }
+
+    /// Returns the message type
+    fn msg_code(&self) -> Msg {
+        Msg::MeterMod
+    }
+
+    /// Marshals the message into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the message to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u16::<BigEndian>(self.command.clone().into());
+        let _ = bytes.write_u16::<BigEndian>(self.flags.clone().into());
+        let _ = bytes.write_u32::<BigEndian>(self.meter_id);
+        for band in &self.bands {
+            let _ = band.marshal(bytes);
+        }
+    }
+
+    /// Checks every band of the meter, see `MeterBand::validate`
+    fn validate(&self) -> Result<(), Error> {
+        self.bands.iter().try_for_each(MeterBand::validate)
+    }
+}
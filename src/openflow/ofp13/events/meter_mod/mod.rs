@@ -0,0 +1,18 @@
+//! OpenFlow v1.3 Meter Modification Implementation
+//!
+//! This module implements the meter modification functionality for OpenFlow v1.3 protocol.
+//! Meters measure the rate of the packets assigned to them and apply their bands,
+//! which drop or remark packets above a configured rate. Flows are attached to a
+//! meter through the meter instruction.
+
+/// Meter modification handler implementation
+pub mod meter_mod_handler;
+pub use meter_mod_handler::MeterModEvent;
+
+/// Meter modification command, flag and reserved meter definitions
+pub mod command;
+pub use command::{MeterFlags, MeterModCommand, OfpMeter};
+
+/// Meter band definitions
+pub mod band;
+pub use band::MeterBand;
@@ -12,22 +12,84 @@ pub use error::ErrorEvent;
 pub mod packet_in;
 pub use packet_in::{PacketInEvent, PacketInReason};
 
+/// Flow-removed message handling module
+pub mod flow_removed;
+pub use flow_removed::{FlowRemovedEvent, FlowRemovedReason};
+
+/// Port-status message handling module
+pub mod port_status;
+pub use port_status::{PortStatusEvent, PortStatusReason};
+
 /// Packet-out message handling module
 pub mod packet_out;
 pub use packet_out::PacketOutEvent;
 
 /// Flow modification message handling module
 pub mod flow_mod;
-pub use flow_mod::{FlowModCommand, FlowModEvent, FlowModFlags, MatchFields};
+pub use flow_mod::{
+    FlowModCommand, FlowModEvent, FlowModFlags, InstructionSet, MatchFields, MatchMasks,
+    OxmExtension, OxmMatchFields, Timeout,
+};
+
+/// Group modification message handling module
+pub mod group_mod;
+pub use group_mod::{Bucket, GroupModCommand, GroupModEvent, GroupType, OfpGroup};
+
+/// Meter modification message handling module
+pub mod meter_mod;
+pub use meter_mod::{MeterBand, MeterFlags, MeterModCommand, MeterModEvent, OfpMeter};
+
+/// Port modification message handling module
+pub mod port_mod;
+pub use port_mod::PortModEvent;
+
+/// Table modification message handling module
+pub mod table_mod;
+pub use table_mod::TableModEvent;
+
+/// Multipart request and reply handling module
+pub mod multipart;
+pub use multipart::{
+    AggregateStats, AggregateStatsRequestEvent, DescRequestEvent, FlowStats, FlowStatsRequestEvent,
+    GroupDesc, GroupDescRequestEvent, GroupFeatures, GroupFeaturesRequestEvent, GroupStats,
+    GroupStatsRequestEvent, MeterConfig, MeterConfigRequestEvent, MeterFeatures,
+    MeterFeaturesRequestEvent, MeterStats, MeterStatsRequestEvent, MultipartReplyBody,
+    MultipartReplyEvent, MultipartType, PortDescRequestEvent, PortStats, PortStatsRequestEvent,
+    QueueStats, QueueStatsRequestEvent, SwitchDescription, TableFeatureProp, TableFeatures,
+    TableFeaturesRequestEvent, TableStats, TableStatsRequestEvent,
+};
 
 /// Action definitions for flow entries
 pub mod actions;
-pub use actions::Action;
+pub use actions::{Action, ActionType, ExperimenterAction, SetField};
+
+/// Experimenter message handling module
+pub mod experimenter;
+pub use experimenter::ExperimenterEvent;
 
 /// Hello message handling module
 pub mod hello;
 pub use hello::HelloEvent;
 
+/// Barrier request message handling module
+pub mod barrier;
+pub use barrier::BarrierRequestEvent;
+
+/// Controller role message handling module
+pub mod role;
+pub use role::{ControllerRole, RoleReplyEvent, RoleRequestEvent};
+
+/// Asynchronous message configuration module
+pub mod async_config;
+pub use async_config::{
+    AsyncConfig, AsyncMasks, FlowRemovedReasons, GetAsyncRequestEvent, PacketInReasons,
+    PortStatusReasons, SetAsyncEvent,
+};
+
+/// Switch configuration message handling module
+pub mod switch_config;
+pub use switch_config::{FragHandling, GetConfigRequestEvent, SetConfigEvent, SwitchConfig};
+
 /// Features request message handling module
 pub mod features_req;
 pub use features_req::FeaturesReqEvent;
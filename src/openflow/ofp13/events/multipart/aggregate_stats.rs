@@ -0,0 +1,99 @@
+//! OpenFlow v1.3 Aggregate Flow Statistics
+//!
+//! This module implements the `OFPMP_AGGREGATE` multipart request and its reply.
+//! The request selects flows with the same filters as an individual flow
+//! statistics request, but the switch only reports the summed counters.
+
+use std::io::{Cursor, Error};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::openflow::ofp13::{FlowStatsRequestEvent, MatchFields, MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Request for aggregate flow statistics
+pub struct AggregateStatsRequestEvent {
+    /// Filters selecting the flows to sum up
+    pub filter: FlowStatsRequestEvent,
+}
+
+impl AggregateStatsRequestEvent {
+    /// Creates a new aggregate statistics request
+    ///
+    /// # Arguments
+    /// * `table_id` - ID of the table to read, or `TABLE_ALL`
+    /// * `match_fields` - Fields to match
+    pub fn new(table_id: u8, match_fields: MatchFields) -> Self {
+        Self {
+            filter: FlowStatsRequestEvent::new(table_id, match_fields),
+        }
+    }
+
+    /// Creates a request summing up every flow of every table
+    pub fn all() -> Self {
+        Self {
+            filter: FlowStatsRequestEvent::all(),
+        }
+    }
+}
+
+impl From<FlowStatsRequestEvent> for AggregateStatsRequestEvent {
+    fn from(filter: FlowStatsRequestEvent) -> Self {
+        Self { filter }
+    }
+}
+
+impl MessageMarshal for AggregateStatsRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::Aggregate.marshal_header(0, bytes);
+        self.filter.marshal_body(bytes);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        let mut bytes = Vec::new();
+        self.marshal(&mut bytes);
+        bytes.len()
+    }
+}
+
+/// Summed counters of the selected flows
+#[derive(Clone, Debug)]
+pub struct AggregateStats {
+    /// Number of packets in the flows
+    pub packet_count: u64,
+    /// Number of bytes in the flows
+    pub byte_count: u64,
+    /// Number of flows
+    pub flow_count: u32,
+}
+
+impl AggregateStats {
+    /// Size of the aggregate statistics reply body in bytes
+    pub const SIZE: usize = 24;
+
+    /// Parses an aggregate statistics reply body
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the body
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let packet_count = bytes.read_u64::<BigEndian>()?;
+        let byte_count = bytes.read_u64::<BigEndian>()?;
+        let flow_count = bytes.read_u32::<BigEndian>()?;
+        // padding
+        bytes.read_u32::<BigEndian>()?;
+        Ok(Self {
+            packet_count,
+            byte_count,
+            flow_count,
+        })
+    }
+}
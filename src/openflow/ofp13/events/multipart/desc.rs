@@ -0,0 +1,93 @@
+//! OpenFlow v1.3 Switch Description
+//!
+//! This module implements the `OFPMP_DESC` multipart request and the switch
+//! description carried in its reply: manufacturer, hardware, software, serial
+//! number and a human readable description of the datapath.
+
+use std::io::{Cursor, Error, Read};
+
+use crate::openflow::{
+    ofp13::{MessageMarshal, Msg},
+    port_name,
+};
+
+use super::MultipartType;
+
+/// Size of the manufacturer, hardware, software and datapath descriptions
+const DESC_STR_LEN: usize = 256;
+
+/// Size of the serial number
+const SERIAL_NUM_LEN: usize = 32;
+
+/// Request for the description of the switch
+pub struct DescRequestEvent {}
+
+impl DescRequestEvent {
+    /// Creates a new switch description request
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for DescRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for DescRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::Desc.marshal_header(0, bytes);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        8
+    }
+}
+
+/// Description of a switch
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SwitchDescription {
+    /// Manufacturer description
+    pub mfr_desc: String,
+    /// Hardware description
+    pub hw_desc: String,
+    /// Software description
+    pub sw_desc: String,
+    /// Serial number
+    pub serial_num: String,
+    /// Human readable description of the datapath
+    pub dp_desc: String,
+}
+
+impl SwitchDescription {
+    /// Size of a switch description in bytes
+    pub const SIZE: usize = 4 * DESC_STR_LEN + SERIAL_NUM_LEN;
+
+    /// Parses a switch description
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the description
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let mut field = |len: usize| -> Result<String, Error> {
+            let mut buf = vec![0u8; len];
+            bytes.read_exact(&mut buf)?;
+            Ok(port_name(&buf))
+        };
+        Ok(Self {
+            mfr_desc: field(DESC_STR_LEN)?,
+            hw_desc: field(DESC_STR_LEN)?,
+            sw_desc: field(DESC_STR_LEN)?,
+            serial_num: field(SERIAL_NUM_LEN)?,
+            dp_desc: field(DESC_STR_LEN)?,
+        })
+    }
+}
@@ -0,0 +1,221 @@
+//! OpenFlow v1.3 Individual Flow Statistics
+//!
+//! This module implements the `OFPMP_FLOW` multipart request and the flow entries
+//! carried in its reply. The request selects flows by table, output port, output
+//! group, cookie and match; the reply describes every selected flow entry.
+
+use std::io::{Cursor, Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp13::{
+    events::{
+        flow_mod::{flow_mod_handler::Timeout, instructions::Instrucion, FlowModFlags},
+        group_mod::OfpGroup,
+    },
+    ofp_port::OfpPort,
+    MatchFields, MessageMarshal, Msg,
+};
+
+use super::{MultipartType, TABLE_ALL};
+
+/// Request for individual flow statistics
+///
+/// Only flows matching every filter are reported. The defaults created by
+/// `FlowStatsRequestEvent::all` select every flow of every table.
+pub struct FlowStatsRequestEvent {
+    /// ID of the table to read, or `TABLE_ALL` for all tables
+    pub table_id: u8,
+    /// Require matching entries to output to this port (`OFPP_ANY` to disable)
+    pub out_port: u32,
+    /// Require matching entries to output to this group (`OFPG_ANY` to disable)
+    pub out_group: u32,
+    /// Require matching entries to contain this cookie value
+    pub cookie: u64,
+    /// Mask used to restrict the cookie bits that must match; 0 disables the filter
+    pub cookie_mask: u64,
+    /// Fields to match
+    pub match_fields: MatchFields,
+}
+
+impl FlowStatsRequestEvent {
+    /// Creates a new flow statistics request
+    ///
+    /// # Arguments
+    /// * `table_id` - ID of the table to read, or `TABLE_ALL`
+    /// * `match_fields` - Fields to match
+    ///
+    /// # Returns
+    /// * `FlowStatsRequestEvent` - Request without port, group or cookie filters
+    pub fn new(table_id: u8, match_fields: MatchFields) -> Self {
+        Self {
+            table_id,
+            out_port: OfpPort::Any as u32,
+            out_group: OfpGroup::Any as u32,
+            cookie: 0,
+            cookie_mask: 0,
+            match_fields,
+        }
+    }
+
+    /// Creates a request for every flow of every table
+    pub fn all() -> Self {
+        Self::new(TABLE_ALL, MatchFields::match_all())
+    }
+
+    /// Restricts the request to flows outputting to a port
+    ///
+    /// # Arguments
+    /// * `out_port` - Required output port
+    pub fn out_port(mut self, out_port: u32) -> Self {
+        self.out_port = out_port;
+        self
+    }
+
+    /// Restricts the request to flows outputting to a group
+    ///
+    /// # Arguments
+    /// * `out_group` - Required output group
+    pub fn out_group(mut self, out_group: u32) -> Self {
+        self.out_group = out_group;
+        self
+    }
+
+    /// Restricts the request to flows whose cookie matches under a mask
+    ///
+    /// # Arguments
+    /// * `cookie` - Required cookie value
+    /// * `cookie_mask` - Bits of the cookie that must match
+    pub fn cookie(mut self, cookie: u64, cookie_mask: u64) -> Self {
+        self.cookie = cookie;
+        self.cookie_mask = cookie_mask;
+        self
+    }
+}
+
+impl FlowStatsRequestEvent {
+    /// Marshals the request body, shared with aggregate statistics requests
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the body to
+    pub(crate) fn marshal_body(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u8(self.table_id);
+        // padding
+        let _ = bytes.write_u16::<BigEndian>(0);
+        let _ = bytes.write_u8(0);
+        let _ = bytes.write_u32::<BigEndian>(self.out_port);
+        let _ = bytes.write_u32::<BigEndian>(self.out_group);
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+        let _ = bytes.write_u64::<BigEndian>(self.cookie);
+        let _ = bytes.write_u64::<BigEndian>(self.cookie_mask);
+        let _ = self.match_fields.marshal(bytes);
+    }
+}
+
+impl MessageMarshal for FlowStatsRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::Flow.marshal_header(0, bytes);
+        self.marshal_body(bytes);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        let mut bytes = Vec::new();
+        self.marshal(&mut bytes);
+        bytes.len()
+    }
+}
+
+/// Statistics of a single flow entry
+#[derive(Clone, Debug)]
+pub struct FlowStats {
+    /// ID of the table the flow is in
+    pub table_id: u8,
+    /// Time the flow has been alive in seconds
+    pub duration_sec: u32,
+    /// Time the flow has been alive in nanoseconds beyond `duration_sec`
+    pub duration_nsec: u32,
+    /// Priority of the entry
+    pub priority: u16,
+    /// Idle timeout of the entry
+    pub idle_timeout: Timeout,
+    /// Hard timeout of the entry
+    pub hard_timeout: Timeout,
+    /// Flags the entry was installed with
+    pub flags: FlowModFlags,
+    /// Opaque controller-issued identifier
+    pub cookie: u64,
+    /// Number of packets in the flow
+    pub packet_count: u64,
+    /// Number of bytes in the flow
+    pub byte_count: u64,
+    /// Match of the entry
+    pub match_fields: MatchFields,
+    /// Instructions of the entry
+    pub instructions: Vec<Instrucion>,
+}
+
+impl FlowStats {
+    /// Size of the fixed part of a flow statistics entry, match excluded
+    pub const SIZE: usize = 48;
+
+    /// Parses a single flow statistics entry
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the entry
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let start = bytes.position();
+        let length = bytes.read_u16::<BigEndian>()? as u64;
+        if length < Self::SIZE as u64 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("flow stats entry too short: {}", length),
+            ));
+        }
+        let table_id = bytes.read_u8()?;
+        // padding
+        bytes.read_u8()?;
+        let duration_sec = bytes.read_u32::<BigEndian>()?;
+        let duration_nsec = bytes.read_u32::<BigEndian>()?;
+        let priority = bytes.read_u16::<BigEndian>()?;
+        let idle_timeout = Timeout::parse(bytes.read_u16::<BigEndian>()?);
+        let hard_timeout = Timeout::parse(bytes.read_u16::<BigEndian>()?);
+        let flags = FlowModFlags::parse(bytes.read_u16::<BigEndian>()?);
+        // padding
+        bytes.read_u32::<BigEndian>()?;
+        let cookie = bytes.read_u64::<BigEndian>()?;
+        let packet_count = bytes.read_u64::<BigEndian>()?;
+        let byte_count = bytes.read_u64::<BigEndian>()?;
+        let match_fields = MatchFields::parse(bytes)?;
+        let consumed = bytes.position() - start;
+        if consumed > length {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "flow stats match exceeds entry length",
+            ));
+        }
+        let instructions = Instrucion::parse_list(bytes, (length - consumed) as usize)?;
+        Ok(Self {
+            table_id,
+            duration_sec,
+            duration_nsec,
+            priority,
+            idle_timeout,
+            hard_timeout,
+            flags,
+            cookie,
+            packet_count,
+            byte_count,
+            match_fields,
+            instructions,
+        })
+    }
+}
@@ -0,0 +1,89 @@
+//! OpenFlow v1.3 Group Description
+//!
+//! This module implements the `OFPMP_GROUP_DESC` multipart request and the
+//! group descriptions carried in its reply: type, id and buckets of every group.
+
+use std::io::{Cursor, Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::openflow::ofp13::{
+    events::group_mod::{Bucket, GroupType},
+    MessageMarshal, Msg,
+};
+
+use super::MultipartType;
+
+/// Request for the description of every group
+pub struct GroupDescRequestEvent {}
+
+impl GroupDescRequestEvent {
+    /// Creates a new group description request
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for GroupDescRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for GroupDescRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::GroupDesc.marshal_header(0, bytes);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        8
+    }
+}
+
+/// Description of a single group
+#[derive(Clone, Debug)]
+pub struct GroupDesc {
+    /// Type of the group
+    pub group_type: GroupType,
+    /// ID of the group
+    pub group_id: u32,
+    /// Action buckets of the group
+    pub buckets: Vec<Bucket>,
+}
+
+impl GroupDesc {
+    /// Size of the fixed part of a group description, buckets excluded
+    pub const SIZE: usize = 8;
+
+    /// Parses a single group description
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the description
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let length = bytes.read_u16::<BigEndian>()? as usize;
+        if length < Self::SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("group description too short: {}", length),
+            ));
+        }
+        let group_type = GroupType::try_from(bytes.read_u8()?)?;
+        // padding
+        bytes.read_u8()?;
+        let group_id = bytes.read_u32::<BigEndian>()?;
+        let buckets = Bucket::parse_list(bytes, length - Self::SIZE)?;
+        Ok(Self {
+            group_type,
+            group_id,
+            buckets,
+        })
+    }
+}
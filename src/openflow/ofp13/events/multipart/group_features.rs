@@ -0,0 +1,136 @@
+//! OpenFlow v1.3 Group Features
+//!
+//! This module implements the `OFPMP_GROUP_FEATURES` multipart request and its
+//! reply, which describes the group types, capabilities and limits of the switch.
+
+use std::io::{Cursor, Error};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::openflow::ofp13::{events::group_mod::GroupType, MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Request for the group features of the switch
+pub struct GroupFeaturesRequestEvent {}
+
+impl GroupFeaturesRequestEvent {
+    /// Creates a new group features request
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for GroupFeaturesRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for GroupFeaturesRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::GroupFeatures.marshal_header(0, bytes);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        8
+    }
+}
+
+/// Group capabilities supported by the switch
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GroupCapabilities {
+    /// Support weight for select groups
+    pub select_weight: bool,
+    /// Support liveness for select groups
+    pub select_liveness: bool,
+    /// Support chaining groups
+    pub chaining: bool,
+    /// Check chaining for loops and delete
+    pub chaining_checks: bool,
+}
+
+impl From<u32> for GroupCapabilities {
+    fn from(value: u32) -> Self {
+        Self {
+            select_weight: value & 1 == 1,
+            select_liveness: value >> 1 & 1 == 1,
+            chaining: value >> 2 & 1 == 1,
+            chaining_checks: value >> 3 & 1 == 1,
+        }
+    }
+}
+
+impl From<GroupCapabilities> for u32 {
+    fn from(value: GroupCapabilities) -> Self {
+        (value.select_weight as u32)
+            | (value.select_liveness as u32) << 1
+            | (value.chaining as u32) << 2
+            | (value.chaining_checks as u32) << 3
+    }
+}
+
+/// Group features of the switch
+#[derive(Clone, Debug)]
+pub struct GroupFeatures {
+    /// Bitmap of supported group types, indexed by `GroupType`
+    pub types: u32,
+    /// Supported group capabilities
+    pub capabilities: GroupCapabilities,
+    /// Maximum number of groups for each group type
+    pub max_groups: [u32; 4],
+    /// Bitmaps of supported action types for each group type
+    pub actions: [u32; 4],
+}
+
+impl GroupFeatures {
+    /// Size of the group features in bytes
+    pub const SIZE: usize = 40;
+
+    /// Parses the group features
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the features
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let types = bytes.read_u32::<BigEndian>()?;
+        let capabilities = GroupCapabilities::from(bytes.read_u32::<BigEndian>()?);
+        let mut max_groups = [0; 4];
+        for max in max_groups.iter_mut() {
+            *max = bytes.read_u32::<BigEndian>()?;
+        }
+        let mut actions = [0; 4];
+        for action in actions.iter_mut() {
+            *action = bytes.read_u32::<BigEndian>()?;
+        }
+        Ok(Self {
+            types,
+            capabilities,
+            max_groups,
+            actions,
+        })
+    }
+
+    /// Returns true if the switch supports a group type
+    ///
+    /// # Arguments
+    /// * `group_type` - The group type to check
+    pub fn supports(&self, group_type: GroupType) -> bool {
+        self.types >> u8::from(group_type) & 1 == 1
+    }
+
+    /// Returns the maximum number of groups of a type
+    ///
+    /// # Arguments
+    /// * `group_type` - The group type to look up
+    pub fn max_groups(&self, group_type: GroupType) -> u32 {
+        self.max_groups[u8::from(group_type) as usize]
+    }
+}
@@ -0,0 +1,132 @@
+//! OpenFlow v1.3 Group Statistics
+//!
+//! This module implements the `OFPMP_GROUP` multipart request and the per-group
+//! counters carried in its reply, including the counters of every bucket.
+
+use std::io::{Cursor, Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp13::{events::group_mod::OfpGroup, MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Request for group statistics
+pub struct GroupStatsRequestEvent {
+    /// Group to read, or `OFPG_ALL` for all groups
+    pub group_id: u32,
+}
+
+impl GroupStatsRequestEvent {
+    /// Creates a new group statistics request
+    ///
+    /// # Arguments
+    /// * `group_id` - Group to read
+    pub fn new(group_id: u32) -> Self {
+        Self { group_id }
+    }
+
+    /// Creates a request for the statistics of every group
+    pub fn all() -> Self {
+        Self::new(OfpGroup::All as u32)
+    }
+}
+
+impl MessageMarshal for GroupStatsRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::Group.marshal_header(0, bytes);
+        let _ = bytes.write_u32::<BigEndian>(self.group_id);
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        16
+    }
+}
+
+/// Counters of a single bucket
+#[derive(Clone, Debug)]
+pub struct BucketCounter {
+    /// Number of packets processed by the bucket
+    pub packet_count: u64,
+    /// Number of bytes processed by the bucket
+    pub byte_count: u64,
+}
+
+impl BucketCounter {
+    /// Size of a bucket counter in bytes
+    pub const SIZE: usize = 16;
+}
+
+/// Counters of a single group
+#[derive(Clone, Debug)]
+pub struct GroupStats {
+    /// ID of the group
+    pub group_id: u32,
+    /// Number of flows or groups that forward to this group
+    pub ref_count: u32,
+    /// Number of packets processed by the group
+    pub packet_count: u64,
+    /// Number of bytes processed by the group
+    pub byte_count: u64,
+    /// Time the group has been alive in seconds
+    pub duration_sec: u32,
+    /// Time the group has been alive in nanoseconds beyond `duration_sec`
+    pub duration_nsec: u32,
+    /// Counters of every bucket, in bucket order
+    pub bucket_stats: Vec<BucketCounter>,
+}
+
+impl GroupStats {
+    /// Size of the fixed part of a group statistics entry, bucket counters excluded
+    pub const SIZE: usize = 40;
+
+    /// Parses a single group statistics entry
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the entry
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let length = bytes.read_u16::<BigEndian>()? as usize;
+        if length < Self::SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("group stats entry too short: {}", length),
+            ));
+        }
+        // padding
+        bytes.read_u16::<BigEndian>()?;
+        let group_id = bytes.read_u32::<BigEndian>()?;
+        let ref_count = bytes.read_u32::<BigEndian>()?;
+        // padding
+        bytes.read_u32::<BigEndian>()?;
+        let packet_count = bytes.read_u64::<BigEndian>()?;
+        let byte_count = bytes.read_u64::<BigEndian>()?;
+        let duration_sec = bytes.read_u32::<BigEndian>()?;
+        let duration_nsec = bytes.read_u32::<BigEndian>()?;
+        let mut bucket_stats = Vec::new();
+        for _ in 0..(length - Self::SIZE) / BucketCounter::SIZE {
+            bucket_stats.push(BucketCounter {
+                packet_count: bytes.read_u64::<BigEndian>()?,
+                byte_count: bytes.read_u64::<BigEndian>()?,
+            });
+        }
+        Ok(Self {
+            group_id,
+            ref_count,
+            packet_count,
+            byte_count,
+            duration_sec,
+            duration_nsec,
+            bucket_stats,
+        })
+    }
+}
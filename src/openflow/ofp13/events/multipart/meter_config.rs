@@ -0,0 +1,95 @@
+//! OpenFlow v1.3 Meter Configuration
+//!
+//! This module implements the `OFPMP_METER_CONFIG` multipart request and the
+//! meter configurations carried in its reply: flags, id and bands of every meter.
+
+use std::io::{Cursor, Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp13::{
+    events::meter_mod::{MeterBand, MeterFlags, OfpMeter},
+    MessageMarshal, Msg,
+};
+
+use super::MultipartType;
+
+/// Request for meter configurations
+pub struct MeterConfigRequestEvent {
+    /// Meter to read, or `OFPM_ALL` for all meters
+    pub meter_id: u32,
+}
+
+impl MeterConfigRequestEvent {
+    /// Creates a new meter configuration request
+    ///
+    /// # Arguments
+    /// * `meter_id` - Meter to read
+    pub fn new(meter_id: u32) -> Self {
+        Self { meter_id }
+    }
+
+    /// Creates a request for the configuration of every meter
+    pub fn all() -> Self {
+        Self::new(OfpMeter::All as u32)
+    }
+}
+
+impl MessageMarshal for MeterConfigRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::MeterConfig.marshal_header(0, bytes);
+        let _ = bytes.write_u32::<BigEndian>(self.meter_id);
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        16
+    }
+}
+
+/// Configuration of a single meter
+#[derive(Clone, Debug)]
+pub struct MeterConfig {
+    /// Meter configuration flags
+    pub flags: MeterFlags,
+    /// ID of the meter
+    pub meter_id: u32,
+    /// Bands of the meter
+    pub bands: Vec<MeterBand>,
+}
+
+impl MeterConfig {
+    /// Size of the fixed part of a meter configuration, bands excluded
+    pub const SIZE: usize = 8;
+
+    /// Parses a single meter configuration
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the configuration
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let length = bytes.read_u16::<BigEndian>()? as usize;
+        if length < Self::SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("meter configuration too short: {}", length),
+            ));
+        }
+        let flags = MeterFlags::from(bytes.read_u16::<BigEndian>()?);
+        let meter_id = bytes.read_u32::<BigEndian>()?;
+        let bands = MeterBand::parse_list(bytes, length - Self::SIZE)?;
+        Ok(Self {
+            flags,
+            meter_id,
+            bands,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+//! OpenFlow v1.3 Meter Features
+//!
+//! This module implements the `OFPMP_METER_FEATURES` multipart request and its
+//! reply, which describes the band types, capabilities and limits of the meters
+//! of the switch.
+
+use std::io::{Cursor, Error};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::openflow::ofp13::{events::meter_mod::MeterFlags, MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Request for the meter features of the switch
+pub struct MeterFeaturesRequestEvent {}
+
+impl MeterFeaturesRequestEvent {
+    /// Creates a new meter features request
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for MeterFeaturesRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for MeterFeaturesRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::MeterFeatures.marshal_header(0, bytes);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        8
+    }
+}
+
+/// Meter features of the switch
+#[derive(Clone, Debug)]
+pub struct MeterFeatures {
+    /// Maximum number of meters
+    pub max_meter: u32,
+    /// Bitmap of supported band types, indexed by band type code
+    pub band_types: u32,
+    /// Supported meter flags
+    pub capabilities: MeterFlags,
+    /// Maximum number of bands per meter
+    pub max_bands: u8,
+    /// Maximum color value
+    pub max_color: u8,
+}
+
+impl MeterFeatures {
+    /// Size of the meter features in bytes
+    pub const SIZE: usize = 16;
+
+    /// Parses the meter features
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the features
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let max_meter = bytes.read_u32::<BigEndian>()?;
+        let band_types = bytes.read_u32::<BigEndian>()?;
+        let capabilities = MeterFlags::from(bytes.read_u32::<BigEndian>()? as u16);
+        let max_bands = bytes.read_u8()?;
+        let max_color = bytes.read_u8()?;
+        // padding
+        bytes.read_u16::<BigEndian>()?;
+        Ok(Self {
+            max_meter,
+            band_types,
+            capabilities,
+            max_bands,
+            max_color,
+        })
+    }
+}
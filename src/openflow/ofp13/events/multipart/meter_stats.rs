@@ -0,0 +1,140 @@
+//! OpenFlow v1.3 Meter Statistics
+//!
+//! This module implements the `OFPMP_METER` multipart request and the per-meter
+//! counters carried in its reply, including the counters of every band.
+
+use std::io::{Cursor, Error, ErrorKind};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp13::{events::meter_mod::OfpMeter, MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Request for meter statistics
+pub struct MeterStatsRequestEvent {
+    /// Meter to read, or `OFPM_ALL` for all meters
+    pub meter_id: u32,
+}
+
+impl MeterStatsRequestEvent {
+    /// Creates a new meter statistics request
+    ///
+    /// # Arguments
+    /// * `meter_id` - Meter to read
+    pub fn new(meter_id: u32) -> Self {
+        Self { meter_id }
+    }
+
+    /// Creates a request for the statistics of every meter
+    pub fn all() -> Self {
+        Self::new(OfpMeter::All as u32)
+    }
+}
+
+impl MessageMarshal for MeterStatsRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::Meter.marshal_header(0, bytes);
+        let _ = bytes.write_u32::<BigEndian>(self.meter_id);
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        16
+    }
+}
+
+/// Counters of a single meter band
+#[derive(Clone, Debug)]
+pub struct MeterBandStats {
+    /// Number of packets in the band
+    pub packet_band_count: u64,
+    /// Number of bytes in the band
+    pub byte_band_count: u64,
+}
+
+impl MeterBandStats {
+    /// Size of band counters in bytes
+    pub const SIZE: usize = 16;
+}
+
+/// Counters of a single meter
+#[derive(Clone, Debug)]
+pub struct MeterStats {
+    /// ID of the meter
+    pub meter_id: u32,
+    /// Number of flows bound to the meter
+    pub flow_count: u32,
+    /// Number of packets in input
+    pub packet_in_count: u64,
+    /// Number of bytes in input
+    pub byte_in_count: u64,
+    /// Time the meter has been alive in seconds
+    pub duration_sec: u32,
+    /// Time the meter has been alive in nanoseconds beyond `duration_sec`
+    pub duration_nsec: u32,
+    /// Counters of every band, in band order
+    pub band_stats: Vec<MeterBandStats>,
+}
+
+impl MeterStats {
+    /// Size of the fixed part of a meter statistics entry, band counters excluded
+    pub const SIZE: usize = 40;
+
+    /// Parses a single meter statistics entry
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the entry
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let meter_id = bytes.read_u32::<BigEndian>()?;
+        let length = bytes.read_u16::<BigEndian>()? as usize;
+        if length < Self::SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("meter stats entry too short: {}", length),
+            ));
+        }
+        if !(length - Self::SIZE).is_multiple_of(MeterBandStats::SIZE) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "meter stats entry length {} ends inside band counters",
+                    length
+                ),
+            ));
+        }
+        // padding
+        bytes.read_u16::<BigEndian>()?;
+        bytes.read_u32::<BigEndian>()?;
+        let flow_count = bytes.read_u32::<BigEndian>()?;
+        let packet_in_count = bytes.read_u64::<BigEndian>()?;
+        let byte_in_count = bytes.read_u64::<BigEndian>()?;
+        let duration_sec = bytes.read_u32::<BigEndian>()?;
+        let duration_nsec = bytes.read_u32::<BigEndian>()?;
+        let mut band_stats = Vec::new();
+        for _ in 0..(length - Self::SIZE) / MeterBandStats::SIZE {
+            band_stats.push(MeterBandStats {
+                packet_band_count: bytes.read_u64::<BigEndian>()?,
+                byte_band_count: bytes.read_u64::<BigEndian>()?,
+            });
+        }
+        Ok(Self {
+            meter_id,
+            flow_count,
+            packet_in_count,
+            byte_in_count,
+            duration_sec,
+            duration_nsec,
+            band_stats,
+        })
+    }
+}
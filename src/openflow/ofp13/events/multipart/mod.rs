@@ -0,0 +1,176 @@
+//! OpenFlow v1.3 Multipart Messages
+//!
+//! This module implements the multipart request and reply messages of OpenFlow v1.3.
+//! Multipart messages carry statistics and descriptions that may not fit into a single
+//! OpenFlow message. Every multipart message starts with the same header:
+//!
+//! | Type (2 bytes) | Flags (2 bytes) | Padding (4 bytes) | Body (variable) |
+//! |----------------|-----------------|-------------------|-----------------|
+//!
+//! Replies larger than one message are split by the switch; every part except the last
+//! one carries the `OFPMPF_REPLY_MORE` flag. `MultipartAssembler` joins those parts again.
+
+use std::io::{Error, ErrorKind};
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+/// Multipart reply handling module
+pub mod multipart_reply;
+pub use multipart_reply::{MultipartAssembler, MultipartReplyBody, MultipartReplyEvent};
+
+/// Switch description module
+pub mod desc;
+pub use desc::{DescRequestEvent, SwitchDescription};
+
+/// Port description module
+pub mod port_desc;
+pub use port_desc::PortDescRequestEvent;
+
+/// Individual flow statistics module
+pub mod flow_stats;
+pub use flow_stats::{FlowStats, FlowStatsRequestEvent};
+
+/// Aggregate flow statistics module
+pub mod aggregate_stats;
+pub use aggregate_stats::{AggregateStats, AggregateStatsRequestEvent};
+
+/// Flow table statistics module
+pub mod table_stats;
+pub use table_stats::{TableStats, TableStatsRequestEvent};
+
+/// Port statistics module
+pub mod port_stats;
+pub use port_stats::{PortStats, PortStatsRequestEvent};
+
+/// Queue statistics module
+pub mod queue_stats;
+pub use queue_stats::{QueueStats, QueueStatsRequestEvent, QUEUE_ALL};
+
+/// Group statistics module
+pub mod group_stats;
+pub use group_stats::{BucketCounter, GroupStats, GroupStatsRequestEvent};
+
+/// Group description module
+pub mod group_desc;
+pub use group_desc::{GroupDesc, GroupDescRequestEvent};
+
+/// Group features module
+pub mod group_features;
+pub use group_features::{GroupCapabilities, GroupFeatures, GroupFeaturesRequestEvent};
+
+/// Meter statistics module
+pub mod meter_stats;
+pub use meter_stats::{MeterBandStats, MeterStats, MeterStatsRequestEvent};
+
+/// Meter configuration module
+pub mod meter_config;
+pub use meter_config::{MeterConfig, MeterConfigRequestEvent};
+
+/// Meter features module
+pub mod meter_features;
+pub use meter_features::{MeterFeatures, MeterFeaturesRequestEvent};
+
+/// Table features module
+pub mod table_features;
+pub use table_features::{
+    ActionId, InstructionId, OxmId, TableFeatureProp, TableFeatures, TableFeaturesRequestEvent,
+};
+
+/// Size of the multipart header in bytes
+pub const MULTIPART_HEADER_SIZE: usize = 8;
+
+/// More requests follow this one (`OFPMPF_REQ_MORE`)
+pub const MULTIPART_REQUEST_MORE: u16 = 1 << 0;
+
+/// More replies follow this one (`OFPMPF_REPLY_MORE`)
+pub const MULTIPART_REPLY_MORE: u16 = 1 << 0;
+
+/// Table id matching every table (`OFPTT_ALL`)
+pub const TABLE_ALL: u8 = 0xff;
+
+/// Types of multipart messages
+#[derive(Clone, Debug, PartialEq)]
+#[repr(u16)]
+pub enum MultipartType {
+    /// Description of the switch
+    Desc = 0,
+    /// Individual flow statistics
+    Flow = 1,
+    /// Aggregate flow statistics
+    Aggregate = 2,
+    /// Flow table statistics
+    Table = 3,
+    /// Port statistics
+    PortStats = 4,
+    /// Queue statistics for a port
+    Queue = 5,
+    /// Group counter statistics
+    Group = 6,
+    /// Group description
+    GroupDesc = 7,
+    /// Group features
+    GroupFeatures = 8,
+    /// Meter statistics
+    Meter = 9,
+    /// Meter configuration
+    MeterConfig = 10,
+    /// Meter features
+    MeterFeatures = 11,
+    /// Table features
+    TableFeatures = 12,
+    /// Port description
+    PortDesc = 13,
+    /// Experimenter extension
+    Experimenter = 0xffff,
+}
+
+impl MultipartType {
+    /// Marshals the multipart header into a byte buffer
+    ///
+    /// # Arguments
+    /// * `flags` - Multipart flags
+    /// * `bytes` - The buffer to write the header to
+    pub fn marshal_header(&self, flags: u16, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u16::<BigEndian>(self.clone().into());
+        let _ = bytes.write_u16::<BigEndian>(flags);
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+    }
+}
+
+impl TryFrom<u16> for MultipartType {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        let typ = match value {
+            0 => Self::Desc,
+            1 => Self::Flow,
+            2 => Self::Aggregate,
+            3 => Self::Table,
+            4 => Self::PortStats,
+            5 => Self::Queue,
+            6 => Self::Group,
+            7 => Self::GroupDesc,
+            8 => Self::GroupFeatures,
+            9 => Self::Meter,
+            10 => Self::MeterConfig,
+            11 => Self::MeterFeatures,
+            12 => Self::TableFeatures,
+            13 => Self::PortDesc,
+            0xffff => Self::Experimenter,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unknown multipart type {}", value),
+                ))
+            }
+        };
+        Ok(typ)
+    }
+}
+
+impl From<MultipartType> for u16 {
+    fn from(value: MultipartType) -> Self {
+        value as u16
+    }
+}
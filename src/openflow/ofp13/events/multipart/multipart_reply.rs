@@ -0,0 +1,286 @@
+//! OpenFlow v1.3 Multipart Reply
+//!
+//! This module implements parsing of multipart replies and the reassembly of
+//! replies that the switch split across several messages.
+
+use std::{
+    collections::HashMap,
+    io::{Cursor, Error, ErrorKind},
+    sync::{Arc, Mutex},
+};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::openflow::ofp13::Port;
+
+use super::{
+    AggregateStats, FlowStats, GroupDesc, GroupFeatures, GroupStats, MeterConfig, MeterFeatures,
+    MeterStats, MultipartType, PortStats, QueueStats, SwitchDescription, TableFeatures, TableStats,
+    MULTIPART_HEADER_SIZE, MULTIPART_REPLY_MORE,
+};
+
+/// Typed body of a multipart reply
+#[derive(Clone, Debug)]
+pub enum MultipartReplyBody {
+    /// Description of the switch
+    Desc(SwitchDescription),
+    /// Individual flow statistics
+    Flow(Vec<FlowStats>),
+    /// Aggregate flow statistics
+    Aggregate(AggregateStats),
+    /// Flow table statistics
+    Table(Vec<TableStats>),
+    /// Port statistics
+    PortStats(Vec<PortStats>),
+    /// Queue statistics
+    Queue(Vec<QueueStats>),
+    /// Description of every port
+    PortDesc(Vec<Port>),
+    /// Group statistics
+    Group(Vec<GroupStats>),
+    /// Description of every group
+    GroupDesc(Vec<GroupDesc>),
+    /// Group features of the switch
+    GroupFeatures(GroupFeatures),
+    /// Meter statistics
+    Meter(Vec<MeterStats>),
+    /// Configuration of every meter
+    MeterConfig(Vec<MeterConfig>),
+    /// Meter features of the switch
+    MeterFeatures(MeterFeatures),
+    /// Features of every flow table
+    TableFeatures(Vec<TableFeatures>),
+    /// Body of a multipart type that is not decoded yet
+    Raw(Vec<u8>),
+}
+
+/// Represents a multipart reply message
+#[derive(Clone, Debug)]
+pub struct MultipartReplyEvent {
+    /// Type of the reply
+    pub typ: MultipartType,
+    /// Multipart flags
+    pub flags: u16,
+    /// Decoded body
+    pub body: MultipartReplyBody,
+}
+
+impl MultipartReplyEvent {
+    /// Parses a multipart reply from a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The message body, multipart header included
+    ///
+    /// # Returns
+    /// Result containing either the parsed MultipartReplyEvent or an error
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let mut bytes = Cursor::new(bytes.to_vec());
+        let typ = MultipartType::try_from(bytes.read_u16::<BigEndian>()?)?;
+        let flags = bytes.read_u16::<BigEndian>()?;
+        // padding
+        bytes.read_u32::<BigEndian>()?;
+        let len = bytes.get_ref().len() as u64;
+
+        let body = match typ {
+            MultipartType::Desc => MultipartReplyBody::Desc(SwitchDescription::parse(&mut bytes)?),
+            MultipartType::Flow => {
+                let mut stats = Vec::new();
+                while bytes.position() < len {
+                    stats.push(FlowStats::parse(&mut bytes)?);
+                }
+                MultipartReplyBody::Flow(stats)
+            }
+            MultipartType::Aggregate => {
+                MultipartReplyBody::Aggregate(AggregateStats::parse(&mut bytes)?)
+            }
+            MultipartType::Table => MultipartReplyBody::Table(parse_entries(
+                &mut bytes,
+                TableStats::SIZE,
+                TableStats::parse,
+            )?),
+            MultipartType::PortStats => MultipartReplyBody::PortStats(parse_entries(
+                &mut bytes,
+                PortStats::SIZE,
+                PortStats::parse,
+            )?),
+            MultipartType::Queue => MultipartReplyBody::Queue(parse_entries(
+                &mut bytes,
+                QueueStats::SIZE,
+                QueueStats::parse,
+            )?),
+            MultipartType::PortDesc => {
+                MultipartReplyBody::PortDesc(parse_entries(&mut bytes, Port::SIZE, |bytes| {
+                    Port::parse(bytes)
+                })?)
+            }
+            MultipartType::Group => {
+                let mut stats = Vec::new();
+                while bytes.position() < len {
+                    stats.push(GroupStats::parse(&mut bytes)?);
+                }
+                MultipartReplyBody::Group(stats)
+            }
+            MultipartType::GroupDesc => {
+                let mut groups = Vec::new();
+                while bytes.position() < len {
+                    groups.push(GroupDesc::parse(&mut bytes)?);
+                }
+                MultipartReplyBody::GroupDesc(groups)
+            }
+            MultipartType::GroupFeatures => {
+                MultipartReplyBody::GroupFeatures(GroupFeatures::parse(&mut bytes)?)
+            }
+            MultipartType::Meter => {
+                let mut stats = Vec::new();
+                while bytes.position() < len {
+                    stats.push(MeterStats::parse(&mut bytes)?);
+                }
+                MultipartReplyBody::Meter(stats)
+            }
+            MultipartType::MeterConfig => {
+                let mut meters = Vec::new();
+                while bytes.position() < len {
+                    meters.push(MeterConfig::parse(&mut bytes)?);
+                }
+                MultipartReplyBody::MeterConfig(meters)
+            }
+            MultipartType::MeterFeatures => {
+                MultipartReplyBody::MeterFeatures(MeterFeatures::parse(&mut bytes)?)
+            }
+            MultipartType::TableFeatures => {
+                let mut tables = Vec::new();
+                while bytes.position() < len {
+                    tables.push(TableFeatures::parse(&mut bytes)?);
+                }
+                MultipartReplyBody::TableFeatures(tables)
+            }
+            _ => MultipartReplyBody::Raw(bytes.get_ref()[MULTIPART_HEADER_SIZE..].to_vec()),
+        };
+        Ok(Self { typ, flags, body })
+    }
+
+    /// Returns true if more parts of this reply follow
+    pub fn more(&self) -> bool {
+        self.flags & MULTIPART_REPLY_MORE != 0
+    }
+}
+
+/// Parses a reply body made of fixed size entries
+///
+/// # Arguments
+/// * `bytes` - Cursor positioned at the first entry
+/// * `size` - Size of a single entry in bytes
+/// * `parse` - Parser of a single entry
+fn parse_entries<T>(
+    bytes: &mut Cursor<Vec<u8>>,
+    size: usize,
+    parse: fn(&mut Cursor<Vec<u8>>) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    let len = bytes.get_ref().len() as u64;
+    let mut entries = Vec::new();
+    while bytes.position() + size as u64 <= len {
+        entries.push(parse(bytes)?);
+    }
+    Ok(entries)
+}
+
+/// Joins multipart replies that were split across several messages
+///
+/// Parts are collected by transaction id until the part without the
+/// `OFPMPF_REPLY_MORE` flag arrives. Cloning is cheap and every clone refers
+/// to the same set of partial replies.
+///
+/// A joined reply may not grow past a maximum size, so a switch that never sends
+/// the last part cannot make the controller buffer without bound.
+#[derive(Clone)]
+pub struct MultipartAssembler {
+    /// Partial replies keyed by transaction id, multipart header included
+    parts: Arc<Mutex<HashMap<u32, Vec<u8>>>>,
+    /// Largest joined reply accepted, in bytes
+    max_size: usize,
+}
+
+impl MultipartAssembler {
+    /// Largest joined reply accepted by default, in bytes
+    pub const DEFAULT_MAX_SIZE: usize = 64 * 1024 * 1024;
+
+    /// Creates a new, empty assembler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new, empty assembler accepting joined replies of up to `max_size` bytes
+    ///
+    /// # Arguments
+    /// * `max_size` - Largest joined reply accepted, multipart header included
+    pub fn with_max_size(max_size: usize) -> Self {
+        Self {
+            parts: Arc::new(Mutex::new(HashMap::new())),
+            max_size,
+        }
+    }
+
+    /// Adds a part of a multipart reply
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction id of the reply
+    /// * `payload` - The message body, multipart header included
+    ///
+    /// # Returns
+    /// The complete reply body once the last part arrived, `None` while parts are missing.
+    /// Fails with `ErrorKind::InvalidData` once the joined reply exceeds the maximum size;
+    /// the parts received so far are dropped.
+    pub fn push(&self, xid: u32, payload: Vec<u8>) -> Result<Option<Vec<u8>>, Error> {
+        if payload.len() < MULTIPART_HEADER_SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("multipart reply too short: {}", payload.len()),
+            ));
+        }
+        let flags = u16::from_be_bytes([payload[2], payload[3]]);
+        let mut parts = self
+            .parts
+            .lock()
+            .map_err(|_| Error::other("multipart assembler poisoned"))?;
+
+        let mut reply = match parts.remove(&xid) {
+            Some(mut reply) => {
+                reply.extend_from_slice(&payload[MULTIPART_HEADER_SIZE..]);
+                reply
+            }
+            None => payload,
+        };
+        if reply.len() > self.max_size {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("multipart reply {} exceeds {} bytes", xid, self.max_size),
+            ));
+        }
+        if flags & MULTIPART_REPLY_MORE != 0 {
+            parts.insert(xid, reply);
+            return Ok(None);
+        }
+        // the joined reply is complete, so clear the flag carried over from the first part
+        reply[2] = 0;
+        reply[3] = 0;
+        Ok(Some(reply))
+    }
+
+    /// Returns the number of replies still waiting for parts
+    pub fn pending(&self) -> usize {
+        self.parts.lock().map(|parts| parts.len()).unwrap_or(0)
+    }
+
+    /// Drops every partial reply
+    pub fn clear(&self) {
+        if let Ok(mut parts) = self.parts.lock() {
+            parts.clear();
+        }
+    }
+}
+
+impl Default for MultipartAssembler {
+    fn default() -> Self {
+        Self::with_max_size(Self::DEFAULT_MAX_SIZE)
+    }
+}
@@ -0,0 +1,43 @@
+//! OpenFlow v1.3 Port Description
+//!
+//! This module implements the `OFPMP_PORT_DESC` multipart request. OpenFlow v1.3
+//! removed the port list from the features reply, so this request is the way to
+//! learn the ports of a switch. Its reply is a list of `ofp_port` structures.
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Request for the description of every port of the switch
+pub struct PortDescRequestEvent {}
+
+impl PortDescRequestEvent {
+    /// Creates a new port description request
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for PortDescRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for PortDescRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::PortDesc.marshal_header(0, bytes);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        8
+    }
+}
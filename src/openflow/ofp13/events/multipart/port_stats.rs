@@ -0,0 +1,121 @@
+//! OpenFlow v1.3 Port Statistics
+//!
+//! This module implements the `OFPMP_PORT_STATS` multipart request and the
+//! per-port counters carried in its reply.
+
+use std::io::{Cursor, Error};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp13::{ofp_port::OfpPort, MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Request for port statistics
+pub struct PortStatsRequestEvent {
+    /// Port to read, or `OFPP_ANY` for all ports
+    pub port_no: u32,
+}
+
+impl PortStatsRequestEvent {
+    /// Creates a new port statistics request
+    ///
+    /// # Arguments
+    /// * `port_no` - Port to read
+    pub fn new(port_no: u32) -> Self {
+        Self { port_no }
+    }
+
+    /// Creates a request for the statistics of every port
+    pub fn all() -> Self {
+        Self::new(OfpPort::Any as u32)
+    }
+}
+
+impl MessageMarshal for PortStatsRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::PortStats.marshal_header(0, bytes);
+        let _ = bytes.write_u32::<BigEndian>(self.port_no);
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        16
+    }
+}
+
+/// Counters of a single port
+#[derive(Clone, Debug)]
+pub struct PortStats {
+    /// Port number
+    pub port_no: u32,
+    /// Number of received packets
+    pub rx_packets: u64,
+    /// Number of transmitted packets
+    pub tx_packets: u64,
+    /// Number of received bytes
+    pub rx_bytes: u64,
+    /// Number of transmitted bytes
+    pub tx_bytes: u64,
+    /// Number of packets dropped by RX
+    pub rx_dropped: u64,
+    /// Number of packets dropped by TX
+    pub tx_dropped: u64,
+    /// Number of receive errors
+    pub rx_errors: u64,
+    /// Number of transmit errors
+    pub tx_errors: u64,
+    /// Number of frame alignment errors
+    pub rx_frame_err: u64,
+    /// Number of packets with RX overrun
+    pub rx_over_err: u64,
+    /// Number of CRC errors
+    pub rx_crc_err: u64,
+    /// Number of collisions
+    pub collisions: u64,
+    /// Time the port has been alive in seconds
+    pub duration_sec: u32,
+    /// Time the port has been alive in nanoseconds beyond `duration_sec`
+    pub duration_nsec: u32,
+}
+
+impl PortStats {
+    /// Size of a port statistics entry in bytes
+    pub const SIZE: usize = 112;
+
+    /// Parses a single port statistics entry
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the entry
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let port_no = bytes.read_u32::<BigEndian>()?;
+        // padding
+        bytes.read_u32::<BigEndian>()?;
+        Ok(Self {
+            port_no,
+            rx_packets: bytes.read_u64::<BigEndian>()?,
+            tx_packets: bytes.read_u64::<BigEndian>()?,
+            rx_bytes: bytes.read_u64::<BigEndian>()?,
+            tx_bytes: bytes.read_u64::<BigEndian>()?,
+            rx_dropped: bytes.read_u64::<BigEndian>()?,
+            tx_dropped: bytes.read_u64::<BigEndian>()?,
+            rx_errors: bytes.read_u64::<BigEndian>()?,
+            tx_errors: bytes.read_u64::<BigEndian>()?,
+            rx_frame_err: bytes.read_u64::<BigEndian>()?,
+            rx_over_err: bytes.read_u64::<BigEndian>()?,
+            rx_crc_err: bytes.read_u64::<BigEndian>()?,
+            collisions: bytes.read_u64::<BigEndian>()?,
+            duration_sec: bytes.read_u32::<BigEndian>()?,
+            duration_nsec: bytes.read_u32::<BigEndian>()?,
+        })
+    }
+}
@@ -0,0 +1,99 @@
+//! OpenFlow v1.3 Queue Statistics
+//!
+//! This module implements the `OFPMP_QUEUE` multipart request and the
+//! per-queue counters carried in its reply.
+
+use std::io::{Cursor, Error};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::ofp13::{ofp_port::OfpPort, MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Queue id matching every queue of a port (`OFPQ_ALL`)
+pub const QUEUE_ALL: u32 = 0xffffffff;
+
+/// Request for queue statistics
+pub struct QueueStatsRequestEvent {
+    /// Port to read, or `OFPP_ANY` for all ports
+    pub port_no: u32,
+    /// Queue to read, or `QUEUE_ALL` for all queues
+    pub queue_id: u32,
+}
+
+impl QueueStatsRequestEvent {
+    /// Creates a new queue statistics request
+    ///
+    /// # Arguments
+    /// * `port_no` - Port to read
+    /// * `queue_id` - Queue to read
+    pub fn new(port_no: u32, queue_id: u32) -> Self {
+        Self { port_no, queue_id }
+    }
+
+    /// Creates a request for every queue of every port
+    pub fn all() -> Self {
+        Self::new(OfpPort::Any as u32, QUEUE_ALL)
+    }
+}
+
+impl MessageMarshal for QueueStatsRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::Queue.marshal_header(0, bytes);
+        let _ = bytes.write_u32::<BigEndian>(self.port_no);
+        let _ = bytes.write_u32::<BigEndian>(self.queue_id);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        16
+    }
+}
+
+/// Counters of a single queue
+#[derive(Clone, Debug)]
+pub struct QueueStats {
+    /// Port the queue is attached to
+    pub port_no: u32,
+    /// ID of the queue
+    pub queue_id: u32,
+    /// Number of transmitted bytes
+    pub tx_bytes: u64,
+    /// Number of transmitted packets
+    pub tx_packets: u64,
+    /// Number of packets dropped due to overrun
+    pub tx_errors: u64,
+    /// Time the queue has been alive in seconds
+    pub duration_sec: u32,
+    /// Time the queue has been alive in nanoseconds beyond `duration_sec`
+    pub duration_nsec: u32,
+}
+
+impl QueueStats {
+    /// Size of a queue statistics entry in bytes
+    pub const SIZE: usize = 40;
+
+    /// Parses a single queue statistics entry
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the entry
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        Ok(Self {
+            port_no: bytes.read_u32::<BigEndian>()?,
+            queue_id: bytes.read_u32::<BigEndian>()?,
+            tx_bytes: bytes.read_u64::<BigEndian>()?,
+            tx_packets: bytes.read_u64::<BigEndian>()?,
+            tx_errors: bytes.read_u64::<BigEndian>()?,
+            duration_sec: bytes.read_u32::<BigEndian>()?,
+            duration_nsec: bytes.read_u32::<BigEndian>()?,
+        })
+    }
+}
@@ -0,0 +1,682 @@
+//! OpenFlow v1.3 Table Features
+//!
+//! This module implements the `OFPMP_TABLE_FEATURES` multipart request and reply.
+//! An empty request queries the capabilities of every flow table; a request carrying
+//! table features configures the pipeline. Each table is described by a fixed part
+//! followed by a list of properties:
+//!
+//! | Length (2) | Table ID (1) | Pad (5) | Name (32) | Metadata Match (8) | Metadata Write (8) |
+//! |------------|--------------|---------|-----------|--------------------|--------------------|
+//! | Config (4) | Max Entries (4) | Properties (variable) |
+//!
+//! Every property is padded to a multiple of 8 bytes. The table-miss variants of a
+//! property may be omitted by the switch, in which case the regular property applies
+//! to the table-miss flow entry too.
+
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::openflow::{
+    ofp13::{
+        events::{
+            actions::ActionType,
+            flow_mod::{instructions::InstructType, OxmMatchFields},
+        },
+        MessageMarshal, Msg,
+    },
+    port_name,
+};
+
+use super::MultipartType;
+
+/// Maximum length of a table name, terminating null byte included
+const MAX_TABLE_NAME_LEN: usize = 32;
+
+/// OXM class of the OpenFlow basic match fields
+const OXM_CLASS_OPENFLOW_BASIC: u16 = 0x8000;
+
+/// OXM class of experimenter match fields
+const OXM_CLASS_EXPERIMENTER: u16 = 0xffff;
+
+/// Experimenter type shared by instructions and actions
+const EXPERIMENTER_TYPE: u16 = 0xffff;
+
+/// Request for the features of every flow table, or to configure them
+pub struct TableFeaturesRequestEvent {
+    /// Tables to configure, empty to query the current features
+    pub tables: Vec<TableFeatures>,
+}
+
+impl TableFeaturesRequestEvent {
+    /// Creates a request querying the features of every table
+    pub fn new() -> Self {
+        Self { tables: Vec::new() }
+    }
+
+    /// Creates a request configuring the pipeline of the switch
+    ///
+    /// # Arguments
+    /// * `tables` - Desired features of every table of the pipeline
+    pub fn set(tables: Vec<TableFeatures>) -> Self {
+        Self { tables }
+    }
+}
+
+impl Default for TableFeaturesRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for TableFeaturesRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::TableFeatures.marshal_header(0, bytes);
+        for table in &self.tables {
+            table.marshal(bytes);
+        }
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        8 + self.tables.iter().map(|t| t.size_of()).sum::<usize>()
+    }
+}
+
+/// Instruction supported by a table
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstructionId {
+    /// Type of the instruction (`OFPIT_*`)
+    pub instruct_type: u16,
+    /// Experimenter ID of an experimenter instruction
+    pub experimenter: Option<u32>,
+}
+
+impl InstructionId {
+    /// Returns the instruction type, `None` if it is unknown
+    pub fn kind(&self) -> Option<InstructType> {
+        InstructType::try_from(self.instruct_type).ok()
+    }
+
+    fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let (instruct_type, experimenter) = parse_type_id(bytes)?;
+        Ok(Self {
+            instruct_type,
+            experimenter,
+        })
+    }
+
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        marshal_type_id(self.instruct_type, self.experimenter, bytes);
+    }
+}
+
+impl From<InstructType> for InstructionId {
+    fn from(value: InstructType) -> Self {
+        Self {
+            instruct_type: value.into(),
+            experimenter: None,
+        }
+    }
+}
+
+/// Action supported by a table
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionId {
+    /// Type of the action (`OFPAT_*`)
+    pub action_type: u16,
+    /// Experimenter ID of an experimenter action
+    pub experimenter: Option<u32>,
+}
+
+impl ActionId {
+    /// Returns the action type, `None` if it is unknown
+    pub fn kind(&self) -> Option<ActionType> {
+        ActionType::try_from(self.action_type).ok()
+    }
+
+    fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let (action_type, experimenter) = parse_type_id(bytes)?;
+        Ok(Self {
+            action_type,
+            experimenter,
+        })
+    }
+
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        marshal_type_id(self.action_type, self.experimenter, bytes);
+    }
+}
+
+impl From<ActionType> for ActionId {
+    fn from(value: ActionType) -> Self {
+        Self {
+            action_type: value.into(),
+            experimenter: None,
+        }
+    }
+}
+
+/// Match field supported by a table, as an OXM header without payload
+#[derive(Clone, Debug, PartialEq)]
+pub struct OxmId {
+    /// OXM class of the field
+    pub class: u16,
+    /// Field within the class
+    pub field: u8,
+    /// Whether the field may be masked
+    pub hasmask: bool,
+    /// Length of the field payload
+    pub length: u8,
+    /// Experimenter ID of an experimenter field
+    pub experimenter: Option<u32>,
+}
+
+impl OxmId {
+    /// Returns the OpenFlow basic match field, `None` for other classes or unknown fields
+    pub fn field(&self) -> Option<OxmMatchFields> {
+        if self.class != OXM_CLASS_OPENFLOW_BASIC {
+            return None;
+        }
+        match OxmMatchFields::from(self.field) {
+            OxmMatchFields::Unparse => None,
+            field => Some(field),
+        }
+    }
+
+    fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let header = bytes.read_u32::<BigEndian>()?;
+        let class = (header >> 16) as u16;
+        let experimenter = if class == OXM_CLASS_EXPERIMENTER {
+            Some(bytes.read_u32::<BigEndian>()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            class,
+            field: ((header >> 9) & 0x7f) as u8,
+            hasmask: header & (1 << 8) != 0,
+            length: header as u8,
+            experimenter,
+        })
+    }
+
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let header = (self.class as u32) << 16
+            | ((self.field & 0x7f) as u32) << 9
+            | (self.hasmask as u32) << 8
+            | self.length as u32;
+        let _ = bytes.write_u32::<BigEndian>(header);
+        if let Some(experimenter) = self.experimenter {
+            let _ = bytes.write_u32::<BigEndian>(experimenter);
+        }
+    }
+}
+
+/// Property of a flow table
+///
+/// Properties with a `miss` flag describe the table-miss flow entry when it is set
+/// and every other flow entry otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TableFeatureProp {
+    /// Instructions supported by the table
+    Instructions {
+        /// Describes the table-miss flow entry
+        miss: bool,
+        /// Supported instructions
+        ids: Vec<InstructionId>,
+    },
+    /// Tables reachable with a goto-table instruction
+    NextTables {
+        /// Describes the table-miss flow entry
+        miss: bool,
+        /// IDs of the reachable tables
+        table_ids: Vec<u8>,
+    },
+    /// Actions supported by the write-actions instruction
+    WriteActions {
+        /// Describes the table-miss flow entry
+        miss: bool,
+        /// Supported actions
+        ids: Vec<ActionId>,
+    },
+    /// Actions supported by the apply-actions instruction
+    ApplyActions {
+        /// Describes the table-miss flow entry
+        miss: bool,
+        /// Supported actions
+        ids: Vec<ActionId>,
+    },
+    /// Fields the table can match on
+    Match(Vec<OxmId>),
+    /// Fields the table can wildcard
+    Wildcards(Vec<OxmId>),
+    /// Fields supported by set-field actions of the write-actions instruction
+    WriteSetfield {
+        /// Describes the table-miss flow entry
+        miss: bool,
+        /// Supported fields
+        ids: Vec<OxmId>,
+    },
+    /// Fields supported by set-field actions of the apply-actions instruction
+    ApplySetfield {
+        /// Describes the table-miss flow entry
+        miss: bool,
+        /// Supported fields
+        ids: Vec<OxmId>,
+    },
+    /// Experimenter defined property
+    Experimenter {
+        /// Describes the table-miss flow entry
+        miss: bool,
+        /// Experimenter ID
+        experimenter: u32,
+        /// Experimenter defined type
+        exp_type: u32,
+        /// Experimenter defined data
+        data: Vec<u8>,
+    },
+}
+
+impl TableFeatureProp {
+    /// Parses a single property including its padding
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the property
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let prop_type = bytes.read_u16::<BigEndian>()?;
+        let length = bytes.read_u16::<BigEndian>()? as usize;
+        if length < 4 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("table feature property too short: {}", length),
+            ));
+        }
+        let mut body = vec![0; length - 4];
+        bytes.read_exact(&mut body)?;
+        let mut padding = vec![0; padding_of(length)];
+        bytes.read_exact(&mut padding)?;
+
+        let miss = prop_type & 1 == 1;
+        let mut body = Cursor::new(body);
+        let prop = match prop_type {
+            0 | 1 => Self::Instructions {
+                miss,
+                ids: parse_ids(&mut body, InstructionId::parse)?,
+            },
+            2 | 3 => Self::NextTables {
+                miss,
+                table_ids: body.into_inner(),
+            },
+            4 | 5 => Self::WriteActions {
+                miss,
+                ids: parse_ids(&mut body, ActionId::parse)?,
+            },
+            6 | 7 => Self::ApplyActions {
+                miss,
+                ids: parse_ids(&mut body, ActionId::parse)?,
+            },
+            8 => Self::Match(parse_ids(&mut body, OxmId::parse)?),
+            10 => Self::Wildcards(parse_ids(&mut body, OxmId::parse)?),
+            12 | 13 => Self::WriteSetfield {
+                miss,
+                ids: parse_ids(&mut body, OxmId::parse)?,
+            },
+            14 | 15 => Self::ApplySetfield {
+                miss,
+                ids: parse_ids(&mut body, OxmId::parse)?,
+            },
+            0xfffe | 0xffff => {
+                let experimenter = body.read_u32::<BigEndian>()?;
+                let exp_type = body.read_u32::<BigEndian>()?;
+                let mut data = Vec::new();
+                body.read_to_end(&mut data)?;
+                Self::Experimenter {
+                    miss,
+                    experimenter,
+                    exp_type,
+                    data,
+                }
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unknown table feature property {}", prop_type),
+                ))
+            }
+        };
+        Ok(prop)
+    }
+
+    /// Marshals the property including its padding
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the property to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) {
+        let mut body = Vec::new();
+        let prop_type = match self {
+            Self::Instructions { miss, ids } => {
+                ids.iter().for_each(|id| id.marshal(&mut body));
+                *miss as u16
+            }
+            Self::NextTables { miss, table_ids } => {
+                body.extend_from_slice(table_ids);
+                2 | *miss as u16
+            }
+            Self::WriteActions { miss, ids } => {
+                ids.iter().for_each(|id| id.marshal(&mut body));
+                4 | *miss as u16
+            }
+            Self::ApplyActions { miss, ids } => {
+                ids.iter().for_each(|id| id.marshal(&mut body));
+                6 | *miss as u16
+            }
+            Self::Match(ids) => {
+                ids.iter().for_each(|id| id.marshal(&mut body));
+                8
+            }
+            Self::Wildcards(ids) => {
+                ids.iter().for_each(|id| id.marshal(&mut body));
+                10
+            }
+            Self::WriteSetfield { miss, ids } => {
+                ids.iter().for_each(|id| id.marshal(&mut body));
+                12 | *miss as u16
+            }
+            Self::ApplySetfield { miss, ids } => {
+                ids.iter().for_each(|id| id.marshal(&mut body));
+                14 | *miss as u16
+            }
+            Self::Experimenter {
+                miss,
+                experimenter,
+                exp_type,
+                data,
+            } => {
+                let _ = body.write_u32::<BigEndian>(*experimenter);
+                let _ = body.write_u32::<BigEndian>(*exp_type);
+                body.extend_from_slice(data);
+                0xfffe | *miss as u16
+            }
+        };
+        let length = 4 + body.len();
+        let _ = bytes.write_u16::<BigEndian>(prop_type);
+        let _ = bytes.write_u16::<BigEndian>(length as u16);
+        bytes.extend_from_slice(&body);
+        bytes.extend_from_slice(&vec![0; padding_of(length)]);
+    }
+
+    /// Returns the size of the property in bytes, padding included
+    pub fn size_of(&self) -> usize {
+        let mut bytes = Vec::new();
+        self.marshal(&mut bytes);
+        bytes.len()
+    }
+}
+
+/// Features of a single flow table
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableFeatures {
+    /// ID of the table
+    pub table_id: u8,
+    /// Name of the table
+    pub name: String,
+    /// Bits of metadata the table can match
+    pub metadata_match: u64,
+    /// Bits of metadata the table can write
+    pub metadata_write: u64,
+    /// Configuration flags of the table (`OFPTC_*`)
+    pub config: u32,
+    /// Maximum number of flow entries supported
+    pub max_entries: u32,
+    /// Properties of the table
+    pub properties: Vec<TableFeatureProp>,
+}
+
+impl TableFeatures {
+    /// Size of the fixed part of the table features, properties excluded
+    pub const SIZE: usize = 64;
+
+    /// Parses the features of a single table
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the table features
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let start = bytes.position();
+        let length = bytes.read_u16::<BigEndian>()? as usize;
+        if length < Self::SIZE {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("table features too short: {}", length),
+            ));
+        }
+        let table_id = bytes.read_u8()?;
+        // padding
+        bytes.set_position(bytes.position() + 5);
+        let mut name = [0; MAX_TABLE_NAME_LEN];
+        bytes.read_exact(&mut name)?;
+        let metadata_match = bytes.read_u64::<BigEndian>()?;
+        let metadata_write = bytes.read_u64::<BigEndian>()?;
+        let config = bytes.read_u32::<BigEndian>()?;
+        let max_entries = bytes.read_u32::<BigEndian>()?;
+
+        let end = start + length as u64;
+        let mut properties = Vec::new();
+        while bytes.position() < end {
+            properties.push(TableFeatureProp::parse(bytes)?);
+        }
+        Ok(Self {
+            table_id,
+            name: port_name(&name),
+            metadata_match,
+            metadata_write,
+            config,
+            max_entries,
+            properties,
+        })
+    }
+
+    /// Marshals the table features into their wire format
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the table features to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u16::<BigEndian>(self.size_of() as u16);
+        let _ = bytes.write_u8(self.table_id);
+        // padding
+        bytes.extend_from_slice(&[0; 5]);
+        let mut name = [0; MAX_TABLE_NAME_LEN];
+        let len = self.name.len().min(MAX_TABLE_NAME_LEN - 1);
+        name[..len].copy_from_slice(&self.name.as_bytes()[..len]);
+        bytes.extend_from_slice(&name);
+        let _ = bytes.write_u64::<BigEndian>(self.metadata_match);
+        let _ = bytes.write_u64::<BigEndian>(self.metadata_write);
+        let _ = bytes.write_u32::<BigEndian>(self.config);
+        let _ = bytes.write_u32::<BigEndian>(self.max_entries);
+        for prop in &self.properties {
+            prop.marshal(bytes);
+        }
+    }
+
+    /// Returns the size of the table features in bytes, properties included
+    pub fn size_of(&self) -> usize {
+        Self::SIZE + self.properties.iter().map(|p| p.size_of()).sum::<usize>()
+    }
+
+    /// Returns the supported instructions
+    ///
+    /// # Arguments
+    /// * `miss` - Whether to return the instructions of the table-miss flow entry
+    pub fn instructions(&self, miss: bool) -> &[InstructionId] {
+        self.find(miss, |prop| match prop {
+            TableFeatureProp::Instructions { miss, ids } => Some((*miss, ids.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Returns the tables reachable with a goto-table instruction
+    ///
+    /// # Arguments
+    /// * `miss` - Whether to return the tables of the table-miss flow entry
+    pub fn next_tables(&self, miss: bool) -> &[u8] {
+        self.find(miss, |prop| match prop {
+            TableFeatureProp::NextTables { miss, table_ids } => Some((*miss, table_ids.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Returns the actions supported by the write-actions instruction
+    ///
+    /// # Arguments
+    /// * `miss` - Whether to return the actions of the table-miss flow entry
+    pub fn write_actions(&self, miss: bool) -> &[ActionId] {
+        self.find(miss, |prop| match prop {
+            TableFeatureProp::WriteActions { miss, ids } => Some((*miss, ids.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Returns the actions supported by the apply-actions instruction
+    ///
+    /// # Arguments
+    /// * `miss` - Whether to return the actions of the table-miss flow entry
+    pub fn apply_actions(&self, miss: bool) -> &[ActionId] {
+        self.find(miss, |prop| match prop {
+            TableFeatureProp::ApplyActions { miss, ids } => Some((*miss, ids.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Returns the fields the table can match on
+    pub fn matches(&self) -> &[OxmId] {
+        self.find(false, |prop| match prop {
+            TableFeatureProp::Match(ids) => Some((false, ids.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Returns the fields the table can wildcard
+    pub fn wildcards(&self) -> &[OxmId] {
+        self.find(false, |prop| match prop {
+            TableFeatureProp::Wildcards(ids) => Some((false, ids.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Returns the fields supported by set-field in the write-actions instruction
+    ///
+    /// # Arguments
+    /// * `miss` - Whether to return the fields of the table-miss flow entry
+    pub fn write_setfields(&self, miss: bool) -> &[OxmId] {
+        self.find(miss, |prop| match prop {
+            TableFeatureProp::WriteSetfield { miss, ids } => Some((*miss, ids.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Returns the fields supported by set-field in the apply-actions instruction
+    ///
+    /// # Arguments
+    /// * `miss` - Whether to return the fields of the table-miss flow entry
+    pub fn apply_setfields(&self, miss: bool) -> &[OxmId] {
+        self.find(miss, |prop| match prop {
+            TableFeatureProp::ApplySetfield { miss, ids } => Some((*miss, ids.as_slice())),
+            _ => None,
+        })
+    }
+
+    /// Returns true if flow entries of the table support the instruction
+    ///
+    /// # Arguments
+    /// * `instruct_type` - Type of the instruction
+    pub fn supports_instruction(&self, instruct_type: InstructType) -> bool {
+        let instruct_type: u16 = instruct_type.into();
+        self.instructions(false)
+            .iter()
+            .any(|id| id.instruct_type == instruct_type)
+    }
+
+    /// Returns true if the table can match on the field
+    ///
+    /// # Arguments
+    /// * `field` - OpenFlow basic match field
+    pub fn supports_match(&self, field: OxmMatchFields) -> bool {
+        self.matches()
+            .iter()
+            .any(|id| id.field().as_ref() == Some(&field))
+    }
+
+    /// Finds a property, falling back from the table-miss to the regular property
+    fn find<'a, T>(
+        &'a self,
+        miss: bool,
+        select: impl Fn(&'a TableFeatureProp) -> Option<(bool, &'a [T])>,
+    ) -> &'a [T] {
+        let mut regular: &[T] = &[];
+        for (prop_miss, ids) in self.properties.iter().filter_map(select) {
+            if prop_miss == miss {
+                return ids;
+            }
+            if !prop_miss {
+                regular = ids;
+            }
+        }
+        regular
+    }
+}
+
+/// Returns the number of padding bytes following a property of the given length
+fn padding_of(length: usize) -> usize {
+    (8 - length % 8) % 8
+}
+
+/// Parses an instruction or action id: type, length and an optional experimenter
+fn parse_type_id(bytes: &mut Cursor<Vec<u8>>) -> Result<(u16, Option<u32>), Error> {
+    let id_type = bytes.read_u16::<BigEndian>()?;
+    let length = bytes.read_u16::<BigEndian>()?;
+    let experimenter = if id_type == EXPERIMENTER_TYPE && length >= 8 {
+        Some(bytes.read_u32::<BigEndian>()?)
+    } else {
+        None
+    };
+    // skip experimenter data following the id
+    let read = if experimenter.is_some() { 8 } else { 4 };
+    if length > read {
+        bytes.set_position(bytes.position() + (length - read) as u64);
+    }
+    Ok((id_type, experimenter))
+}
+
+/// Marshals an instruction or action id
+fn marshal_type_id(id_type: u16, experimenter: Option<u32>, bytes: &mut Vec<u8>) {
+    let _ = bytes.write_u16::<BigEndian>(id_type);
+    match experimenter {
+        Some(experimenter) => {
+            let _ = bytes.write_u16::<BigEndian>(8);
+            let _ = bytes.write_u32::<BigEndian>(experimenter);
+        }
+        None => {
+            let _ = bytes.write_u16::<BigEndian>(4);
+        }
+    }
+}
+
+/// Parses every id of a property body
+fn parse_ids<T>(
+    body: &mut Cursor<Vec<u8>>,
+    parse: fn(&mut Cursor<Vec<u8>>) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    let len = body.get_ref().len() as u64;
+    let mut ids = Vec::new();
+    while body.position() < len {
+        ids.push(parse(body)?);
+    }
+    Ok(ids)
+}
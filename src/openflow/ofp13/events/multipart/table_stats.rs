@@ -0,0 +1,80 @@
+//! OpenFlow v1.3 Table Statistics
+//!
+//! This module implements the `OFPMP_TABLE` multipart request and the
+//! per-table counters carried in its reply.
+
+use std::io::{Cursor, Error};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+use super::MultipartType;
+
+/// Request for the statistics of every flow table
+pub struct TableStatsRequestEvent {}
+
+impl TableStatsRequestEvent {
+    /// Creates a new table statistics request
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for TableStatsRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for TableStatsRequestEvent {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        MultipartType::Table.marshal_header(0, bytes);
+    }
+
+    fn msg_code(&self) -> Msg {
+        Msg::MultipartRequest
+    }
+
+    fn msg_usize(&self) -> usize {
+        Msg::MultipartRequest as usize
+    }
+
+    fn size_of(&self) -> usize {
+        8
+    }
+}
+
+/// Counters of a single flow table
+#[derive(Clone, Debug)]
+pub struct TableStats {
+    /// ID of the table
+    pub table_id: u8,
+    /// Number of active entries
+    pub active_count: u32,
+    /// Number of packets looked up in the table
+    pub lookup_count: u64,
+    /// Number of packets that hit an entry
+    pub matched_count: u64,
+}
+
+impl TableStats {
+    /// Size of a table statistics entry in bytes
+    pub const SIZE: usize = 24;
+
+    /// Parses a single table statistics entry
+    ///
+    /// # Arguments
+    /// * `bytes` - Cursor positioned at the start of the entry
+    pub fn parse(bytes: &mut Cursor<Vec<u8>>) -> Result<Self, Error> {
+        let table_id = bytes.read_u8()?;
+        // padding
+        bytes.read_u24::<BigEndian>()?;
+        Ok(Self {
+            table_id,
+            active_count: bytes.read_u32::<BigEndian>()?,
+            lookup_count: bytes.read_u64::<BigEndian>()?,
+            matched_count: bytes.read_u64::<BigEndian>()?,
+        })
+    }
+}
@@ -0,0 +1,139 @@
+//! OpenFlow v1.3 Port Modification Message Implementation
+//!
+//! This module implements the Port-Mod message type used in OpenFlow v1.3 protocol.
+//! Port-Mod messages change the configuration of a switch port, e.g. to bring it
+//! administratively down or to stop packet-in messages for it. Only the configuration
+//! bits selected by the mask are changed.
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg, Port, PortConfig, PortFeatures};
+use crate::utils::MacAddr;
+
+/// Port modification event structure
+pub struct PortModEvent {
+    /// Number of the port to modify
+    pub port_no: u32,
+    /// Hardware address of the port, used by the switch as a sanity check
+    pub hw_addr: MacAddr,
+    /// New values of the configuration flags
+    pub config: PortConfig,
+    /// Configuration flags to change
+    pub mask: PortConfig,
+    /// Features to advertise, unchanged when `None`
+    pub advertise: Option<PortFeatures>,
+}
+
+impl PortModEvent {
+    /// Creates a port modification event that changes nothing yet
+    ///
+    /// # Arguments
+    /// * `port_no` - Number of the port to modify
+    /// * `hw_addr` - Hardware address of the port
+    ///
+    /// # Returns
+    /// * `PortModEvent` - The new port modification event
+    pub fn new(port_no: u32, hw_addr: MacAddr) -> Self {
+        Self {
+            port_no,
+            hw_addr,
+            config: PortConfig::default(),
+            mask: PortConfig::default(),
+            advertise: None,
+        }
+    }
+
+    /// Creates a port modification event for a known port
+    ///
+    /// # Arguments
+    /// * `port` - Description of the port, e.g. from the registry
+    pub fn for_port(port: &Port) -> Self {
+        Self::new(port.port_no, port.hw_addr)
+    }
+
+    /// Sets the configuration flags selected by the mask
+    ///
+    /// # Arguments
+    /// * `config` - New values of the configuration flags
+    /// * `mask` - Configuration flags to change
+    pub fn config(mut self, config: PortConfig, mask: PortConfig) -> Self {
+        self.config = config;
+        self.mask = mask;
+        self
+    }
+
+    /// Brings the port administratively down or up
+    pub fn port_down(mut self, down: bool) -> Self {
+        self.config.port_down = down;
+        self.mask.port_down = true;
+        self
+    }
+
+    /// Drops or accepts all packets received by the port
+    pub fn no_recv(mut self, drop: bool) -> Self {
+        self.config.no_recv = drop;
+        self.mask.no_recv = true;
+        self
+    }
+
+    /// Drops or accepts packets forwarded to the port
+    pub fn no_fwd(mut self, drop: bool) -> Self {
+        self.config.no_fwd = drop;
+        self.mask.no_fwd = true;
+        self
+    }
+
+    /// Disables or enables packet-in messages for the port
+    pub fn no_packet_in(mut self, disable: bool) -> Self {
+        self.config.no_packet_in = disable;
+        self.mask.no_packet_in = true;
+        self
+    }
+
+    /// Sets the features advertised by the port
+    ///
+    /// # Arguments
+    /// * `features` - Features to advertise
+    pub fn advertise(mut self, features: PortFeatures) -> Self {
+        self.advertise = Some(features);
+        self
+    }
+}
+
+impl MessageMarshal for PortModEvent {
+    /// Returns the message type as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::PortMod as usize
+    }
+
+    /// Returns the size of the message in bytes
+    fn size_of(&self) -> usize {
+        32
+    }
+
+    /// Returns the message type
+    fn msg_code(&self) -> Msg {
+        Msg::PortMod
+    }
+
+    /// Marshals the message into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the message to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u32::<BigEndian>(self.port_no);
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+        self.hw_addr.marshal(bytes);
+        // padding
+        let _ = bytes.write_u16::<BigEndian>(0);
+        let _ = bytes.write_u32::<BigEndian>(self.config.clone().into());
+        let _ = bytes.write_u32::<BigEndian>(self.mask.clone().into());
+        let _ = bytes.write_u32::<BigEndian>(match &self.advertise {
+            Some(features) => features.clone().into(),
+            None => 0,
+        });
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+    }
+}
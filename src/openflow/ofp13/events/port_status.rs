@@ -0,0 +1,68 @@
+//! OpenFlow v1.3 Port-Status Message Implementation
+//!
+//! This module implements the Port-Status message type used in OpenFlow v1.3 protocol.
+//! Port-Status messages are sent by the switch whenever a port is added, removed
+//! or changes its configuration or state, e.g. when a link goes down.
+
+use byteorder::ReadBytesExt;
+use std::io::{BufRead, Cursor, Error};
+
+use crate::openflow::ofp13::Port;
+
+/// Represents the reason why a port status message was sent
+#[repr(u8)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PortStatusReason {
+    /// The port was added
+    Add,
+    /// The port was removed
+    Delete,
+    /// Some attribute of the port has changed
+    Modify,
+    /// Unknown reason code
+    Unknown(u8),
+}
+
+impl PortStatusReason {
+    /// Creates a new PortStatusReason from a reason code
+    ///
+    /// # Arguments
+    /// * `code` - The numeric reason code
+    ///
+    /// # Returns
+    /// The corresponding PortStatusReason variant
+    fn new(code: u8) -> Self {
+        match code {
+            0 => PortStatusReason::Add,
+            1 => PortStatusReason::Delete,
+            2 => PortStatusReason::Modify,
+            t => PortStatusReason::Unknown(t),
+        }
+    }
+}
+
+/// Represents a Port-Status message in OpenFlow v1.3
+#[derive(Clone, Debug)]
+pub struct PortStatusEvent {
+    /// Reason why the message was sent
+    pub reason: PortStatusReason,
+    /// Full description of the port after the change
+    pub port: Port,
+}
+
+impl PortStatusEvent {
+    /// Parses a port-status event from a byte buffer
+    ///
+    /// # Arguments
+    /// * `payload` - The byte buffer containing the port-status message body
+    ///
+    /// # Returns
+    /// Result containing either the parsed PortStatusEvent or an error
+    pub fn parse(payload: &[u8]) -> Result<PortStatusEvent, Error> {
+        let mut bytes = Cursor::new(payload);
+        let reason = PortStatusReason::new(bytes.read_u8()?);
+        bytes.consume(7);
+        let port = Port::parse(&mut bytes)?;
+        Ok(PortStatusEvent { reason, port })
+    }
+}
@@ -0,0 +1,145 @@
+//! OpenFlow v1.3 Controller Role Messages
+//!
+//! This module implements the Role Request and Role Reply messages used in OpenFlow v1.3
+//! protocol. When several controllers are connected to one switch, each of them holds a
+//! role: an equal or master controller has full access, while a slave controller only
+//! receives port status messages and may not change the state of the switch.
+//!
+//! Master and slave requests carry a generation id that lets the switch reject requests
+//! from a controller whose view of the election is outdated.
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{BufRead, Cursor, Error, ErrorKind};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+/// Roles a controller can hold on a switch
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u32)]
+pub enum ControllerRole {
+    /// Keep the current role, used to query the role and generation id
+    NoChange = 0,
+    /// Full access, at least one of possibly many equal controllers
+    Equal = 1,
+    /// Full access, at most one master
+    Master = 2,
+    /// Read-only access
+    Slave = 3,
+}
+
+impl ControllerRole {
+    /// Returns true if the role requires a generation id
+    pub fn uses_generation_id(&self) -> bool {
+        matches!(self, Self::Master | Self::Slave)
+    }
+}
+
+impl TryFrom<u32> for ControllerRole {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::NoChange),
+            1 => Ok(Self::Equal),
+            2 => Ok(Self::Master),
+            3 => Ok(Self::Slave),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown controller role {}", value),
+            )),
+        }
+    }
+}
+
+impl From<ControllerRole> for u32 {
+    fn from(value: ControllerRole) -> Self {
+        value as u32
+    }
+}
+
+/// Role request message structure
+pub struct RoleRequestEvent {
+    /// Role to claim
+    pub role: ControllerRole,
+    /// Generation id of the election, ignored by the switch for equal and no change
+    pub generation_id: u64,
+}
+
+impl RoleRequestEvent {
+    /// Creates a new role request
+    ///
+    /// # Arguments
+    /// * `role` - Role to claim
+    /// * `generation_id` - Generation id of the election
+    ///
+    /// # Returns
+    /// * `RoleRequestEvent` - The new role request
+    pub fn new(role: ControllerRole, generation_id: u64) -> Self {
+        Self {
+            role,
+            generation_id,
+        }
+    }
+
+    /// Creates a request that only queries the current role and generation id
+    pub fn query() -> Self {
+        Self::new(ControllerRole::NoChange, 0)
+    }
+}
+
+impl MessageMarshal for RoleRequestEvent {
+    /// Returns the message type as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::RoleRequest as usize
+    }
+
+    /// Returns the size of the message in bytes
+    fn size_of(&self) -> usize {
+        16
+    }
+
+    /// Returns the message type
+    fn msg_code(&self) -> Msg {
+        Msg::RoleRequest
+    }
+
+    /// Marshals the message into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the message to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u32::<BigEndian>(self.role.into());
+        // padding
+        let _ = bytes.write_u32::<BigEndian>(0);
+        let _ = bytes.write_u64::<BigEndian>(self.generation_id);
+    }
+}
+
+/// Role reply message structure
+#[derive(Clone, Debug)]
+pub struct RoleReplyEvent {
+    /// Role the controller holds after the request
+    pub role: ControllerRole,
+    /// Generation id known to the switch
+    pub generation_id: u64,
+}
+
+impl RoleReplyEvent {
+    /// Parses a role reply from a byte buffer
+    ///
+    /// # Arguments
+    /// * `payload` - The byte buffer containing the role reply body
+    ///
+    /// # Returns
+    /// Result containing either the parsed RoleReplyEvent or an error
+    pub fn parse(payload: &[u8]) -> Result<Self, Error> {
+        let mut bytes = Cursor::new(payload);
+        let role = ControllerRole::try_from(bytes.read_u32::<BigEndian>()?)?;
+        bytes.consume(4);
+        let generation_id = bytes.read_u64::<BigEndian>()?;
+        Ok(Self {
+            role,
+            generation_id,
+        })
+    }
+}
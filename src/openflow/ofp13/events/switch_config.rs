@@ -0,0 +1,191 @@
+//! OpenFlow v1.3 Switch Configuration Messages
+//!
+//! This module implements the Get Config Request, Get Config Reply and Set Config
+//! messages used in OpenFlow v1.3 protocol. The switch configuration selects how IP
+//! fragments are handled and how many bytes of a packet are included in packet-in
+//! messages generated by table-miss flow entries.
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Cursor, Error, ErrorKind};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+/// Handling of IP fragments (`ofp_config_flags`)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(u16)]
+pub enum FragHandling {
+    /// No special handling for fragments
+    #[default]
+    Normal = 0,
+    /// Drop fragments
+    Drop = 1,
+    /// Reassemble fragments, only if the switch supports it
+    Reassemble = 2,
+}
+
+impl TryFrom<u16> for FragHandling {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value & 3 {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Drop),
+            2 => Ok(Self::Reassemble),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown fragment handling {}", value),
+            )),
+        }
+    }
+}
+
+impl From<FragHandling> for u16 {
+    fn from(value: FragHandling) -> Self {
+        value as u16
+    }
+}
+
+/// Configuration of a switch (`ofp_switch_config`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwitchConfig {
+    /// Handling of IP fragments
+    pub frag: FragHandling,
+    /// Max bytes of a packet sent to the controller by table-miss flow entries
+    pub miss_send_len: u16,
+}
+
+impl SwitchConfig {
+    /// Size of the configuration on the wire
+    pub const SIZE: usize = 4;
+
+    /// Largest `miss_send_len` that still lets the switch buffer the packet
+    pub const MAX_LEN: u16 = 0xffe5;
+
+    /// `miss_send_len` that sends the whole packet without buffering it
+    pub const NO_BUFFER: u16 = 0xffff;
+
+    /// Creates a new switch configuration
+    ///
+    /// # Arguments
+    /// * `frag` - Handling of IP fragments
+    /// * `miss_send_len` - Max bytes of a packet sent to the controller
+    pub fn new(frag: FragHandling, miss_send_len: u16) -> Self {
+        Self {
+            frag,
+            miss_send_len,
+        }
+    }
+
+    /// Parses a switch configuration from a get config reply body
+    ///
+    /// # Arguments
+    /// * `payload` - The byte buffer containing the reply body
+    ///
+    /// # Returns
+    /// Result containing either the parsed SwitchConfig or an error
+    pub fn parse(payload: &[u8]) -> Result<Self, Error> {
+        let mut bytes = Cursor::new(payload);
+        let frag = FragHandling::try_from(bytes.read_u16::<BigEndian>()?)?;
+        let miss_send_len = bytes.read_u16::<BigEndian>()?;
+        Ok(Self::new(frag, miss_send_len))
+    }
+
+    /// Marshals the configuration into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the configuration to
+    pub fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u16::<BigEndian>(self.frag.into());
+        let _ = bytes.write_u16::<BigEndian>(self.miss_send_len);
+    }
+}
+
+/// The configuration a switch starts with: normal fragments and 128 byte packet-ins
+impl Default for SwitchConfig {
+    fn default() -> Self {
+        Self::new(FragHandling::Normal, 128)
+    }
+}
+
+/// Get config request message structure
+pub struct GetConfigRequestEvent {}
+
+impl GetConfigRequestEvent {
+    /// Creates a new get config request
+    ///
+    /// # Returns
+    /// * `GetConfigRequestEvent` - The new get config request
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for GetConfigRequestEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageMarshal for GetConfigRequestEvent {
+    /// Returns the message type as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::ConfigRequest as usize
+    }
+
+    /// Returns the size of the message in bytes
+    fn size_of(&self) -> usize {
+        0
+    }
+
+    /// Returns the message type
+    fn msg_code(&self) -> Msg {
+        Msg::ConfigRequest
+    }
+
+    /// Marshals the message into a byte buffer, the request has no body
+    fn marshal(&self, _: &mut Vec<u8>) {}
+}
+
+/// Set config message structure
+pub struct SetConfigEvent {
+    /// Configuration to apply to the switch
+    pub config: SwitchConfig,
+}
+
+impl SetConfigEvent {
+    /// Creates a new set config message
+    ///
+    /// # Arguments
+    /// * `config` - Configuration to apply to the switch
+    ///
+    /// # Returns
+    /// * `SetConfigEvent` - The new set config message
+    pub fn new(config: SwitchConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl MessageMarshal for SetConfigEvent {
+    /// Returns the message type as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::SetConfig as usize
+    }
+
+    /// Returns the size of the message in bytes
+    fn size_of(&self) -> usize {
+        SwitchConfig::SIZE
+    }
+
+    /// Returns the message type
+    fn msg_code(&self) -> Msg {
+        Msg::SetConfig
+    }
+
+    /// Marshals the message into a byte buffer
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the message to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        self.config.marshal(bytes);
+    }
+}
@@ -0,0 +1,63 @@
+//! OpenFlow v1.3 Table Modification Message Implementation
+//!
+//! This module implements the Table-Mod message type used in OpenFlow v1.3 protocol.
+//! Table-Mod messages change the configuration of a flow table. OpenFlow v1.3 only
+//! keeps the deprecated table-miss bits in the configuration; the table-miss behaviour
+//! itself is set with a table-miss flow entry.
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+use crate::openflow::ofp13::{MessageMarshal, Msg};
+
+/// Table modification event structure
+pub struct TableModEvent {
+    /// ID of the table to configure, `TABLE_ALL` for every table
+    pub table_id: u8,
+    /// Configuration flags of the table (`OFPTC_*`)
+    pub config: u32,
+}
+
+impl TableModEvent {
+    /// Mask of the table configuration bits deprecated since OpenFlow v1.3
+    pub const DEPRECATED_MASK: u32 = 3;
+
+    /// Creates a new table modification event
+    ///
+    /// # Arguments
+    /// * `table_id` - ID of the table to configure
+    /// * `config` - Configuration flags of the table
+    ///
+    /// # Returns
+    /// * `TableModEvent` - The new table modification event
+    pub fn new(table_id: u8, config: u32) -> Self {
+        Self { table_id, config }
+    }
+}
+
+impl MessageMarshal for TableModEvent {
+    /// Marshals the table modification into its wire format
+    ///
+    /// # Arguments
+    /// * `bytes` - The buffer to write the message body to
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        let _ = bytes.write_u8(self.table_id);
+        // padding
+        bytes.extend_from_slice(&[0; 3]);
+        let _ = bytes.write_u32::<BigEndian>(self.config);
+    }
+
+    /// Returns the OpenFlow message code for Table-Mod
+    fn msg_code(&self) -> Msg {
+        Msg::TableMod
+    }
+
+    /// Returns the message code as a usize
+    fn msg_usize(&self) -> usize {
+        Msg::TableMod as usize
+    }
+
+    /// Returns the size of the message body in bytes
+    fn size_of(&self) -> usize {
+        8
+    }
+}
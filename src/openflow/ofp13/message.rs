@@ -57,10 +57,10 @@ pub enum Msg {
     BarrierRequest = 20,
     /// Barrier reply confirmation
     BarrierReply = 21,
-    /// Get configuration request
-    GetConfigRequest = 22,
-    /// Get configuration reply
-    GetConfigReply = 23,
+    /// Queue configuration request
+    QueueGetConfigRequest = 22,
+    /// Queue configuration reply
+    QueueGetConfigReply = 23,
     /// Controller role request
     RoleRequest = 24,
     /// Controller role reply
@@ -91,11 +91,21 @@ impl Msg {
     /// # Returns
     /// The corresponding Msg enum variant, or NotFound if the code is invalid
     pub fn from(msg_code: u8) -> Self {
-        if msg_code > 21 {
+        if msg_code > 29 {
             return Self::NotFound;
         }
         unsafe { transmute::<u8, Msg>(msg_code) }
     }
+
+    /// Returns true for messages the switch sends on its own rather than as a reply
+    ///
+    /// Such messages never complete a request, even if their xid happens to match one.
+    pub fn is_unsolicited(&self) -> bool {
+        matches!(
+            self,
+            Msg::Hello | Msg::EchoRequest | Msg::PacketIn | Msg::FlowRemove | Msg::PortStatus
+        )
+    }
 }
 
 /// Implementation of From trait to convert Msg to u8
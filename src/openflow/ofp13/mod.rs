@@ -18,12 +18,22 @@ pub mod message;
 pub use message::Msg;
 
 pub mod ofp_port;
-pub use ofp_port::PseudoPort;
+pub use ofp_port::{Port, PortConfig, PortFeatures, PortState, PseudoPort};
 
 pub mod events;
 pub use events::{
-    Action, EchoReplyEvent, EchoRequestEvent, ErrorEvent, FeaturesReplyEvent, FlowModEvent,
-    HelloEvent, MatchFields, PacketInEvent, PacketOutEvent,
+    Action, AggregateStats, AggregateStatsRequestEvent, AsyncConfig, BarrierRequestEvent,
+    DescRequestEvent, EchoReplyEvent, EchoRequestEvent, ErrorEvent, ExperimenterAction,
+    ExperimenterEvent, FeaturesReplyEvent, FlowModEvent, FlowRemovedEvent, FlowStats,
+    FlowStatsRequestEvent, GetAsyncRequestEvent, GetConfigRequestEvent, GroupDesc,
+    GroupDescRequestEvent, GroupFeatures, GroupFeaturesRequestEvent, GroupModEvent, GroupStats,
+    GroupStatsRequestEvent, HelloEvent, InstructionSet, MatchFields, MeterConfig,
+    MeterConfigRequestEvent, MeterFeatures, MeterFeaturesRequestEvent, MeterModEvent, MeterStats,
+    MeterStatsRequestEvent, MultipartReplyBody, MultipartReplyEvent, OxmExtension, PacketInEvent,
+    PacketOutEvent, PortDescRequestEvent, PortModEvent, PortStats, PortStatsRequestEvent,
+    PortStatusEvent, QueueStats, QueueStatsRequestEvent, RoleReplyEvent, RoleRequestEvent,
+    SetAsyncEvent, SetConfigEvent, SetField, SwitchConfig, SwitchDescription, TableFeatures,
+    TableFeaturesRequestEvent, TableModEvent, TableStats, TableStatsRequestEvent,
 };
 
 pub mod ofp_header;
@@ -35,6 +45,12 @@ pub use ofp_manager::Openflow13;
 pub mod controller_frame;
 pub use controller_frame::ControllerFrame13;
 
+pub mod registry;
+pub use registry::{Datapath, DatapathRegistry};
+
+pub mod switch_handle;
+pub use switch_handle::{Reply, RequestError, SwitchHandle};
+
 pub mod tcp_listener;
 pub use tcp_listener::tcp_listener_handler;
 
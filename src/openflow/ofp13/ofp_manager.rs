@@ -1,6 +1,16 @@
 use super::{
-    events::{Action, FeaturesReqEvent, Payload},
+    events::{
+        multipart::QUEUE_ALL, Action, AggregateStatsRequestEvent, AsyncConfig, BarrierRequestEvent,
+        ControllerRole, DescRequestEvent, ExperimenterEvent, FeaturesReqEvent,
+        FlowStatsRequestEvent, GetAsyncRequestEvent, GetConfigRequestEvent, GroupDescRequestEvent,
+        GroupFeaturesRequestEvent, GroupStatsRequestEvent, MatchFields, MeterConfigRequestEvent,
+        MeterFeaturesRequestEvent, MeterStatsRequestEvent, OfpGroup, OfpMeter, Payload,
+        PortDescRequestEvent, PortStatsRequestEvent, QueueStatsRequestEvent, RoleRequestEvent,
+        SetAsyncEvent, SetConfigEvent, SwitchConfig, TableFeaturesRequestEvent, TableModEvent,
+        TableStatsRequestEvent,
+    },
     ofp_header::OfpHeader,
+    ofp_port::OfpPort,
     HelloEvent, Msg, OfpMsgEvent, OpenflowHeader, PacketOutEvent,
 };
 
@@ -45,6 +55,60 @@ impl OfpMsgEvent for Openflow13 {
         FeaturesReqEvent::new()
     }
 
+    /// Creates a new Barrier Request event to confirm earlier messages were processed
+    fn barrier_req(&self) -> BarrierRequestEvent {
+        BarrierRequestEvent::new()
+    }
+
+    /// Creates a new Get Config Request event to read the switch configuration
+    fn get_config_req(&self) -> GetConfigRequestEvent {
+        GetConfigRequestEvent::new()
+    }
+
+    /// Creates a new Set Config event
+    /// Parameters:
+    /// - config: Switch configuration to apply
+    fn set_config(&self, config: SwitchConfig) -> SetConfigEvent {
+        SetConfigEvent::new(config)
+    }
+
+    /// Creates a new Table Mod event
+    /// Parameters:
+    /// - table_id: Table to configure
+    /// - config: Configuration flags of the table
+    fn table_mod(&self, table_id: u8, config: u32) -> TableModEvent {
+        TableModEvent::new(table_id, config)
+    }
+
+    /// Creates a new Experimenter message
+    /// Parameters:
+    /// - experimenter: Experimenter id
+    /// - exp_type: Experimenter defined message type
+    /// - data: Experimenter defined body
+    fn experimenter(&self, experimenter: u32, exp_type: u32, data: Vec<u8>) -> ExperimenterEvent {
+        ExperimenterEvent::new(experimenter, exp_type, data)
+    }
+
+    /// Creates a new Role Request event to claim a controller role
+    /// Parameters:
+    /// - role: Role to claim
+    /// - generation_id: Generation id of the election
+    fn role_req(&self, role: ControllerRole, generation_id: u64) -> RoleRequestEvent {
+        RoleRequestEvent::new(role, generation_id)
+    }
+
+    /// Creates a new Get Async Request event to read the asynchronous message configuration
+    fn get_async_req(&self) -> GetAsyncRequestEvent {
+        GetAsyncRequestEvent::new()
+    }
+
+    /// Creates a new Set Async event
+    /// Parameters:
+    /// - config: Asynchronous message configuration to apply
+    fn set_async(&self, config: AsyncConfig) -> SetAsyncEvent {
+        SetAsyncEvent::new(config)
+    }
+
     /// Creates a new Packet Out event for sending packets through the switch
     /// Parameters:
     /// - port_id: Optional port number to send the packet out
@@ -59,6 +123,104 @@ impl OfpMsgEvent for Openflow13 {
         PacketOutEvent::new(port_id, payload, actions)
     }
 
+    /// Creates a new flow statistics request
+    /// Parameters:
+    /// - table_id: ID of the table to read, or TABLE_ALL for every table
+    /// - match_fields: Fields the reported flows must match
+    fn flow_stats_req(&self, table_id: u8, match_fields: MatchFields) -> FlowStatsRequestEvent {
+        FlowStatsRequestEvent::new(table_id, match_fields)
+    }
+
+    /// Creates a new aggregate flow statistics request
+    /// Parameters:
+    /// - table_id: ID of the table to read, or TABLE_ALL for every table
+    /// - match_fields: Fields the summed flows must match
+    fn aggregate_stats_req(
+        &self,
+        table_id: u8,
+        match_fields: MatchFields,
+    ) -> AggregateStatsRequestEvent {
+        AggregateStatsRequestEvent::new(table_id, match_fields)
+    }
+
+    /// Creates a new flow table statistics request
+    fn table_stats_req(&self) -> TableStatsRequestEvent {
+        TableStatsRequestEvent::new()
+    }
+
+    /// Creates a new port statistics request
+    /// Parameters:
+    /// - port_no: Port to read, or None for every port
+    fn port_stats_req(&self, port_no: Option<u32>) -> PortStatsRequestEvent {
+        PortStatsRequestEvent::new(port_no.unwrap_or(OfpPort::Any as u32))
+    }
+
+    /// Creates a new queue statistics request
+    /// Parameters:
+    /// - port_no: Port to read, or None for every port
+    /// - queue_id: Queue to read, or None for every queue
+    fn queue_stats_req(
+        &self,
+        port_no: Option<u32>,
+        queue_id: Option<u32>,
+    ) -> QueueStatsRequestEvent {
+        QueueStatsRequestEvent::new(
+            port_no.unwrap_or(OfpPort::Any as u32),
+            queue_id.unwrap_or(QUEUE_ALL),
+        )
+    }
+
+    /// Creates a new switch description request
+    fn desc_req(&self) -> DescRequestEvent {
+        DescRequestEvent::new()
+    }
+
+    /// Creates a new port description request
+    fn port_desc_req(&self) -> PortDescRequestEvent {
+        PortDescRequestEvent::new()
+    }
+
+    /// Creates a new group statistics request
+    /// Parameters:
+    /// - group_id: Group to read, or None for every group
+    fn group_stats_req(&self, group_id: Option<u32>) -> GroupStatsRequestEvent {
+        GroupStatsRequestEvent::new(group_id.unwrap_or(OfpGroup::All as u32))
+    }
+
+    /// Creates a new group description request
+    fn group_desc_req(&self) -> GroupDescRequestEvent {
+        GroupDescRequestEvent::new()
+    }
+
+    /// Creates a new group features request
+    fn group_features_req(&self) -> GroupFeaturesRequestEvent {
+        GroupFeaturesRequestEvent::new()
+    }
+
+    /// Creates a new meter statistics request
+    /// Parameters:
+    /// - meter_id: Meter to read, or None for every meter
+    fn meter_stats_req(&self, meter_id: Option<u32>) -> MeterStatsRequestEvent {
+        MeterStatsRequestEvent::new(meter_id.unwrap_or(OfpMeter::All as u32))
+    }
+
+    /// Creates a new meter configuration request
+    /// Parameters:
+    /// - meter_id: Meter to read, or None for every meter
+    fn meter_config_req(&self, meter_id: Option<u32>) -> MeterConfigRequestEvent {
+        MeterConfigRequestEvent::new(meter_id.unwrap_or(OfpMeter::All as u32))
+    }
+
+    /// Creates a new meter features request
+    fn meter_features_req(&self) -> MeterFeaturesRequestEvent {
+        MeterFeaturesRequestEvent::new()
+    }
+
+    /// Creates a new table features request
+    fn table_features_req(&self) -> TableFeaturesRequestEvent {
+        TableFeaturesRequestEvent::new()
+    }
+
     /// Returns the OpenFlow protocol version (0x04 for version 1.3)
     fn ofp_version() -> usize {
         0x04
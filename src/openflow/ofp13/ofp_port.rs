@@ -4,7 +4,11 @@
 //! both physical ports and special ports used for packet forwarding and control.
 //! OpenFlow 1.3 uses 32-bit port numbers instead of 16-bit as in version 1.0.
 
-use byteorder::{BigEndian, WriteBytesExt};
+use std::io::{Error, Read};
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{openflow::port_name, utils::MacAddr};
 
 /// Represents the standard OpenFlow 1.3 port numbers
 ///
@@ -39,7 +43,7 @@ pub enum OfpPort {
 /// This enum provides a more ergonomic way to work with ports, handling both
 /// physical ports and special ports with their associated data. All port numbers
 /// are 32-bit values in OpenFlow 1.3.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum PseudoPort {
     /// Physical port with port number
     PhysicalPort(u32),
@@ -92,7 +96,7 @@ impl PseudoPort {
                 Some(len) => PseudoPort::Controller(len),
                 None => PseudoPort::Unsupport,
             },
-            p if p == (OfpPort::Local as u32) => PseudoPort::InPort,
+            p if p == (OfpPort::Local as u32) => PseudoPort::Local,
             _ => {
                 if port <= (OfpPort::Max as u32) {
                     PseudoPort::PhysicalPort(port)
@@ -123,3 +127,219 @@ impl PseudoPort {
         let _ = bytes.write_u32::<BigEndian>(port);
     }
 }
+
+/// Length of the port name field in bytes
+pub const OFP_MAX_PORT_NAME_LEN: usize = 16;
+
+/// Description of a switch port (`ofp_port`)
+///
+/// Switches report this structure in port description replies and port status
+/// messages.
+#[derive(Clone, Debug)]
+pub struct Port {
+    /// Port number
+    pub port_no: u32,
+    /// Hardware address of the port
+    pub hw_addr: MacAddr,
+    /// Human readable name of the port
+    pub name: String,
+    /// Port configuration flags
+    pub config: PortConfig,
+    /// Port state flags
+    pub state: PortState,
+    /// Current features
+    pub curr: PortFeatures,
+    /// Features being advertised by the port
+    pub advertised: PortFeatures,
+    /// Features supported by the port
+    pub supported: PortFeatures,
+    /// Features advertised by the peer
+    pub peer: PortFeatures,
+    /// Current port bitrate in kbps
+    pub curr_speed: u32,
+    /// Max port bitrate in kbps
+    pub max_speed: u32,
+}
+
+impl Port {
+    /// Size of the port structure on the wire
+    pub const SIZE: usize = 64;
+
+    /// Parses a port description from a reader
+    ///
+    /// # Arguments
+    /// * `bytes` - Reader positioned at the start of the port structure
+    ///
+    /// # Returns
+    /// Result containing either the parsed Port or an error
+    pub fn parse(bytes: &mut impl Read) -> Result<Self, Error> {
+        let port_no = bytes.read_u32::<BigEndian>()?;
+        // padding
+        bytes.read_u32::<BigEndian>()?;
+        let mut mac = [0u8; 6];
+        bytes.read_exact(&mut mac)?;
+        // padding
+        bytes.read_u16::<BigEndian>()?;
+        let mut name = [0u8; OFP_MAX_PORT_NAME_LEN];
+        bytes.read_exact(&mut name)?;
+        Ok(Self {
+            port_no,
+            hw_addr: MacAddr::new(mac),
+            name: port_name(&name),
+            config: bytes.read_u32::<BigEndian>()?.into(),
+            state: bytes.read_u32::<BigEndian>()?.into(),
+            curr: bytes.read_u32::<BigEndian>()?.into(),
+            advertised: bytes.read_u32::<BigEndian>()?.into(),
+            supported: bytes.read_u32::<BigEndian>()?.into(),
+            peer: bytes.read_u32::<BigEndian>()?.into(),
+            curr_speed: bytes.read_u32::<BigEndian>()?,
+            max_speed: bytes.read_u32::<BigEndian>()?,
+        })
+    }
+}
+
+/// Bitmap of port configuration flags (`ofp_port_config`)
+///
+/// Switches report the current configuration in port descriptions; the controller
+/// changes it with port modification messages.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PortConfig {
+    /// Port is administratively down
+    pub port_down: bool,
+    /// Drop all packets received by the port
+    pub no_recv: bool,
+    /// Drop packets forwarded to the port
+    pub no_fwd: bool,
+    /// Do not send packet-in messages for the port
+    pub no_packet_in: bool,
+}
+
+impl From<u32> for PortConfig {
+    fn from(value: u32) -> Self {
+        Self {
+            port_down: value & 1 == 1,
+            no_recv: value >> 2 & 1 == 1,
+            no_fwd: value >> 5 & 1 == 1,
+            no_packet_in: value >> 6 & 1 == 1,
+        }
+    }
+}
+
+impl From<PortConfig> for u32 {
+    fn from(value: PortConfig) -> Self {
+        (value.port_down as u32)
+            | (value.no_recv as u32) << 2
+            | (value.no_fwd as u32) << 5
+            | (value.no_packet_in as u32) << 6
+    }
+}
+
+/// Bitmap of port state flags (`ofp_port_state`)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PortState {
+    /// No physical link present
+    pub link_down: bool,
+    /// Port is blocked
+    pub blocked: bool,
+    /// Live for fast failover groups
+    pub live: bool,
+}
+
+impl From<u32> for PortState {
+    fn from(value: u32) -> Self {
+        Self {
+            link_down: value & 1 == 1,
+            blocked: value >> 1 & 1 == 1,
+            live: value >> 2 & 1 == 1,
+        }
+    }
+}
+
+impl From<PortState> for u32 {
+    fn from(value: PortState) -> Self {
+        (value.link_down as u32) | (value.blocked as u32) << 1 | (value.live as u32) << 2
+    }
+}
+
+/// Bitmap of port features (`ofp_port_features`)
+///
+/// Used for the current, advertised, supported and peer features of a port.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PortFeatures {
+    /// 10 Mb half-duplex rate support
+    pub rate_10mb_hd: bool,
+    /// 10 Mb full-duplex rate support
+    pub rate_10mb_fd: bool,
+    /// 100 Mb half-duplex rate support
+    pub rate_100mb_hd: bool,
+    /// 100 Mb full-duplex rate support
+    pub rate_100mb_fd: bool,
+    /// 1 Gb half-duplex rate support
+    pub rate_1gb_hd: bool,
+    /// 1 Gb full-duplex rate support
+    pub rate_1gb_fd: bool,
+    /// 10 Gb full-duplex rate support
+    pub rate_10gb_fd: bool,
+    /// 40 Gb full-duplex rate support
+    pub rate_40gb_fd: bool,
+    /// 100 Gb full-duplex rate support
+    pub rate_100gb_fd: bool,
+    /// 1 Tb full-duplex rate support
+    pub rate_1tb_fd: bool,
+    /// Other rate, not in the list
+    pub other: bool,
+    /// Copper medium
+    pub copper: bool,
+    /// Fiber medium
+    pub fiber: bool,
+    /// Auto-negotiation
+    pub autoneg: bool,
+    /// Pause
+    pub pause: bool,
+    /// Asymmetric pause
+    pub pause_asym: bool,
+}
+
+impl From<u32> for PortFeatures {
+    fn from(value: u32) -> Self {
+        Self {
+            rate_10mb_hd: value & 1 == 1,
+            rate_10mb_fd: value >> 1 & 1 == 1,
+            rate_100mb_hd: value >> 2 & 1 == 1,
+            rate_100mb_fd: value >> 3 & 1 == 1,
+            rate_1gb_hd: value >> 4 & 1 == 1,
+            rate_1gb_fd: value >> 5 & 1 == 1,
+            rate_10gb_fd: value >> 6 & 1 == 1,
+            rate_40gb_fd: value >> 7 & 1 == 1,
+            rate_100gb_fd: value >> 8 & 1 == 1,
+            rate_1tb_fd: value >> 9 & 1 == 1,
+            other: value >> 10 & 1 == 1,
+            copper: value >> 11 & 1 == 1,
+            fiber: value >> 12 & 1 == 1,
+            autoneg: value >> 13 & 1 == 1,
+            pause: value >> 14 & 1 == 1,
+            pause_asym: value >> 15 & 1 == 1,
+        }
+    }
+}
+
+impl From<PortFeatures> for u32 {
+    fn from(value: PortFeatures) -> Self {
+        (value.rate_10mb_hd as u32)
+            | (value.rate_10mb_fd as u32) << 1
+            | (value.rate_100mb_hd as u32) << 2
+            | (value.rate_100mb_fd as u32) << 3
+            | (value.rate_1gb_hd as u32) << 4
+            | (value.rate_1gb_fd as u32) << 5
+            | (value.rate_10gb_fd as u32) << 6
+            | (value.rate_40gb_fd as u32) << 7
+            | (value.rate_100gb_fd as u32) << 8
+            | (value.rate_1tb_fd as u32) << 9
+            | (value.other as u32) << 10
+            | (value.copper as u32) << 11
+            | (value.fiber as u32) << 12
+            | (value.autoneg as u32) << 13
+            | (value.pause as u32) << 14
+            | (value.pause_asym as u32) << 15
+    }
+}
@@ -0,0 +1,178 @@
+//! OpenFlow 1.3 Datapath Registry
+//!
+//! This module keeps track of every switch connected to a controller. The
+//! listener clones the controller for each connection, so per-connection state
+//! cannot be used to find other switches. The registry is shared by all
+//! connections of a listener instead: a datapath is added once its features
+//! reply arrives and removed again when its connection closes.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use super::{
+    events::PortStatusReason, FeaturesReplyEvent, Port, PortStatusEvent, SwitchDescription,
+    SwitchHandle,
+};
+
+/// A connected switch and everything known about it
+#[derive(Clone)]
+pub struct Datapath {
+    /// Datapath id reported in the features reply
+    pub datapath_id: u64,
+    /// Handle used to send messages to the switch
+    pub switch: SwitchHandle,
+    /// Features reported during the handshake
+    pub features: FeaturesReplyEvent,
+    /// Known ports, keyed by port number
+    pub ports: HashMap<u32, Port>,
+    /// Description of the switch, once it was fetched
+    pub description: Option<SwitchDescription>,
+}
+
+impl Datapath {
+    /// Creates a new datapath entry from a features reply
+    ///
+    /// # Arguments
+    /// * `switch` - Handle of the connection
+    /// * `features` - Features reply received from the switch
+    pub fn new(switch: SwitchHandle, features: FeaturesReplyEvent) -> Self {
+        Self {
+            datapath_id: features.datapath_id,
+            switch,
+            features,
+            ports: HashMap::new(),
+            description: None,
+        }
+    }
+
+    /// Applies a port status message to the port table
+    ///
+    /// Added and modified ports replace the known entry, deleted ports are removed.
+    ///
+    /// # Arguments
+    /// * `status` - Port status message received from the switch
+    pub fn apply_port_status(&mut self, status: &PortStatusEvent) {
+        match status.reason {
+            PortStatusReason::Delete => {
+                self.ports.remove(&status.port.port_no);
+            }
+            _ => {
+                self.ports.insert(status.port.port_no, status.port.clone());
+            }
+        }
+    }
+}
+
+/// Registry of connected datapaths shared across all connections
+///
+/// Cloning the registry is cheap and every clone refers to the same table.
+#[derive(Clone, Default)]
+pub struct DatapathRegistry {
+    /// Connected datapaths keyed by datapath id
+    datapaths: Arc<RwLock<HashMap<u64, Datapath>>>,
+}
+
+impl DatapathRegistry {
+    /// Creates a new, empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a datapath, replacing any stale entry with the same datapath id
+    ///
+    /// # Arguments
+    /// * `datapath` - The datapath to register
+    pub fn insert(&self, datapath: Datapath) {
+        if let Ok(mut datapaths) = self.datapaths.write() {
+            datapaths.insert(datapath.datapath_id, datapath);
+        }
+    }
+
+    /// Removes the datapath registered for a connection
+    ///
+    /// The entry is only removed when it still belongs to the given connection,
+    /// so a switch that has already reconnected is not dropped by its old connection.
+    ///
+    /// # Arguments
+    /// * `switch` - Handle of the closed connection
+    ///
+    /// # Returns
+    /// The removed datapath, if any
+    pub fn remove(&self, switch: &SwitchHandle) -> Option<Datapath> {
+        let datapath_id = switch.datapath_id()?;
+        let mut datapaths = self.datapaths.write().ok()?;
+        match datapaths.get(&datapath_id) {
+            Some(dp) if dp.switch.same_connection(switch) => datapaths.remove(&datapath_id),
+            _ => None,
+        }
+    }
+
+    /// Returns a snapshot of a datapath
+    ///
+    /// # Arguments
+    /// * `datapath_id` - Datapath id of the switch
+    pub fn get(&self, datapath_id: u64) -> Option<Datapath> {
+        self.datapaths.read().ok()?.get(&datapath_id).cloned()
+    }
+
+    /// Returns the handle of a connected switch
+    ///
+    /// # Arguments
+    /// * `datapath_id` - Datapath id of the switch
+    pub fn switch(&self, datapath_id: u64) -> Option<SwitchHandle> {
+        self.datapaths
+            .read()
+            .ok()?
+            .get(&datapath_id)
+            .map(|dp| dp.switch.clone())
+    }
+
+    /// Returns the ids of all connected datapaths
+    pub fn datapath_ids(&self) -> Vec<u64> {
+        match self.datapaths.read() {
+            Ok(datapaths) => datapaths.keys().copied().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Returns a snapshot of all connected datapaths
+    pub fn datapaths(&self) -> Vec<Datapath> {
+        match self.datapaths.read() {
+            Ok(datapaths) => datapaths.values().cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the number of connected datapaths
+    pub fn len(&self) -> usize {
+        self.datapaths.read().map(|dp| dp.len()).unwrap_or(0)
+    }
+
+    /// Returns true when no datapath is connected
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Modifies a registered datapath in place
+    ///
+    /// # Arguments
+    /// * `datapath_id` - Datapath id of the switch
+    /// * `f` - Function applied to the entry
+    ///
+    /// # Returns
+    /// True if the datapath was found
+    pub fn update<F: FnOnce(&mut Datapath)>(&self, datapath_id: u64, f: F) -> bool {
+        match self.datapaths.write() {
+            Ok(mut datapaths) => match datapaths.get_mut(&datapath_id) {
+                Some(dp) => {
+                    f(dp);
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+}
@@ -0,0 +1,836 @@
+//! OpenFlow 1.3 Switch Handle
+//!
+//! This module provides the per-connection handle used to talk to a switch.
+//! The handle owns no socket itself: outgoing messages are marshaled and pushed
+//! into an mpsc channel that is drained by a dedicated writer task. Because of
+//! that, a handle can be cloned freely and used from timers, other switches'
+//! callbacks or any other task for as long as the connection is alive.
+
+use std::{
+    io::{Error, ErrorKind},
+    net::SocketAddr,
+    sync::{Arc, OnceLock, RwLock},
+    time::Duration,
+};
+
+use tokio::{sync::mpsc::Sender, time::timeout};
+
+use crate::openflow::{framing::OFP_HEADER_SIZE, PendingRequests};
+
+use super::{
+    events::multipart::{MultipartAssembler, MultipartType},
+    events::ControllerRole,
+    AggregateStats, AggregateStatsRequestEvent, AsyncConfig, BarrierRequestEvent, DatapathRegistry,
+    DescRequestEvent, ErrorEvent, FeaturesReplyEvent, FlowStats, FlowStatsRequestEvent,
+    GetAsyncRequestEvent, GetConfigRequestEvent, GroupDesc, GroupDescRequestEvent, GroupFeatures,
+    GroupFeaturesRequestEvent, GroupStats, GroupStatsRequestEvent, MessageMarshal, MeterConfig,
+    MeterConfigRequestEvent, MeterFeatures, MeterFeaturesRequestEvent, MeterStats,
+    MeterStatsRequestEvent, Msg, MultipartReplyBody, MultipartReplyEvent, OfpMsgEvent, Openflow13,
+    OpenflowHeader, Port, PortDescRequestEvent, PortStats, PortStatsRequestEvent, PortStatusEvent,
+    QueueStats, QueueStatsRequestEvent, RoleReplyEvent, RoleRequestEvent, SwitchConfig,
+    SwitchDescription, TableFeatures, TableFeaturesRequestEvent, TableStats,
+    TableStatsRequestEvent,
+};
+
+/// Error returned by requests awaited through `SwitchHandle::request`
+pub type RequestError = crate::openflow::RequestError<ErrorEvent>;
+
+/// Reply received for a request awaited through `SwitchHandle::request`
+pub struct Reply {
+    /// Type of the reply message
+    pub message: Msg,
+    /// Transaction ID shared by the request and the reply
+    pub xid: u32,
+    /// Body of the reply, header excluded
+    pub payload: Vec<u8>,
+}
+
+/// Role of the controller on a connection and the last known generation id
+#[derive(Clone, Copy)]
+struct RoleState {
+    /// Role granted by the switch
+    role: ControllerRole,
+    /// Generation id reported by the switch for a master or slave role
+    generation_id: Option<u64>,
+}
+
+/// Cloneable handle to a single switch connection
+///
+/// The datapath id is only known once the switch has answered the features
+/// request, so it is shared between all clones of the handle and filled in
+/// when the features reply arrives. Every handle also carries the registry
+/// of the listener that accepted it, so any callback can reach other switches.
+///
+/// The handle also tracks the role of the controller on the connection. While
+/// the controller is a slave, messages that would change the state of the switch
+/// are refused before they are sent.
+#[derive(Clone)]
+pub struct SwitchHandle {
+    /// Datapath id reported in the features reply
+    datapath_id: Arc<OnceLock<u64>>,
+    /// Address of the switch side of the TCP connection
+    peer_addr: SocketAddr,
+    /// OpenFlow version used on this connection
+    version: u8,
+    /// Queue drained by the connection's writer task
+    sender: Sender<Vec<u8>>,
+    /// Registry shared by all connections of the listener
+    registry: DatapathRegistry,
+    /// Transaction ids and requests awaiting a reply on this connection
+    requests: PendingRequests,
+    /// Multipart replies still waiting for further parts
+    multipart: MultipartAssembler,
+    /// Role of the controller on this connection
+    role: Arc<RwLock<RoleState>>,
+}
+
+impl SwitchHandle {
+    /// Creates a new handle for a connection
+    ///
+    /// # Arguments
+    /// * `peer_addr` - Address of the switch
+    /// * `version` - Negotiated OpenFlow version
+    /// * `sender` - Channel consumed by the writer task of the connection
+    /// * `registry` - Registry shared by all connections of the listener
+    pub fn new(
+        peer_addr: SocketAddr,
+        version: u8,
+        sender: Sender<Vec<u8>>,
+        registry: DatapathRegistry,
+    ) -> Self {
+        Self {
+            datapath_id: Arc::new(OnceLock::new()),
+            peer_addr,
+            version,
+            sender,
+            registry,
+            requests: PendingRequests::new(),
+            multipart: MultipartAssembler::new(),
+            role: Arc::new(RwLock::new(RoleState {
+                role: ControllerRole::Equal,
+                generation_id: None,
+            })),
+        }
+    }
+
+    /// Returns the datapath id of the switch, if the features reply was received
+    pub fn datapath_id(&self) -> Option<u64> {
+        self.datapath_id.get().copied()
+    }
+
+    /// Records the datapath id reported by the switch
+    ///
+    /// The id can only be set once per connection; later calls are ignored.
+    pub fn set_datapath_id(&self, datapath_id: u64) {
+        let _ = self.datapath_id.set(datapath_id);
+    }
+
+    /// Returns the address of the switch
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.peer_addr
+    }
+
+    /// Returns the OpenFlow version negotiated on this connection
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the registry of all datapaths connected to the same listener
+    pub fn registry(&self) -> &DatapathRegistry {
+        &self.registry
+    }
+
+    /// Returns true if both handles refer to the same connection
+    pub fn same_connection(&self, other: &SwitchHandle) -> bool {
+        Arc::ptr_eq(&self.datapath_id, &other.datapath_id)
+    }
+
+    /// Returns a transaction id that is not used by any other request on this connection
+    pub fn next_xid(&self) -> u32 {
+        self.requests.next_xid()
+    }
+
+    /// Hands a received message to the request waiting for its xid
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID of the message
+    /// * `message` - Type of the message
+    /// * `payload` - Body of the message, header excluded
+    ///
+    /// # Returns
+    /// True if the message was consumed by a waiting request
+    pub fn complete_request(&self, xid: u32, message: u8, payload: Vec<u8>) -> bool {
+        self.requests.complete(xid, message, payload)
+    }
+
+    /// Ends the request waiting for an xid with an error
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID of the request
+    /// * `error` - Why the reply could not be received
+    ///
+    /// # Returns
+    /// True if a request was waiting for the xid
+    pub fn fail_request(&self, xid: u32, error: Error) -> bool {
+        self.requests.fail(xid, error)
+    }
+
+    /// Adds a part of a multipart reply received on this connection
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction ID of the reply
+    /// * `payload` - Body of the message, header excluded
+    ///
+    /// # Returns
+    /// The joined reply body once the last part arrived, `None` while parts are missing
+    pub fn assemble_multipart(&self, xid: u32, payload: Vec<u8>) -> Result<Option<Vec<u8>>, Error> {
+        self.multipart.push(xid, payload)
+    }
+
+    /// Hands a received message to the request waiting for it, if there is one
+    ///
+    /// Called by the reader of the connection before a message is queued for the
+    /// callbacks, so that replies arrive while a callback awaits them.
+    ///
+    /// # Arguments
+    /// * `message` - One complete message, header included
+    ///
+    /// # Returns
+    /// True if the message was consumed and must not be passed to the callbacks
+    pub(crate) fn take_reply(&self, message: &[u8]) -> bool {
+        if message.len() < OFP_HEADER_SIZE {
+            return false;
+        }
+        let msg = Msg::from(message[1]);
+        let xid = u32::from_be_bytes([message[4], message[5], message[6], message[7]]);
+        if msg.is_unsolicited() || !self.requests.is_pending(xid) {
+            return false;
+        }
+        let payload = message[OFP_HEADER_SIZE..].to_vec();
+        if let Msg::MultipartReply = msg {
+            return match self.assemble_multipart(xid, payload) {
+                Ok(Some(reply)) => self.complete_request(xid, msg.to_int(), reply),
+                Ok(None) => true,
+                Err(error) => self.fail_request(xid, error),
+            };
+        }
+        self.complete_request(xid, msg.to_int(), payload)
+    }
+
+    /// Returns the role of the controller on this connection
+    ///
+    /// Connections start out as `ControllerRole::Equal`.
+    pub fn role(&self) -> ControllerRole {
+        self.role
+            .read()
+            .map(|state| state.role)
+            .unwrap_or(ControllerRole::Equal)
+    }
+
+    /// Returns the generation id last reported by the switch, if any
+    pub fn generation_id(&self) -> Option<u64> {
+        self.role.read().ok().and_then(|state| state.generation_id)
+    }
+
+    /// Returns the generation id to use for the next master or slave request
+    ///
+    /// This is the last known generation id plus one, or 0 if none is known yet.
+    pub fn next_generation_id(&self) -> u64 {
+        self.generation_id()
+            .map(|id| id.wrapping_add(1))
+            .unwrap_or(0)
+    }
+
+    /// Records the role reported by the switch in a role reply
+    ///
+    /// # Arguments
+    /// * `reply` - A role reply received on this connection
+    pub(crate) fn record_role(&self, reply: &RoleReplyEvent) {
+        if let Ok(mut state) = self.role.write() {
+            if reply.role != ControllerRole::NoChange {
+                state.role = reply.role;
+            }
+            if reply.role.uses_generation_id() {
+                state.generation_id = Some(reply.generation_id);
+            }
+        }
+    }
+
+    /// Refuses messages a slave controller is not allowed to send
+    ///
+    /// A table features multipart request only changes the switch when it carries
+    /// tables, so the body decides for that request.
+    ///
+    /// # Arguments
+    /// * `msg` - Type of the message about to be sent
+    /// * `body` - Body of the message, header excluded
+    fn check_role(&self, msg: &Msg, body: &[u8]) -> Result<(), Error> {
+        let modifies_state = match msg {
+            Msg::PacketOut
+            | Msg::FlowMod
+            | Msg::GroupMod
+            | Msg::PortMod
+            | Msg::TableMod
+            | Msg::MeterMod
+            | Msg::SetConfig => true,
+            Msg::MultipartRequest => {
+                body.len() > 8 && body[..2] == (MultipartType::TableFeatures as u16).to_be_bytes()
+            }
+            _ => false,
+        };
+        if modifies_state && self.role() == ControllerRole::Slave {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "a slave controller cannot change the state of the switch",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Refuses an invalid message or one a slave controller is not allowed to send
+    ///
+    /// # Arguments
+    /// * `msg` - The message about to be sent
+    fn check_msg<MSM: MessageMarshal>(&self, msg: &MSM) -> Result<(), Error> {
+        msg.validate()?;
+        let mut body = Vec::new();
+        if let Msg::MultipartRequest = msg.msg_code() {
+            msg.marshal(&mut body);
+        }
+        self.check_role(&msg.msg_code(), &body)
+    }
+
+    /// Fails every request still waiting on this connection with `RequestError::Disconnected`
+    pub fn close_requests(&self) {
+        self.requests.close();
+        self.multipart.clear();
+    }
+
+    /// Returns true while the connection to the switch is open
+    pub fn is_connected(&self) -> bool {
+        !self.sender.is_closed()
+    }
+
+    /// Queues already marshaled bytes for sending
+    ///
+    /// Fails with `ErrorKind::PermissionDenied` if the controller is a slave and the
+    /// message would change the state of the switch.
+    ///
+    /// # Arguments
+    /// * `bytes` - A complete OpenFlow message, header included
+    pub async fn send_raw(&self, bytes: Vec<u8>) -> Result<(), Error> {
+        let header_size = Openflow13::new().header_size();
+        if bytes.len() >= header_size {
+            self.check_role(&Msg::from(bytes[1]), &bytes[header_size..])?;
+        }
+        self.sender
+            .send(bytes)
+            .await
+            .map_err(|_| Error::new(ErrorKind::NotConnected, "switch connection is closed"))
+    }
+
+    /// Marshals a message with its OpenFlow header and queues it for sending
+    ///
+    /// Fails with `ErrorKind::PermissionDenied` if the controller is a slave and the
+    /// message would change the state of the switch, and with the error of
+    /// `MessageMarshal::validate` if the message is invalid.
+    ///
+    /// # Arguments
+    /// * `msg` - The message to send
+    /// * `xid` - Transaction ID
+    pub async fn send_msg<MSM: MessageMarshal>(&self, msg: MSM, xid: u32) -> Result<(), Error> {
+        msg.validate()?;
+        let ofp = Openflow13::new();
+        let mut header_bytes: Vec<u8> = Vec::new();
+        let mut body_bytes: Vec<u8> = Vec::new();
+
+        msg.marshal(&mut body_bytes);
+        let ofp_header = ofp.header(msg.msg_usize() as u8, body_bytes.len() as u16, xid);
+        ofp_header.marshal(&mut header_bytes);
+        header_bytes.append(&mut body_bytes);
+        self.send_raw(header_bytes).await
+    }
+
+    /// Sends a message and waits for the reply carrying the same xid
+    ///
+    /// The xid is allocated by the handle. A reply that completes the request is not
+    /// passed to the controller callbacks. Replies are picked up by the reader of the
+    /// connection, so a request may be awaited from within a callback.
+    ///
+    /// # Arguments
+    /// * `msg` - The request to send
+    /// * `wait` - How long to wait for the reply
+    ///
+    /// # Returns
+    /// The reply, or `RequestError::Switch` if the switch answered with an error message
+    pub async fn request<MSM: MessageMarshal>(
+        &self,
+        msg: MSM,
+        wait: Duration,
+    ) -> Result<Reply, RequestError> {
+        self.check_msg(&msg)?;
+        let xid = self.next_xid();
+        let receiver = self.requests.register(xid);
+        if self.send_msg(msg, xid).await.is_err() {
+            self.requests.cancel(xid);
+            return Err(RequestError::Disconnected);
+        }
+        let (message, payload) = match timeout(wait, receiver).await {
+            Ok(Ok(Ok(reply))) => reply,
+            Ok(Ok(Err(error))) => return Err(RequestError::Io(error)),
+            Ok(Err(_)) => return Err(RequestError::Disconnected),
+            Err(_) => {
+                self.requests.cancel(xid);
+                return Err(RequestError::Timeout);
+            }
+        };
+
+        match Msg::from(message) {
+            Msg::Error => Err(RequestError::Switch(ErrorEvent::parse(&payload)?)),
+            message => Ok(Reply {
+                message,
+                xid,
+                payload,
+            }),
+        }
+    }
+
+    /// Requests a controller role and waits for the reply
+    ///
+    /// The granted role and generation id are recorded on the handle. The switch
+    /// rejects master and slave requests with a stale generation id with a
+    /// `RoleRequestFailed` error.
+    ///
+    /// # Arguments
+    /// * `role` - Role to claim
+    /// * `generation_id` - Generation id of the election, see `next_generation_id`
+    /// * `wait` - How long to wait for the reply
+    pub async fn request_role(
+        &self,
+        role: ControllerRole,
+        generation_id: u64,
+        wait: Duration,
+    ) -> Result<RoleReplyEvent, RequestError> {
+        let reply = self
+            .request(RoleRequestEvent::new(role, generation_id), wait)
+            .await?;
+        let reply = RoleReplyEvent::parse(&reply.payload)?;
+        self.record_role(&reply);
+        Ok(reply)
+    }
+
+    /// Queries the current role and generation id without changing them
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn query_role(&self, wait: Duration) -> Result<RoleReplyEvent, RequestError> {
+        self.request_role(ControllerRole::NoChange, 0, wait).await
+    }
+
+    /// Reads the asynchronous message configuration of this connection
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn async_config(&self, wait: Duration) -> Result<AsyncConfig, RequestError> {
+        let reply = self.request(GetAsyncRequestEvent::new(), wait).await?;
+        Ok(AsyncConfig::parse(&reply.payload)?)
+    }
+
+    /// Requests the features of the switch and waits for the reply
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn features(&self, wait: Duration) -> Result<FeaturesReplyEvent, RequestError> {
+        let reply = self.request(Openflow13::new().fetures_req(), wait).await?;
+        Ok(FeaturesReplyEvent::parse(&reply.payload)?)
+    }
+
+    /// Reads the configuration of the switch
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn switch_config(&self, wait: Duration) -> Result<SwitchConfig, RequestError> {
+        let reply = self.request(GetConfigRequestEvent::new(), wait).await?;
+        Ok(SwitchConfig::parse(&reply.payload)?)
+    }
+
+    /// Sends a barrier request and waits for the barrier reply
+    ///
+    /// Once this returns, the switch has processed every message sent before the barrier.
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn barrier(&self, wait: Duration) -> Result<(), RequestError> {
+        self.request(BarrierRequestEvent::new(), wait).await?;
+        Ok(())
+    }
+
+    /// Sends a batch of messages followed by a barrier and waits until they are applied
+    ///
+    /// Every message is sent with its own xid. The call resolves once the barrier reply
+    /// arrives, or early as soon as the switch answers any message of the batch with an
+    /// error. Other replies to messages of the batch are dropped.
+    ///
+    /// Messages of different types can be sent together as `Box<dyn MessageMarshal + Send>`.
+    ///
+    /// # Arguments
+    /// * `msgs` - Messages to send, in order
+    /// * `wait` - How long to wait for the whole batch to be applied
+    ///
+    /// # Returns
+    /// `RequestError::Switch` with the first error the switch reported for the batch
+    pub async fn send_batch<MSM, I>(&self, msgs: I, wait: Duration) -> Result<(), RequestError>
+    where
+        MSM: MessageMarshal,
+        I: IntoIterator<Item = MSM>,
+    {
+        let msgs: Vec<(u32, MSM)> = msgs.into_iter().map(|msg| (self.next_xid(), msg)).collect();
+        for (_, msg) in &msgs {
+            self.check_msg(msg)?;
+        }
+        let barrier_xid = self.next_xid();
+        let mut xids: Vec<u32> = msgs.iter().map(|(xid, _)| *xid).collect();
+        xids.push(barrier_xid);
+        let mut replies = self.requests.register_batch(&xids);
+        let cancel = || xids.iter().for_each(|xid| self.requests.cancel(*xid));
+
+        for (xid, msg) in msgs {
+            if self.send_msg(msg, xid).await.is_err() {
+                cancel();
+                return Err(RequestError::Disconnected);
+            }
+        }
+        if self
+            .send_msg(BarrierRequestEvent::new(), barrier_xid)
+            .await
+            .is_err()
+        {
+            cancel();
+            return Err(RequestError::Disconnected);
+        }
+
+        let result = timeout(wait, async {
+            loop {
+                match replies.recv().await {
+                    Some((xid, _)) if xid == barrier_xid => return Ok(()),
+                    Some((_, Ok((message, payload)))) => {
+                        if let Msg::Error = Msg::from(message) {
+                            return Err(RequestError::Switch(ErrorEvent::parse(&payload)?));
+                        }
+                    }
+                    Some((_, Err(error))) => return Err(RequestError::Io(error)),
+                    None => return Err(RequestError::Disconnected),
+                }
+            }
+        })
+        .await;
+        cancel();
+        result.unwrap_or(Err(RequestError::Timeout))
+    }
+
+    /// Sends a multipart request and waits for the complete reply
+    ///
+    /// Replies split across several messages are joined before they are returned.
+    ///
+    /// # Arguments
+    /// * `msg` - The multipart request to send
+    /// * `wait` - How long to wait for the reply
+    pub async fn multipart<MSM: MessageMarshal>(
+        &self,
+        msg: MSM,
+        wait: Duration,
+    ) -> Result<MultipartReplyEvent, RequestError> {
+        let reply = self.request(msg, wait).await?;
+        let reply = MultipartReplyEvent::parse(&reply.payload)?;
+        self.record_multipart(&reply);
+        Ok(reply)
+    }
+
+    /// Stores switch and port descriptions in the registry entry of this switch
+    ///
+    /// Other multipart replies are ignored.
+    ///
+    /// # Arguments
+    /// * `reply` - A complete multipart reply received on this connection
+    pub(crate) fn record_multipart(&self, reply: &MultipartReplyEvent) {
+        let datapath_id = match self.datapath_id() {
+            Some(datapath_id) => datapath_id,
+            None => return,
+        };
+        match &reply.body {
+            MultipartReplyBody::Desc(desc) => {
+                self.registry.update(datapath_id, |dp| {
+                    dp.description = Some(desc.clone());
+                });
+            }
+            MultipartReplyBody::PortDesc(ports) => {
+                self.registry.update(datapath_id, |dp| {
+                    dp.ports = ports
+                        .iter()
+                        .map(|port| (port.port_no, port.clone()))
+                        .collect();
+                });
+            }
+            _ => (),
+        }
+    }
+
+    /// Applies a port status message to the registry entry of this switch
+    ///
+    /// # Arguments
+    /// * `status` - A port status message received on this connection
+    pub(crate) fn record_port_status(&self, status: &PortStatusEvent) {
+        if let Some(datapath_id) = self.datapath_id() {
+            self.registry
+                .update(datapath_id, |dp| dp.apply_port_status(status));
+        }
+    }
+
+    /// Requests the description of the switch and waits for the reply
+    ///
+    /// The description is also stored in the registry entry of the switch.
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn description(&self, wait: Duration) -> Result<SwitchDescription, RequestError> {
+        match self.multipart(DescRequestEvent::new(), wait).await?.body {
+            MultipartReplyBody::Desc(desc) => Ok(desc),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests the description of every port and waits for the reply
+    ///
+    /// The ports also replace the known ports in the registry entry of the switch.
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn port_desc(&self, wait: Duration) -> Result<Vec<Port>, RequestError> {
+        match self
+            .multipart(PortDescRequestEvent::new(), wait)
+            .await?
+            .body
+        {
+            MultipartReplyBody::PortDesc(ports) => Ok(ports),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests individual flow statistics and waits for the reply
+    ///
+    /// # Arguments
+    /// * `request` - Filters selecting the flows to report
+    /// * `wait` - How long to wait for the reply
+    pub async fn flow_stats(
+        &self,
+        request: FlowStatsRequestEvent,
+        wait: Duration,
+    ) -> Result<Vec<FlowStats>, RequestError> {
+        match self.multipart(request, wait).await?.body {
+            MultipartReplyBody::Flow(stats) => Ok(stats),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests aggregate flow statistics and waits for the reply
+    ///
+    /// # Arguments
+    /// * `request` - Filters selecting the flows to sum up
+    /// * `wait` - How long to wait for the reply
+    pub async fn aggregate_stats(
+        &self,
+        request: AggregateStatsRequestEvent,
+        wait: Duration,
+    ) -> Result<AggregateStats, RequestError> {
+        match self.multipart(request, wait).await?.body {
+            MultipartReplyBody::Aggregate(stats) => Ok(stats),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests the statistics of every flow table and waits for the reply
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn table_stats(&self, wait: Duration) -> Result<Vec<TableStats>, RequestError> {
+        match self
+            .multipart(TableStatsRequestEvent::new(), wait)
+            .await?
+            .body
+        {
+            MultipartReplyBody::Table(stats) => Ok(stats),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests port statistics and waits for the reply
+    ///
+    /// # Arguments
+    /// * `request` - Port selection
+    /// * `wait` - How long to wait for the reply
+    pub async fn port_stats(
+        &self,
+        request: PortStatsRequestEvent,
+        wait: Duration,
+    ) -> Result<Vec<PortStats>, RequestError> {
+        match self.multipart(request, wait).await?.body {
+            MultipartReplyBody::PortStats(stats) => Ok(stats),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests queue statistics and waits for the reply
+    ///
+    /// # Arguments
+    /// * `request` - Port and queue selection
+    /// * `wait` - How long to wait for the reply
+    pub async fn queue_stats(
+        &self,
+        request: QueueStatsRequestEvent,
+        wait: Duration,
+    ) -> Result<Vec<QueueStats>, RequestError> {
+        match self.multipart(request, wait).await?.body {
+            MultipartReplyBody::Queue(stats) => Ok(stats),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests group statistics and waits for the reply
+    ///
+    /// # Arguments
+    /// * `request` - Group selection
+    /// * `wait` - How long to wait for the reply
+    pub async fn group_stats(
+        &self,
+        request: GroupStatsRequestEvent,
+        wait: Duration,
+    ) -> Result<Vec<GroupStats>, RequestError> {
+        match self.multipart(request, wait).await?.body {
+            MultipartReplyBody::Group(stats) => Ok(stats),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests the description of every group and waits for the reply
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn group_desc(&self, wait: Duration) -> Result<Vec<GroupDesc>, RequestError> {
+        match self
+            .multipart(GroupDescRequestEvent::new(), wait)
+            .await?
+            .body
+        {
+            MultipartReplyBody::GroupDesc(groups) => Ok(groups),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests the group features of the switch and waits for the reply
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn group_features(&self, wait: Duration) -> Result<GroupFeatures, RequestError> {
+        match self
+            .multipart(GroupFeaturesRequestEvent::new(), wait)
+            .await?
+            .body
+        {
+            MultipartReplyBody::GroupFeatures(features) => Ok(features),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests meter statistics and waits for the reply
+    ///
+    /// # Arguments
+    /// * `request` - Meter selection
+    /// * `wait` - How long to wait for the reply
+    pub async fn meter_stats(
+        &self,
+        request: MeterStatsRequestEvent,
+        wait: Duration,
+    ) -> Result<Vec<MeterStats>, RequestError> {
+        match self.multipart(request, wait).await?.body {
+            MultipartReplyBody::Meter(stats) => Ok(stats),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests meter configurations and waits for the reply
+    ///
+    /// # Arguments
+    /// * `request` - Meter selection
+    /// * `wait` - How long to wait for the reply
+    pub async fn meter_config(
+        &self,
+        request: MeterConfigRequestEvent,
+        wait: Duration,
+    ) -> Result<Vec<MeterConfig>, RequestError> {
+        match self.multipart(request, wait).await?.body {
+            MultipartReplyBody::MeterConfig(meters) => Ok(meters),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests the meter features of the switch and waits for the reply
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn meter_features(&self, wait: Duration) -> Result<MeterFeatures, RequestError> {
+        match self
+            .multipart(MeterFeaturesRequestEvent::new(), wait)
+            .await?
+            .body
+        {
+            MultipartReplyBody::MeterFeatures(features) => Ok(features),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+
+    /// Requests the features of every flow table and waits for the reply
+    ///
+    /// # Arguments
+    /// * `wait` - How long to wait for the reply
+    pub async fn table_features(&self, wait: Duration) -> Result<Vec<TableFeatures>, RequestError> {
+        self.configure_tables(TableFeaturesRequestEvent::new(), wait)
+            .await
+    }
+
+    /// Configures the flow tables of the switch and waits for the resulting features
+    ///
+    /// Tables missing from the list are removed from the pipeline. Fails with
+    /// `ErrorKind::PermissionDenied` while the controller is a slave.
+    ///
+    /// # Arguments
+    /// * `tables` - Desired features of every table
+    /// * `wait` - How long to wait for the reply
+    pub async fn set_table_features(
+        &self,
+        tables: Vec<TableFeatures>,
+        wait: Duration,
+    ) -> Result<Vec<TableFeatures>, RequestError> {
+        self.configure_tables(TableFeaturesRequestEvent::set(tables), wait)
+            .await
+    }
+
+    /// Sends a table features request and returns the tables of the reply
+    async fn configure_tables(
+        &self,
+        request: TableFeaturesRequestEvent,
+        wait: Duration,
+    ) -> Result<Vec<TableFeatures>, RequestError> {
+        match self.multipart(request, wait).await?.body {
+            MultipartReplyBody::TableFeatures(tables) => Ok(tables),
+            _ => Err(unexpected_multipart()),
+        }
+    }
+}
+
+/// Error returned when a multipart reply does not match the type of its request
+fn unexpected_multipart() -> RequestError {
+    RequestError::Io(Error::new(
+        ErrorKind::InvalidData,
+        "unexpected multipart reply type",
+    ))
+}
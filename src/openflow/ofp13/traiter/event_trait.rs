@@ -7,7 +7,16 @@
 use std::io::Error;
 
 use crate::openflow::ofp13::{
-    events::{Action, FeaturesReqEvent, HelloEvent, PacketOutEvent, Payload},
+    events::{
+        Action, AggregateStatsRequestEvent, AsyncConfig, BarrierRequestEvent, ControllerRole,
+        DescRequestEvent, ExperimenterEvent, FeaturesReqEvent, FlowStatsRequestEvent,
+        GetAsyncRequestEvent, GetConfigRequestEvent, GroupDescRequestEvent,
+        GroupFeaturesRequestEvent, GroupStatsRequestEvent, HelloEvent, MatchFields,
+        MeterConfigRequestEvent, MeterFeaturesRequestEvent, MeterStatsRequestEvent, PacketOutEvent,
+        Payload, PortDescRequestEvent, PortStatsRequestEvent, QueueStatsRequestEvent,
+        RoleRequestEvent, SetAsyncEvent, SetConfigEvent, SwitchConfig, TableFeaturesRequestEvent,
+        TableModEvent, TableStatsRequestEvent,
+    },
     ofp_header::OfpHeader,
     Msg,
 };
@@ -40,6 +49,40 @@ pub trait MessageMarshal {
     /// # Returns
     /// * `usize` - The message size in bytes
     fn size_of(&self) -> usize;
+
+    /// Checks the message before it is sent
+    ///
+    /// `SwitchHandle` refuses messages failing this check. Most messages cannot be
+    /// invalid once built, so the default accepts them.
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Boxed messages marshal like the message they hold
+///
+/// This allows sending messages of different types together, e.g. as a batch of
+/// `Box<dyn MessageMarshal + Send>`.
+impl<T: MessageMarshal + ?Sized> MessageMarshal for Box<T> {
+    fn marshal(&self, bytes: &mut Vec<u8>) {
+        (**self).marshal(bytes)
+    }
+
+    fn msg_code(&self) -> Msg {
+        (**self).msg_code()
+    }
+
+    fn msg_usize(&self) -> usize {
+        (**self).msg_usize()
+    }
+
+    fn size_of(&self) -> usize {
+        (**self).size_of()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        (**self).validate()
+    }
 }
 
 /// Trait for working with OpenFlow message events
@@ -115,6 +158,73 @@ pub trait OfpMsgEvent {
     /// * `FeaturesReqEvent` - The new features request event
     fn fetures_req(&self) -> FeaturesReqEvent;
 
+    /// Creates a new barrier request event
+    ///
+    /// # Returns
+    /// * `BarrierRequestEvent` - The new barrier request event
+    fn barrier_req(&self) -> BarrierRequestEvent;
+
+    /// Creates a new get config request event
+    ///
+    /// # Returns
+    /// * `GetConfigRequestEvent` - The new get config request event
+    fn get_config_req(&self) -> GetConfigRequestEvent;
+
+    /// Creates a new set config event
+    ///
+    /// # Arguments
+    /// * `config` - Switch configuration to apply
+    ///
+    /// # Returns
+    /// * `SetConfigEvent` - The new set config event
+    fn set_config(&self, config: SwitchConfig) -> SetConfigEvent;
+
+    /// Creates a new table modification event
+    ///
+    /// # Arguments
+    /// * `table_id` - ID of the table to configure
+    /// * `config` - Configuration flags of the table
+    ///
+    /// # Returns
+    /// * `TableModEvent` - The new table modification event
+    fn table_mod(&self, table_id: u8, config: u32) -> TableModEvent;
+
+    /// Creates a new experimenter message
+    ///
+    /// # Arguments
+    /// * `experimenter` - Experimenter id
+    /// * `exp_type` - Experimenter defined message type
+    /// * `data` - Experimenter defined body
+    ///
+    /// # Returns
+    /// * `ExperimenterEvent` - The new experimenter message
+    fn experimenter(&self, experimenter: u32, exp_type: u32, data: Vec<u8>) -> ExperimenterEvent;
+
+    /// Creates a new role request event
+    ///
+    /// # Arguments
+    /// * `role` - Role to claim
+    /// * `generation_id` - Generation id of the election
+    ///
+    /// # Returns
+    /// * `RoleRequestEvent` - The new role request event
+    fn role_req(&self, role: ControllerRole, generation_id: u64) -> RoleRequestEvent;
+
+    /// Creates a new get async request event
+    ///
+    /// # Returns
+    /// * `GetAsyncRequestEvent` - The new get async request event
+    fn get_async_req(&self) -> GetAsyncRequestEvent;
+
+    /// Creates a new set async event
+    ///
+    /// # Arguments
+    /// * `config` - Asynchronous message configuration to apply
+    ///
+    /// # Returns
+    /// * `SetAsyncEvent` - The new set async event
+    fn set_async(&self, config: AsyncConfig) -> SetAsyncEvent;
+
     /// Creates a new packet out event
     ///
     /// # Arguments
@@ -130,4 +240,120 @@ pub trait OfpMsgEvent {
         payload: Payload,
         actions: Vec<Action>,
     ) -> PacketOutEvent;
+
+    /// Creates a new flow statistics request
+    ///
+    /// # Arguments
+    /// * `table_id` - ID of the table to read, or `TABLE_ALL`
+    /// * `match_fields` - Fields to match
+    ///
+    /// # Returns
+    /// * `FlowStatsRequestEvent` - The new flow statistics request
+    fn flow_stats_req(&self, table_id: u8, match_fields: MatchFields) -> FlowStatsRequestEvent;
+
+    /// Creates a new aggregate flow statistics request
+    ///
+    /// # Arguments
+    /// * `table_id` - ID of the table to read, or `TABLE_ALL`
+    /// * `match_fields` - Fields to match
+    ///
+    /// # Returns
+    /// * `AggregateStatsRequestEvent` - The new aggregate statistics request
+    fn aggregate_stats_req(
+        &self,
+        table_id: u8,
+        match_fields: MatchFields,
+    ) -> AggregateStatsRequestEvent;
+
+    /// Creates a new flow table statistics request
+    ///
+    /// # Returns
+    /// * `TableStatsRequestEvent` - The new table statistics request
+    fn table_stats_req(&self) -> TableStatsRequestEvent;
+
+    /// Creates a new port statistics request
+    ///
+    /// # Arguments
+    /// * `port_no` - Port to read, or `None` for every port
+    ///
+    /// # Returns
+    /// * `PortStatsRequestEvent` - The new port statistics request
+    fn port_stats_req(&self, port_no: Option<u32>) -> PortStatsRequestEvent;
+
+    /// Creates a new queue statistics request
+    ///
+    /// # Arguments
+    /// * `port_no` - Port to read, or `None` for every port
+    /// * `queue_id` - Queue to read, or `None` for every queue
+    ///
+    /// # Returns
+    /// * `QueueStatsRequestEvent` - The new queue statistics request
+    fn queue_stats_req(
+        &self,
+        port_no: Option<u32>,
+        queue_id: Option<u32>,
+    ) -> QueueStatsRequestEvent;
+
+    /// Creates a new switch description request
+    ///
+    /// # Returns
+    /// * `DescRequestEvent` - The new switch description request
+    fn desc_req(&self) -> DescRequestEvent;
+
+    /// Creates a new port description request
+    ///
+    /// # Returns
+    /// * `PortDescRequestEvent` - The new port description request
+    fn port_desc_req(&self) -> PortDescRequestEvent;
+
+    /// Creates a new group statistics request
+    ///
+    /// # Arguments
+    /// * `group_id` - Group to read, or `None` for every group
+    ///
+    /// # Returns
+    /// * `GroupStatsRequestEvent` - The new group statistics request
+    fn group_stats_req(&self, group_id: Option<u32>) -> GroupStatsRequestEvent;
+
+    /// Creates a new group description request
+    ///
+    /// # Returns
+    /// * `GroupDescRequestEvent` - The new group description request
+    fn group_desc_req(&self) -> GroupDescRequestEvent;
+
+    /// Creates a new group features request
+    ///
+    /// # Returns
+    /// * `GroupFeaturesRequestEvent` - The new group features request
+    fn group_features_req(&self) -> GroupFeaturesRequestEvent;
+
+    /// Creates a new meter statistics request
+    ///
+    /// # Arguments
+    /// * `meter_id` - Meter to read, or `None` for every meter
+    ///
+    /// # Returns
+    /// * `MeterStatsRequestEvent` - The new meter statistics request
+    fn meter_stats_req(&self, meter_id: Option<u32>) -> MeterStatsRequestEvent;
+
+    /// Creates a new meter configuration request
+    ///
+    /// # Arguments
+    /// * `meter_id` - Meter to read, or `None` for every meter
+    ///
+    /// # Returns
+    /// * `MeterConfigRequestEvent` - The new meter configuration request
+    fn meter_config_req(&self, meter_id: Option<u32>) -> MeterConfigRequestEvent;
+
+    /// Creates a new meter features request
+    ///
+    /// # Returns
+    /// * `MeterFeaturesRequestEvent` - The new meter features request
+    fn meter_features_req(&self) -> MeterFeaturesRequestEvent;
+
+    /// Creates a new table features request
+    ///
+    /// # Returns
+    /// * `TableFeaturesRequestEvent` - The new table features request
+    fn table_features_req(&self) -> TableFeaturesRequestEvent;
 }
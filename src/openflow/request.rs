@@ -0,0 +1,219 @@
+//! OpenFlow Request/Reply Correlation
+//!
+//! This module implements the transaction id allocator and the table of pending
+//! requests shared by the OpenFlow 1.0 and 1.3 stacks. A request registers its xid
+//! before it is sent; when a message with the same xid comes back, the listener
+//! hands it to the waiting task instead of the regular controller callbacks.
+//!
+//! Replies are stored as the raw message type and body, so the table does not
+//! depend on the protocol version. Decoding is left to the version's `SwitchHandle`.
+//!
+//! A request can also be failed with an error found while receiving its reply, e.g.
+//! a multipart reply whose parts cannot be joined.
+//!
+//! Several xids can also share one channel. This is used to wait for a batch of
+//! messages closed by a barrier, where any of them may be answered by an error.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Display},
+    io::Error,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tokio::sync::{mpsc, oneshot};
+
+/// Transaction ids handed out by the allocator start here.
+///
+/// Messages sent through the controller callbacks use caller chosen xids, usually
+/// the xid of the message being answered or 0, so the allocator starts well above
+/// the values picked by hand.
+const FIRST_XID: u32 = 0x1000;
+
+/// Message type and body of a reply, header excluded
+pub type RawReply = (u8, Vec<u8>);
+
+/// Outcome delivered to a waiting request: its reply or the error that ended it
+pub type Delivery = Result<RawReply, Error>;
+
+/// Where the reply for a registered xid is delivered
+enum Waiter {
+    /// A single request waiting for its own reply
+    Single(oneshot::Sender<Delivery>),
+    /// A batch sharing one channel; replies are tagged with their xid
+    Batch(mpsc::UnboundedSender<(u32, Delivery)>),
+}
+
+/// Allocator for transaction ids and table of requests awaiting a reply
+///
+/// Cloning is cheap and every clone refers to the same table.
+#[derive(Clone)]
+pub struct PendingRequests {
+    /// Next transaction id to hand out
+    next_xid: Arc<AtomicU32>,
+    /// Waiting requests keyed by transaction id
+    pending: Arc<Mutex<HashMap<u32, Waiter>>>,
+}
+
+impl PendingRequests {
+    /// Creates a new, empty table
+    pub fn new() -> Self {
+        Self {
+            next_xid: Arc::new(AtomicU32::new(FIRST_XID)),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns a fresh transaction id
+    ///
+    /// Ids wrap around after `u32::MAX` and skip the range reserved for hand picked xids.
+    pub fn next_xid(&self) -> u32 {
+        loop {
+            let xid = self.next_xid.fetch_add(1, Ordering::Relaxed);
+            if xid >= FIRST_XID {
+                return xid;
+            }
+        }
+    }
+
+    /// Registers a request and returns the receiver its reply will be delivered to
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction id of the request
+    pub fn register(&self, xid: u32) -> oneshot::Receiver<Delivery> {
+        let (sender, receiver) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(xid, Waiter::Single(sender));
+        }
+        receiver
+    }
+
+    /// Registers a batch of requests whose replies share one channel
+    ///
+    /// Each reply is delivered together with its xid. The channel closes once the
+    /// table is closed or every xid has been answered or cancelled.
+    ///
+    /// # Arguments
+    /// * `xids` - Transaction ids of the requests
+    pub fn register_batch(&self, xids: &[u32]) -> mpsc::UnboundedReceiver<(u32, Delivery)> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            for xid in xids {
+                pending.insert(*xid, Waiter::Batch(sender.clone()));
+            }
+        }
+        receiver
+    }
+
+    /// Delivers a message to the request waiting for its xid
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction id of the received message
+    /// * `message` - Type of the received message
+    /// * `payload` - Body of the received message, header excluded
+    ///
+    /// # Returns
+    /// True if a request was waiting for the message
+    pub fn complete(&self, xid: u32, message: u8, payload: Vec<u8>) -> bool {
+        self.deliver(xid, Ok((message, payload)))
+    }
+
+    /// Ends the request waiting for an xid with an error
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction id of the request
+    /// * `error` - Why the reply could not be received
+    ///
+    /// # Returns
+    /// True if a request was waiting for the xid
+    pub fn fail(&self, xid: u32, error: Error) -> bool {
+        self.deliver(xid, Err(error))
+    }
+
+    /// Hands an outcome to the request waiting for its xid
+    fn deliver(&self, xid: u32, delivery: Delivery) -> bool {
+        let sender = match self.pending.lock() {
+            Ok(mut pending) => pending.remove(&xid),
+            Err(_) => None,
+        };
+        match sender {
+            Some(Waiter::Single(sender)) => {
+                let _ = sender.send(delivery);
+                true
+            }
+            Some(Waiter::Batch(sender)) => {
+                let _ = sender.send((xid, delivery));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns true if a request is waiting for the given xid
+    pub fn is_pending(&self, xid: u32) -> bool {
+        self.pending
+            .lock()
+            .map(|pending| pending.contains_key(&xid))
+            .unwrap_or(false)
+    }
+
+    /// Forgets a request, e.g. after it timed out
+    ///
+    /// # Arguments
+    /// * `xid` - Transaction id of the request
+    pub fn cancel(&self, xid: u32) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&xid);
+        }
+    }
+
+    /// Drops every waiting request; their callers observe a disconnect
+    pub fn close(&self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.clear();
+        }
+    }
+}
+
+impl Default for PendingRequests {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reasons an awaited request can fail
+///
+/// `E` is the error message type of the protocol version in use.
+#[derive(Debug)]
+pub enum RequestError<E> {
+    /// No reply arrived within the given time
+    Timeout,
+    /// The connection closed before the reply arrived
+    Disconnected,
+    /// The switch answered with an error message
+    Switch(E),
+    /// The reply could not be decoded
+    Io(Error),
+}
+
+impl<E: Debug> Display for RequestError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "request timed out"),
+            Self::Disconnected => write!(f, "switch connection is closed"),
+            Self::Switch(error) => write!(f, "switch replied with error {:?}", error),
+            Self::Io(error) => write!(f, "cannot decode reply: {}", error),
+        }
+    }
+}
+
+impl<E: Debug> std::error::Error for RequestError<E> {}
+
+impl<E> From<Error> for RequestError<E> {
+    fn from(value: Error) -> Self {
+        Self::Io(value)
+    }
+}
@@ -0,0 +1,38 @@
+//! Outgoing Message Queue
+//!
+//! Each switch connection has a single writer task that owns the write half of
+//! the socket. Everything that wants to talk to the switch pushes complete,
+//! already marshaled messages into the task's channel, which keeps writes from
+//! different tasks from interleaving on the wire.
+
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::mpsc::{channel, Sender},
+    task::JoinHandle,
+};
+
+/// Number of messages that can wait in a connection's send queue
+pub const SEND_QUEUE_SIZE: usize = 1024;
+
+/// Spawns the writer task for a connection
+///
+/// # Arguments
+/// * `writer` - The write half of the connection
+///
+/// # Returns
+/// The sender feeding the task and the task's join handle, which the connection
+/// aborts once the switch disconnects
+pub fn spawn_writer<W>(mut writer: W) -> (Sender<Vec<u8>>, JoinHandle<()>)
+where
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let (sender, mut receiver) = channel::<Vec<u8>>(SEND_QUEUE_SIZE);
+    let task = tokio::spawn(async move {
+        while let Some(bytes) = receiver.recv().await {
+            if writer.write_all(&bytes).await.is_err() {
+                break;
+            }
+        }
+    });
+    (sender, task)
+}
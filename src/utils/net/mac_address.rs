@@ -4,7 +4,7 @@ use byteorder::WriteBytesExt;
 ///
 /// This struct provides functionality for creating, converting, and manipulating MAC addresses.
 /// It stores the MAC address as a 6-byte array and provides various conversion methods.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct MacAddr {
     mac: [u8; 6],
 }
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Cursor,
+        net::{Ipv4Addr, Ipv6Addr},
+    };
+
+    use tenjin_sdn::{
+        openflow::ofp13::{Action, ExperimenterAction, MatchFields, PseudoPort, SetField},
+        utils::MacAddr,
+    };
+
+    fn round_trip(actions: Vec<Action>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for action in &actions {
+            action.marshal(&mut bytes).unwrap();
+        }
+        assert_eq!(bytes.len() % 8, 0);
+        let len = bytes.len();
+        let parsed = Action::parse_list(&mut Cursor::new(bytes.clone()), len).unwrap();
+        assert_eq!(parsed, actions);
+        bytes
+    }
+
+    #[test]
+    fn test_actions_round_trip_v1_3() {
+        round_trip(vec![
+            Action::output(PseudoPort::PhysicalPort(3)),
+            Action::output(PseudoPort::Controller(128)),
+            Action::output(PseudoPort::Local),
+            Action::Oputput(PseudoPort::PhysicalPort(4), 128),
+            Action::Oputput(PseudoPort::Controller(0xffe5), 0xffe5),
+            Action::CopyTtlOut,
+            Action::CopyTtlIn,
+            Action::SetMplsTtl(64),
+            Action::DecMplsTtl,
+            Action::PushVlan(0x8100),
+            Action::PushMpls(0x8847),
+            Action::PushPbb(0x88e7),
+            Action::PopVlan,
+            Action::PopMpls(0x0800),
+            Action::PopPbb,
+            Action::SetQueue(7),
+            Action::Group(9),
+            Action::SetNwTtl(32),
+            Action::DecNwTtl,
+            Action::Experimenter(ExperimenterAction::new(
+                0x2320,
+                vec![0, 1, 2, 3, 4, 5, 6, 7],
+            )),
+        ]);
+    }
+
+    #[test]
+    fn test_set_field_round_trip_v1_3() {
+        let mac = MacAddr::from([0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        round_trip(vec![
+            Action::SetField(SetField::InPort(PseudoPort::PhysicalPort(1))),
+            Action::SetField(SetField::Metadata(0xdead_beef)),
+            Action::SetField(SetField::EthDst(mac)),
+            Action::SetField(SetField::EthTyp(0x0800)),
+            Action::SetField(SetField::VlanVid(MatchFields::VLAN_PRESENT | 10)),
+            Action::SetField(SetField::VlanPcp(3)),
+            Action::SetField(SetField::IpDscp(46)),
+            Action::SetField(SetField::IpEcn(1)),
+            Action::SetField(SetField::Ipv4Src(Ipv4Addr::new(10, 0, 0, 1))),
+            Action::SetField(SetField::SctpDst(2905)),
+            Action::SetField(SetField::Icmpv4Type(8)),
+            Action::SetField(SetField::ArpOp(2)),
+            Action::SetField(SetField::ArpTpa(Ipv4Addr::new(10, 0, 0, 2))),
+            Action::SetField(SetField::ArpSha(mac)),
+            Action::SetField(SetField::Ipv6Dst(Ipv6Addr::LOCALHOST)),
+            Action::SetField(SetField::Ipv6Flabel(0x12345)),
+            Action::SetField(SetField::Icmpv6Code(0)),
+            Action::SetField(SetField::Ipv6NdTarget(Ipv6Addr::LOCALHOST)),
+            Action::SetField(SetField::Ipv6NdTll(mac)),
+            Action::SetField(SetField::MplsLabel(100)),
+            Action::SetField(SetField::MplsTc(5)),
+            Action::SetField(SetField::MplsBos(1)),
+            Action::SetField(SetField::PbbIsid(0xabcdef)),
+            Action::SetField(SetField::TunnelId(42)),
+        ]);
+    }
+
+    #[test]
+    fn test_set_field_padding_v1_3() {
+        // 4 byte action header + 4 byte oxm header + 2 byte value, padded to 16
+        let bytes = round_trip(vec![Action::SetField(SetField::VlanVid(
+            MatchFields::VLAN_PRESENT | 5,
+        ))]);
+        assert_eq!(
+            bytes,
+            vec![0x00, 0x19, 0x00, 0x10, 0x80, 0x00, 0x0c, 0x02, 0x10, 0x05, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_action_invalid_length_v1_3() {
+        // a pop-vlan action claiming 16 bytes
+        let bytes = vec![0x00, 0x12, 0x00, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(Action::parse(&mut Cursor::new(bytes)).is_err());
+        // a masked set-field
+        let bytes = vec![
+            0x00, 0x19, 0x00, 0x10, 0x80, 0x00, 0x0d, 0x04, 0x10, 0x05, 0xff, 0xff,
+        ];
+        let mut padded = bytes.clone();
+        padded.resize(16, 0);
+        assert!(Action::parse(&mut Cursor::new(padded)).is_err());
+        // a set-field whose value is too short for the field
+        let bytes = vec![0x00, 0x19, 0x00, 0x08, 0x80, 0x00, 0x16, 0x00];
+        assert!(Action::parse(&mut Cursor::new(bytes)).is_err());
+    }
+}
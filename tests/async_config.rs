@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use tenjin_sdn::openflow::ofp13::{
+        events::{AsyncMasks, PacketInReasons},
+        AsyncConfig, MessageMarshal, OfpMsgEvent, Openflow13,
+    };
+
+    #[test]
+    fn test_set_async_marshal_and_parse() {
+        let mut config = AsyncConfig::default();
+        assert!(config.master.packet_in.no_match && !config.master.packet_in.invalid_ttl);
+        assert!(config.slave.port_status.modify && !config.slave.flow_removed.delete);
+
+        // table-miss only for the master, nothing at all for slaves
+        config.master.packet_in = PacketInReasons {
+            no_match: true,
+            ..Default::default()
+        };
+        config.slave = AsyncMasks::default();
+
+        let set_async = Openflow13::new().set_async(config.clone());
+        let mut bytes = Vec::new();
+        set_async.marshal(&mut bytes);
+        assert_eq!(bytes.len(), set_async.size_of());
+        assert_eq!(
+            bytes,
+            vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0, 0xf, 0, 0, 0, 0]
+        );
+        assert_eq!(AsyncConfig::parse(&bytes).unwrap(), config);
+    }
+}
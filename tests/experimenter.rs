@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        any::Any,
+        io::{Cursor, Error, ErrorKind},
+    };
+
+    use tenjin_sdn::openflow::{
+        ofp10,
+        ofp13::{
+            Action, ExperimenterAction, ExperimenterEvent, MatchFields, MessageMarshal,
+            OxmExtension,
+        },
+        ExperimenterCodec, ExperimenterRegistry, ExperimenterValue,
+    };
+
+    const EXPERIMENTER: u32 = 0x00ab_cdef;
+
+    /// Action of the test experimenter: a single counter
+    #[derive(Debug, PartialEq)]
+    struct Count(u16);
+
+    struct TestCodec;
+
+    impl ExperimenterCodec for TestCodec {
+        fn experimenter(&self) -> u32 {
+            EXPERIMENTER
+        }
+
+        fn decode_action(&self, data: &[u8]) -> Result<ExperimenterValue, Error> {
+            match data {
+                [hi, lo, ..] => Ok(Box::new(Count(u16::from_be_bytes([*hi, *lo])))),
+                _ => Err(Error::new(ErrorKind::InvalidData, "count too short")),
+            }
+        }
+
+        fn encode_action(&self, value: &dyn Any) -> Result<Vec<u8>, Error> {
+            let count = value
+                .downcast_ref::<Count>()
+                .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "not a count"))?;
+            Ok(count.0.to_be_bytes().to_vec())
+        }
+
+        fn decode_oxm(&self, oxm: &OxmExtension) -> Result<ExperimenterValue, Error> {
+            Ok(Box::new(oxm.data().to_vec()))
+        }
+    }
+
+    #[test]
+    fn test_experimenter_action_codec() {
+        let mut registry = ExperimenterRegistry::new();
+        registry.register(TestCodec);
+
+        let data = registry.encode_action(EXPERIMENTER, &Count(7)).unwrap();
+        let action = Action::Experimenter(ExperimenterAction::new(EXPERIMENTER, data));
+        let mut bytes = Vec::new();
+        action.marshal(&mut bytes).unwrap();
+        assert_eq!(
+            bytes,
+            vec![0xff, 0xff, 0, 16, 0, 0xab, 0xcd, 0xef, 0, 7, 0, 0, 0, 0, 0, 0]
+        );
+
+        let parsed = match Action::parse(&mut Cursor::new(bytes)).unwrap() {
+            Action::Experimenter(action) => action,
+            action => panic!("unexpected action {:?}", action),
+        };
+        let value = registry
+            .decode_action(parsed.experimenter, &parsed.data)
+            .unwrap()
+            .unwrap();
+        assert_eq!(value.downcast_ref::<Count>(), Some(&Count(7)));
+
+        // unknown experimenters stay raw
+        assert!(registry
+            .decode_action(0x2320, &parsed.data)
+            .unwrap()
+            .is_none());
+        assert!(registry.encode_action(0x2320, &Count(7)).is_err());
+        // kinds the codec does not define are rejected
+        assert!(registry.decode_message(EXPERIMENTER, 0, &[]).is_err());
+    }
+
+    #[test]
+    fn test_experimenter_oxm_round_trip() {
+        let mut registry = ExperimenterRegistry::new();
+        registry.register(TestCodec);
+
+        let mut fields = MatchFields::match_all();
+        fields.in_port = Some(1);
+        fields.extensions.push(OxmExtension::experimenter_field(
+            EXPERIMENTER,
+            3,
+            false,
+            &[1, 2],
+        ));
+        let mut bytes = Vec::new();
+        fields.marshal(&mut bytes).unwrap();
+        let parsed = MatchFields::parse(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(parsed, fields);
+
+        let oxm = &parsed.extensions[0];
+        assert_eq!(oxm.experimenter(), Some(EXPERIMENTER));
+        let value = registry.decode_oxm(oxm).unwrap().unwrap();
+        assert_eq!(value.downcast_ref::<Vec<u8>>(), Some(&vec![1, 2]));
+    }
+
+    #[test]
+    fn test_experimenter_message_round_trip() {
+        let message = ExperimenterEvent::new(EXPERIMENTER, 5, vec![9, 8, 7]);
+        let mut bytes = Vec::new();
+        message.marshal(&mut bytes);
+        assert_eq!(bytes.len(), message.size_of());
+        assert_eq!(ExperimenterEvent::parse(&bytes).unwrap(), message);
+
+        let vendor = ofp10::VendorEvent::new(0x2320, vec![0, 0, 0, 11, 1]);
+        let mut bytes = Vec::new();
+        ofp10::MessageMarshal::marshal(&vendor, &mut bytes);
+        let parsed = ofp10::VendorEvent::parse(&bytes).unwrap();
+        assert_eq!(parsed, vendor);
+        assert_eq!(parsed.split_type(), Some((11, &[1u8][..])));
+    }
+
+    #[test]
+    fn test_vendor_action_v1_0() {
+        let actions = vec![
+            ofp10::Action::Vendor(0x2320, vec![0, 1, 0, 0, 0, 0, 0, 0]),
+            ofp10::Action::SetTos(4),
+        ];
+        let mut bytes = Vec::new();
+        for action in &actions {
+            action.marshal(&mut bytes);
+        }
+        assert_eq!(bytes.len(), 24);
+        let parsed = ofp10::Action::parse_sequence(&mut Cursor::new(bytes));
+        match &parsed[..] {
+            [ofp10::Action::Vendor(0x2320, data), ofp10::Action::SetTos(4)] => {
+                assert_eq!(data, &vec![0, 1, 0, 0, 0, 0, 0, 0])
+            }
+            actions => panic!("unexpected actions {:?}", actions),
+        }
+    }
+}
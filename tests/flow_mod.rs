@@ -0,0 +1,234 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Cursor, ErrorKind},
+        net::{Ipv4Addr, Ipv6Addr},
+    };
+
+    use tenjin_sdn::{
+        openflow::{
+            ofp10::{self, MessageMarshal as _},
+            ofp13::{
+                events::{FlowModFlags, FlowRemovedReason, MatchMasks, Timeout},
+                Action, FlowModEvent, FlowRemovedEvent, MatchFields, MessageMarshal, OxmExtension,
+                PseudoPort,
+            },
+        },
+        utils::MacAddr,
+    };
+
+    #[test]
+    fn test_flow_mod_commands_v1_3() {
+        let mut match_fields = MatchFields::match_all();
+        match_fields.in_port = Some(1);
+        let flow = FlowModEvent::modify_flow_strict(
+            100,
+            match_fields,
+            vec![Action::output(PseudoPort::PhysicalPort(2))],
+            1,
+        )
+        .idle_timeout(Timeout::ExpireAfter(30))
+        .hard_timeout(Timeout::ExpireAfter(300))
+        .cookie(0xab, !0)
+        .flags(FlowModFlags::new(true, false, false, false, false));
+        let mut bytes = Vec::new();
+        flow.marshal(&mut bytes);
+
+        assert_eq!(&bytes[..8], &0xabu64.to_be_bytes());
+        assert_eq!(&bytes[8..16], &[0xff; 8]);
+        // table, command, idle timeout, hard timeout, priority
+        assert_eq!(&bytes[16..24], &[1, 2, 0, 30, 1, 44, 0, 100]);
+        // flags
+        assert_eq!(&bytes[36..38], &[0, 1]);
+
+        let delete = FlowModEvent::delete_all().out_group(7);
+        let mut bytes = Vec::new();
+        delete.marshal(&mut bytes);
+        assert_eq!(&bytes[16..18], &[0xff, 3]);
+        assert_eq!(&bytes[28..32], &[0xff; 4]); // out port any
+        assert_eq!(&bytes[32..36], &[0, 0, 0, 7]);
+        // empty match, no instructions
+        assert_eq!(bytes.len(), 40 + 8);
+    }
+
+    #[test]
+    fn test_flow_mod_commands_v1_0() {
+        let delete = ofp10::FlowModEvent::delete_flow_strict(10, ofp10::MatchFields::match_all())
+            .out_port(ofp10::PseudoPort::PhysicalPort(3))
+            .cookie(5);
+        let mut bytes = Vec::new();
+        delete.marshal(&mut bytes);
+
+        // the match takes 40 bytes
+        assert_eq!(&bytes[40..48], &5u64.to_be_bytes());
+        // command, idle timeout, hard timeout, priority
+        assert_eq!(&bytes[48..56], &[0, 4, 0, 0, 0, 0, 0, 10]);
+        assert_eq!(&bytes[56..60], &[0xff; 4]); // no buffer
+        assert_eq!(&bytes[60..62], &[0, 3]);
+        assert_eq!(bytes.len(), 64);
+    }
+
+    #[test]
+    fn test_flow_removed_parse() {
+        let mut match_fields = MatchFields::match_all();
+        match_fields.in_port = Some(3);
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&0xabu64.to_be_bytes());
+        payload.extend_from_slice(&[0, 100, 0, 2]); // priority, reason, table
+        payload.extend_from_slice(&[0, 0, 0, 9, 0, 0, 0, 1]); // duration
+        payload.extend_from_slice(&[0, 30, 0, 0]); // idle timeout, hard timeout
+        payload.extend_from_slice(&7u64.to_be_bytes());
+        payload.extend_from_slice(&700u64.to_be_bytes());
+        match_fields.marshal(&mut payload).unwrap();
+
+        let removed = FlowRemovedEvent::parse(&payload).unwrap();
+        assert_eq!(removed.cookie, 0xab);
+        assert_eq!(removed.priority, 100);
+        assert_eq!(removed.reason, FlowRemovedReason::IdleTimeout);
+        assert_eq!(removed.table_id, 2);
+        assert_eq!(removed.duration_sec, 9);
+        assert!(matches!(removed.idle_timeout, Timeout::ExpireAfter(30)));
+        assert!(matches!(removed.hard_timeout, Timeout::Permanent));
+        assert_eq!(removed.packet_count, 7);
+        assert_eq!(removed.byte_count, 700);
+        assert_eq!(removed.match_fields.in_port, Some(3));
+
+        let mut match_fields = ofp10::MatchFields::match_all();
+        match_fields.transport_dest = Some(80);
+        let mut payload = Vec::new();
+        match_fields.marshal(&mut payload);
+        payload.extend_from_slice(&1u64.to_be_bytes());
+        payload.extend_from_slice(&[0, 10, 2, 0]); // priority, reason, pad
+        payload.extend_from_slice(&[0, 0, 0, 4, 0, 0, 0, 0]); // duration
+        payload.extend_from_slice(&[0, 0, 0, 0]); // idle timeout, pad
+        payload.extend_from_slice(&2u64.to_be_bytes());
+        payload.extend_from_slice(&128u64.to_be_bytes());
+
+        let removed = ofp10::FlowRemovedEvent::parse(&payload).unwrap();
+        assert_eq!(removed.cookie, 1);
+        assert_eq!(removed.priority, 10);
+        assert_eq!(removed.reason, ofp10::events::FlowRemovedReason::Delete);
+        assert_eq!(removed.duration_sec, 4);
+        assert_eq!(removed.packet_count, 2);
+        assert_eq!(removed.byte_count, 128);
+        assert_eq!(removed.match_fields.transport_dest, Some(80));
+        assert_eq!(removed.match_fields.in_port, None);
+    }
+
+    #[test]
+    fn test_match_fields_round_trip_v1_3() {
+        let mut fields = MatchFields::match_all();
+        fields.in_port = Some(1);
+        fields.in_phy_port = Some(2);
+        fields.metadata = Some(0xdead_beef);
+        fields.eth_src = Some(MacAddr::from("00:11:22:33:44:55"));
+        fields.eth_typ = Some(0x0806);
+        fields.vlan_vid = Some(MatchFields::VLAN_PRESENT | 10);
+        fields.vlan_pcp = Some(5);
+        fields.ip_dscp = Some(46);
+        fields.ip_ecn = Some(1);
+        fields.ip_proto = Some(132);
+        fields.sctp_src = Some(3868);
+        fields.sctp_dst = Some(2905);
+        fields.icmpv4_type = Some(8);
+        fields.icmpv4_code = Some(0);
+        fields.arp_op = Some(1);
+        fields.arp_spa = Some(Ipv4Addr::new(10, 0, 0, 1));
+        fields.arp_tpa = Some(Ipv4Addr::new(10, 0, 0, 2));
+        fields.arp_sha = Some(MacAddr::from("00:11:22:33:44:55"));
+        fields.arp_tha = Some(MacAddr::from("66:77:88:99:aa:bb"));
+        fields.ipv6_src = Some(Ipv6Addr::LOCALHOST);
+        fields.ipv6_flabel = Some(0xfffff);
+        fields.icmpv6_type = Some(135);
+        fields.icmpv6_code = Some(0);
+        fields.ipv6_nd_target = Some("fe80::1".parse().unwrap());
+        fields.ipv6_nd_sll = Some(MacAddr::from("00:11:22:33:44:55"));
+        fields.ipv6_nd_tll = Some(MacAddr::from("66:77:88:99:aa:bb"));
+        fields.mpls_label = Some(0x12345);
+        fields.mpls_tc = Some(3);
+        fields.mpls_bos = Some(1);
+        fields.pbb_isid = Some(0xabcdef);
+        fields.tunnel_id = Some(42);
+        fields.ipv6_exthdr = Some(0x1ff);
+
+        let mut bytes = Vec::new();
+        fields.marshal(&mut bytes).unwrap();
+        assert_eq!(bytes.len() % 8, 0);
+        // fields are written in OXM field order
+        assert_eq!(&bytes[4..8], &[0x80, 0x00, 0x00, 4]);
+        assert_eq!(&bytes[8..12], &[0, 0, 0, 1]);
+        let parsed = MatchFields::parse(&mut Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(parsed, fields);
+
+        // an OXM field of another class is kept as an extension
+        let mut nxm = vec![0, 1, 0, 19, 0, 1, 0x02, 4, 0, 0, 0, 7];
+        nxm.extend_from_slice(&[0x80, 0, 0x4a, 3, 0, 0, 9, 0, 0, 0, 0, 0]);
+        let parsed = MatchFields::parse(&mut Cursor::new(nxm)).unwrap();
+        assert_eq!(parsed.in_port, None);
+        assert_eq!(parsed.pbb_isid, Some(9));
+        assert_eq!(
+            parsed.extensions,
+            vec![OxmExtension::new(1, 1, false, vec![0, 0, 0, 7])]
+        );
+    }
+
+    #[test]
+    fn test_match_fields_masks_v1_3() {
+        let mut fields = MatchFields::match_all();
+        fields.eth_typ = Some(0x0800);
+        fields.ipv4_dst_prefix(Ipv4Addr::new(10, 1, 2, 3), 8);
+        fields.vlan_present();
+        fields.eth_dst = Some(MacAddr::from("01:00:5e:00:00:01"));
+        fields.masks.eth_dst = Some(MacAddr::from("01:00:00:00:00:00"));
+        fields.metadata = Some(0x1234);
+        fields.masks.metadata = Some(0xff00);
+        fields.ipv6_src_prefix("2001:db8::1".parse().unwrap(), 64);
+        fields.ipv4_src = Some(Ipv4Addr::new(192, 168, 0, 1));
+        fields.masks.ipv4_src = Some(MatchMasks::ipv4_prefix(32));
+
+        let mut bytes = Vec::new();
+        fields.marshal(&mut bytes).unwrap();
+        let parsed = MatchFields::parse(&mut Cursor::new(bytes.clone())).unwrap();
+
+        // values are cleared outside of their mask, full masks are left out
+        assert_eq!(parsed.ipv4_dst, Some(Ipv4Addr::new(10, 0, 0, 0)));
+        assert_eq!(parsed.masks.ipv4_dst, Some(Ipv4Addr::new(255, 0, 0, 0)));
+        assert_eq!(parsed.ipv4_src, Some(Ipv4Addr::new(192, 168, 0, 1)));
+        assert_eq!(parsed.masks.ipv4_src, None);
+        assert_eq!(parsed.vlan_vid, Some(MatchFields::VLAN_PRESENT));
+        assert_eq!(parsed.masks.vlan_vid, Some(MatchFields::VLAN_PRESENT));
+        assert_eq!(parsed.eth_dst, Some(MacAddr::from("01:00:00:00:00:00")));
+        assert_eq!(parsed.metadata, Some(0x1200));
+        assert_eq!(parsed.ipv6_src, Some("2001:db8::".parse().unwrap()));
+        assert_eq!(
+            parsed.masks.ipv6_src,
+            Some("ffff:ffff:ffff:ffff::".parse().unwrap())
+        );
+
+        // metadata comes first: class, field 2 with the mask bit, length 16
+        assert_eq!(&bytes[4..8], &[0x80, 0x00, 0x05, 16]);
+        assert_eq!(MatchMasks::ipv4_prefix(0), Ipv4Addr::UNSPECIFIED);
+        assert_eq!(MatchMasks::ipv6_prefix(200), Ipv6Addr::from(u128::MAX));
+    }
+
+    #[test]
+    fn test_match_fields_invalid_length_v1_3() {
+        // a match holding one oxm field, padded to 8 bytes
+        let parse = |oxm: &[u8]| {
+            let mut bytes = vec![0, 1, 0, 4 + oxm.len() as u8];
+            bytes.extend_from_slice(oxm);
+            bytes.resize(bytes.len().div_ceil(8) * 8, 0);
+            MatchFields::parse(&mut Cursor::new(bytes))
+        };
+        let invalid = |oxm: &[u8]| parse(oxm).unwrap_err().kind() == ErrorKind::InvalidData;
+        // in_port with a 2 byte value
+        assert!(invalid(&[0x80, 0x00, 0x00, 0x02, 0, 1]));
+        // eth_type with a 4 byte value
+        assert!(invalid(&[0x80, 0x00, 0x0a, 0x04, 0x08, 0x00, 0, 0]));
+        // masked ipv4_dst of odd length
+        assert!(invalid(&[0x80, 0x00, 0x19, 0x07, 10, 0, 0, 0, 255, 0, 0]));
+        // masked ipv4_dst with a 2 byte value and mask
+        assert!(invalid(&[0x80, 0x00, 0x19, 0x04, 10, 0, 255, 0]));
+        assert!(parse(&[0x80, 0x00, 0x19, 0x08, 10, 0, 0, 0, 255, 0, 0, 0]).is_ok());
+    }
+}
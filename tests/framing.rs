@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use tenjin_sdn::openflow::MessageFramer;
+    use tokio::io::AsyncWriteExt;
+
+    fn echo_request(xid: u8, body: &[u8]) -> Vec<u8> {
+        let mut msg = vec![4, 2, 0, (8 + body.len()) as u8, 0, 0, 0, xid];
+        msg.extend_from_slice(body);
+        msg
+    }
+
+    #[test]
+    fn test_framer_waits_for_fragmented_message() {
+        let msg = echo_request(1, &[1, 2, 3, 4]);
+        let mut framer = MessageFramer::new();
+
+        framer.push(&msg[..5]);
+        assert_eq!(framer.next_message().unwrap(), None);
+        framer.push(&msg[5..10]);
+        assert_eq!(framer.next_message().unwrap(), None);
+        framer.push(&msg[10..]);
+        assert_eq!(framer.next_message().unwrap(), Some(msg));
+        assert_eq!(framer.buffered(), 0);
+    }
+
+    #[test]
+    fn test_framer_splits_merged_messages() {
+        let first = echo_request(1, &[0xaa; 3]);
+        let second = echo_request(2, &[]);
+        let third = echo_request(3, &[0xbb; 6]);
+        let mut stream = first.clone();
+        stream.extend_from_slice(&second);
+        stream.extend_from_slice(&third[..4]);
+
+        let mut framer = MessageFramer::new();
+        framer.push(&stream);
+        assert_eq!(framer.next_message().unwrap(), Some(first));
+        assert_eq!(framer.next_message().unwrap(), Some(second));
+        assert_eq!(framer.next_message().unwrap(), None);
+        framer.push(&third[4..]);
+        assert_eq!(framer.next_message().unwrap(), Some(third));
+    }
+
+    #[test]
+    fn test_framer_rejects_invalid_length() {
+        let mut framer = MessageFramer::new();
+        framer.push(&[4, 2, 0, 4, 0, 0, 0, 1]);
+        assert!(framer.next_message().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_framer_reads_from_stream() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+        let first = echo_request(7, &[9; 20]);
+        let second = echo_request(8, &[1]);
+
+        let mut framer = MessageFramer::new();
+        let mut wire = first.clone();
+        wire.extend_from_slice(&second);
+        client.write_all(&wire[..13]).await.unwrap();
+        let writer = tokio::spawn(async move {
+            client.write_all(&wire[13..]).await.unwrap();
+        });
+
+        assert_eq!(framer.read_message(&mut server).await.unwrap(), Some(first));
+        assert_eq!(
+            framer.read_message(&mut server).await.unwrap(),
+            Some(second)
+        );
+        writer.await.unwrap();
+        assert_eq!(framer.read_message(&mut server).await.unwrap(), None);
+    }
+}
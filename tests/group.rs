@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tenjin_sdn::openflow::ofp13::{
+        events::{Bucket, GroupType},
+        Action, GroupModEvent, MessageMarshal, MultipartReplyBody, MultipartReplyEvent, PseudoPort,
+    };
+
+    #[test]
+    fn test_group_mod_marshal() {
+        let group = GroupModEvent::add_group(
+            GroupType::Select,
+            7,
+            vec![
+                Bucket::new(vec![Action::output(PseudoPort::PhysicalPort(1))]).weight(2),
+                Bucket::new(vec![Action::output(PseudoPort::PhysicalPort(2))]).weight(1),
+            ],
+        );
+        let mut bytes = Vec::new();
+        group.marshal(&mut bytes);
+
+        assert_eq!(&bytes[..8], &[0, 0, 1, 0, 0, 0, 0, 7]);
+        assert_eq!(bytes.len(), 8 + 2 * 32);
+        // bucket length, weight, watch port and watch group
+        assert_eq!(
+            &bytes[8..24],
+            &[0, 32, 0, 2, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]
+        );
+        assert_eq!(&bytes[24..32], &[0, 0, 0, 16, 0, 0, 0, 1]);
+
+        let mut delete = Vec::new();
+        GroupModEvent::delete_all().marshal(&mut delete);
+        assert_eq!(delete, vec![0, 2, 0, 0, 0xff, 0xff, 0xff, 0xfc]);
+    }
+
+    #[test]
+    fn test_bucket_invalid_length() {
+        let mut bytes = Vec::new();
+        Bucket::new(vec![Action::output(PseudoPort::PhysicalPort(1))])
+            .marshal(&mut bytes)
+            .unwrap();
+        let parse =
+            |bytes: &[u8], len: usize| Bucket::parse_list(&mut Cursor::new(bytes.to_vec()), len);
+        assert_eq!(parse(&bytes, 32).unwrap().len(), 1);
+
+        // the bucket runs past the end of the list
+        assert!(parse(&bytes, 24).is_err());
+        // the length is not a multiple of 8
+        let mut odd = bytes.clone();
+        odd[1] = 36;
+        odd.extend_from_slice(&[0; 4]);
+        assert!(parse(&odd, 36).is_err());
+        // the output action runs past the end of the bucket
+        let mut short = bytes.clone();
+        short[1] = 24;
+        assert!(parse(&short, 32).is_err());
+    }
+
+    #[test]
+    fn test_group_desc_and_stats_reply() {
+        // fast failover group 3 watching port 1, falling back to port 2
+        let mut buckets = Vec::new();
+        Bucket::new(vec![Action::output(PseudoPort::PhysicalPort(1))])
+            .watch_port(1)
+            .marshal(&mut buckets)
+            .unwrap();
+        Bucket::new(vec![Action::output(PseudoPort::PhysicalPort(2))])
+            .watch_port(2)
+            .marshal(&mut buckets)
+            .unwrap();
+        let mut desc = vec![0, 7, 0, 0, 0, 0, 0, 0];
+        desc[0..2].copy_from_slice(&(8 + buckets.len() as u16).to_be_bytes());
+        desc[2] = 3;
+        desc[4..8].copy_from_slice(&3u32.to_be_bytes());
+        desc.append(&mut buckets);
+        let mut body = vec![0, 7, 0, 0, 0, 0, 0, 0];
+        body.append(&mut desc);
+
+        match MultipartReplyEvent::parse(&body).unwrap().body {
+            MultipartReplyBody::GroupDesc(groups) => {
+                assert_eq!(groups.len(), 1);
+                assert_eq!(groups[0].group_type, GroupType::FastFailover);
+                assert_eq!(groups[0].group_id, 3);
+                assert_eq!(groups[0].buckets.len(), 2);
+                assert_eq!(groups[0].buckets[1].watch_port, 2);
+                match &groups[0].buckets[1].actions[..] {
+                    [Action::Oputput(PseudoPort::PhysicalPort(2), _)] => (),
+                    actions => panic!("unexpected actions {:?}", actions),
+                }
+            }
+            _ => panic!("expected group description"),
+        }
+
+        let mut stats = vec![0, 6, 0, 0, 0, 0, 0, 0];
+        stats.extend_from_slice(&56u16.to_be_bytes());
+        stats.extend_from_slice(&[0; 2]);
+        stats.extend_from_slice(&3u32.to_be_bytes()); // group id
+        stats.extend_from_slice(&2u32.to_be_bytes()); // ref count
+        stats.extend_from_slice(&[0; 4]);
+        stats.extend_from_slice(&10u64.to_be_bytes());
+        stats.extend_from_slice(&640u64.to_be_bytes());
+        stats.extend_from_slice(&5u32.to_be_bytes());
+        stats.extend_from_slice(&0u32.to_be_bytes());
+        stats.extend_from_slice(&10u64.to_be_bytes());
+        stats.extend_from_slice(&640u64.to_be_bytes());
+        match MultipartReplyEvent::parse(&stats).unwrap().body {
+            MultipartReplyBody::Group(stats) => {
+                assert_eq!(stats.len(), 1);
+                assert_eq!(stats[0].ref_count, 2);
+                assert_eq!(stats[0].bucket_stats.len(), 1);
+                assert_eq!(stats[0].bucket_stats[0].byte_count, 640);
+            }
+            _ => panic!("expected group stats"),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use tenjin_sdn::openflow::{handshake, Hello, HelloElement, MessageFramer};
+    use tokio::io::AsyncWriteExt;
+
+    #[test]
+    fn test_hello_version_bitmap() {
+        let hello = Hello::new(&[0x04, 0x01]);
+        let bytes = hello.marshal();
+        assert_eq!(
+            bytes,
+            vec![4, 0, 0, 16, 0, 0, 0, 0, 0, 1, 0, 8, 0, 0, 0, 0x12]
+        );
+        assert_eq!(Hello::parse(&bytes).unwrap(), hello);
+
+        // OpenFlow 1.0 only: no element is needed
+        assert_eq!(Hello::new(&[0x01]).marshal(), vec![1, 0, 0, 8, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_hello_negotiate() {
+        let supported = [0x01, 0x04];
+        let bitmap = |versions: Vec<u8>| Hello {
+            version: *versions.iter().max().unwrap(),
+            xid: 0,
+            elements: vec![
+                HelloElement::Unknown {
+                    elem_type: 7,
+                    data: vec![1, 2, 3],
+                },
+                HelloElement::VersionBitmap(versions),
+            ],
+        };
+        assert_eq!(bitmap(vec![1, 4, 5]).negotiate(&supported), Some(4));
+        assert_eq!(bitmap(vec![1, 2, 3]).negotiate(&supported), Some(1));
+        assert_eq!(bitmap(vec![2, 3]).negotiate(&supported), None);
+
+        let plain = |version| Hello {
+            version,
+            xid: 0,
+            elements: Vec::new(),
+        };
+        assert_eq!(plain(0x01).negotiate(&supported), Some(1));
+        assert_eq!(plain(0x06).negotiate(&supported), Some(4));
+        assert_eq!(plain(0x03).negotiate(&supported), None);
+
+        let with_unknown = bitmap(vec![1, 4]);
+        assert_eq!(Hello::parse(&with_unknown.marshal()).unwrap(), with_unknown);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_incompatible() {
+        let (mut switch, controller) = tokio::io::duplex(256);
+        let (mut reader, mut writer) = tokio::io::split(controller);
+        let task = tokio::spawn(async move {
+            let mut framer = MessageFramer::new();
+            handshake(&mut reader, &mut writer, &mut framer, &[0x01, 0x04]).await
+        });
+
+        // A switch speaking OpenFlow 1.2 only
+        switch.write_all(&[3, 0, 0, 8, 0, 0, 0, 9]).await.unwrap();
+        assert!(task.await.unwrap().is_err());
+
+        let mut framer = MessageFramer::new();
+        let hello = framer.read_message(&mut switch).await.unwrap().unwrap();
+        assert_eq!(
+            Hello::parse(&hello).unwrap().version_bitmap(),
+            Some(&[1, 4][..])
+        );
+        let error = framer.read_message(&mut switch).await.unwrap().unwrap();
+        assert_eq!(&error[..8], &[3, 1, 0, error.len() as u8, 0, 0, 0, 9]);
+        assert_eq!(&error[8..12], &[0, 0, 0, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_keeps_early_messages() {
+        let (mut switch, controller) = tokio::io::duplex(256);
+        let (mut reader, mut writer) = tokio::io::split(controller);
+
+        // Hello and features reply arrive in a single segment
+        let mut bytes = Hello::new(&[0x01]).marshal();
+        bytes.extend_from_slice(&[1, 6, 0, 8, 0, 0, 0, 2]);
+        switch.write_all(&bytes).await.unwrap();
+
+        let mut framer = MessageFramer::new();
+        let negotiated = handshake(&mut reader, &mut writer, &mut framer, &[0x01, 0x04])
+            .await
+            .unwrap();
+        assert_eq!(negotiated, (0x01, 0));
+        assert_eq!(framer.buffered(), 8);
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tenjin_sdn::openflow::ofp13::{
+        events::flow_mod::instructions::{GotoTable, Instrucion, InstructType},
+        Action, FlowModEvent, InstructionSet, MatchFields, MessageMarshal, PseudoPort,
+    };
+
+    #[test]
+    fn test_instruction_set_order() {
+        let set = InstructionSet::new()
+            .goto_table(2)
+            .write_metadata(0xab, 0xff)
+            .apply_actions(vec![Action::output(PseudoPort::PhysicalPort(1))])
+            .meter(5)
+            .apply_actions(vec![Action::output(PseudoPort::PhysicalPort(3))]);
+        let types: Vec<InstructType> = set.iter().map(|i| i.instruct_type()).collect();
+        assert_eq!(
+            types,
+            vec![
+                InstructType::Meter,
+                InstructType::ApplyActions,
+                InstructType::WriteMetadata,
+                InstructType::GotoTable,
+            ]
+        );
+        // the second apply-actions replaced the first one
+        match set.get(&InstructType::ApplyActions) {
+            Some(Instrucion::InstructActions(instruct)) => match &instruct.actions[..] {
+                [Action::Oputput(PseudoPort::PhysicalPort(3), _)] => (),
+                actions => panic!("unexpected actions {:?}", actions),
+            },
+            other => panic!("unexpected instruction {:?}", other),
+        }
+
+        let flow =
+            FlowModEvent::add_flow(10, MatchFields::match_all(), vec![], 1, None).instructions(set);
+        assert!(flow.validate().is_ok());
+        let mut bytes = Vec::new();
+        flow.marshal(&mut bytes);
+        // meter, apply-actions with one output, write-metadata, goto-table
+        assert_eq!(bytes.len(), 48 + 8 + 24 + 24 + 8);
+        assert_eq!(&bytes[48..50], &[0, 6]);
+        assert_eq!(&bytes[56..58], &[0, 4]);
+        assert_eq!(&bytes[80..82], &[0, 2]);
+        assert_eq!(&bytes[104..], &[0, 1, 0, 8, 2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_instruction_set_validation() {
+        let backwards = FlowModEvent::add_flow(10, MatchFields::match_all(), vec![], 3, None)
+            .instructions(InstructionSet::new().goto_table(3));
+        assert!(backwards.validate().is_err());
+
+        let duplicate = vec![
+            Instrucion::GotoTable(GotoTable::new(1)),
+            Instrucion::GotoTable(GotoTable::new(2)),
+        ];
+        assert!(InstructionSet::try_from(duplicate).is_err());
+
+        let set = InstructionSet::new()
+            .clear_actions()
+            .experimenter(0x2320, vec![1, 2, 3]);
+        assert!(set.validate(0).is_ok());
+        let mut bytes = Vec::new();
+        set.marshal(&mut bytes);
+        assert_eq!(bytes.len(), 8 + 16);
+        assert_eq!(&bytes[8..16], &[0xff, 0xff, 0, 16, 0, 0, 0x23, 0x20]);
+    }
+
+    #[test]
+    fn test_instruction_invalid_length() {
+        let parse = |bytes: Vec<u8>| {
+            let len = bytes.len();
+            Instrucion::parse_list(&mut Cursor::new(bytes), len)
+        };
+        // goto-table claiming 16 bytes
+        assert!(parse(vec![0, 1, 0, 16, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+        // clear-actions carrying an action
+        let mut clear = vec![0, 5, 0, 16, 0, 0, 0, 0];
+        clear.extend_from_slice(&[0, 24, 0, 8, 0, 0, 0, 0]);
+        assert!(parse(clear).is_err());
+        // meter claiming 8 bytes, but the list ends after 4
+        let bytes = vec![0, 6, 0, 8, 0, 0, 0, 1];
+        assert!(Instrucion::parse_list(&mut Cursor::new(bytes.clone()), 4).is_err());
+        assert_eq!(parse(bytes).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use tenjin_sdn::openflow::ofp13::{
+        events::{MeterBand, MeterFlags},
+        Action, FlowModEvent, MatchFields, MessageMarshal, MeterModEvent, MultipartReplyBody,
+        MultipartReplyEvent, PseudoPort,
+    };
+
+    #[test]
+    fn test_meter_mod_marshal() {
+        let meter = MeterModEvent::add_meter(
+            MeterFlags {
+                kbps: true,
+                burst: true,
+                stats: true,
+                ..MeterFlags::default()
+            },
+            5,
+            vec![
+                MeterBand::Drop {
+                    rate: 10_000,
+                    burst_size: 100,
+                },
+                MeterBand::DscpRemark {
+                    rate: 5_000,
+                    burst_size: 50,
+                    prec_level: 1,
+                },
+            ],
+        );
+        let mut bytes = Vec::new();
+        meter.marshal(&mut bytes);
+
+        assert_eq!(bytes.len(), 8 + 2 * 16);
+        assert_eq!(&bytes[..8], &[0, 0, 0, 0b1101, 0, 0, 0, 5]);
+        assert_eq!(
+            &bytes[8..24],
+            &[0, 1, 0, 16, 0, 0, 0x27, 0x10, 0, 0, 0, 100, 0, 0, 0, 0]
+        );
+        assert_eq!(&bytes[24..28], &[0, 2, 0, 16]);
+        assert_eq!(bytes[36], 1);
+    }
+
+    #[test]
+    fn test_flow_mod_with_meter() {
+        let flow = FlowModEvent::add_flow(
+            10,
+            MatchFields::match_all(),
+            vec![Action::output(PseudoPort::PhysicalPort(1))],
+            0,
+            None,
+        )
+        .meter(5);
+        let mut bytes = Vec::new();
+        flow.marshal(&mut bytes);
+        // the meter instruction follows the 8 byte empty match
+        assert_eq!(&bytes[48..56], &[0, 6, 0, 8, 0, 0, 0, 5]);
+        assert_eq!(&bytes[56..58], &[0, 4]);
+    }
+
+    #[test]
+    fn test_meter_config_and_stats_reply() {
+        let mut band = Vec::new();
+        MeterBand::Drop {
+            rate: 1000,
+            burst_size: 0,
+        }
+        .marshal(&mut band)
+        .unwrap();
+        let mut body = vec![0, 10, 0, 0, 0, 0, 0, 0];
+        body.extend_from_slice(&24u16.to_be_bytes());
+        body.extend_from_slice(&1u16.to_be_bytes()); // kbps
+        body.extend_from_slice(&5u32.to_be_bytes());
+        body.append(&mut band);
+        match MultipartReplyEvent::parse(&body).unwrap().body {
+            MultipartReplyBody::MeterConfig(meters) => {
+                assert_eq!(meters.len(), 1);
+                assert_eq!(meters[0].meter_id, 5);
+                assert!(meters[0].flags.kbps);
+                assert_eq!(
+                    meters[0].bands,
+                    vec![MeterBand::Drop {
+                        rate: 1000,
+                        burst_size: 0
+                    }]
+                );
+            }
+            _ => panic!("expected meter configuration"),
+        }
+
+        let mut stats = vec![0, 9, 0, 0, 0, 0, 0, 0];
+        stats.extend_from_slice(&5u32.to_be_bytes());
+        stats.extend_from_slice(&56u16.to_be_bytes());
+        stats.extend_from_slice(&[0; 6]);
+        stats.extend_from_slice(&2u32.to_be_bytes()); // flow count
+        stats.extend_from_slice(&100u64.to_be_bytes());
+        stats.extend_from_slice(&6400u64.to_be_bytes());
+        stats.extend_from_slice(&[0; 8]);
+        stats.extend_from_slice(&40u64.to_be_bytes());
+        stats.extend_from_slice(&2560u64.to_be_bytes());
+        match MultipartReplyEvent::parse(&stats).unwrap().body {
+            MultipartReplyBody::Meter(stats) => {
+                assert_eq!(stats[0].meter_id, 5);
+                assert_eq!(stats[0].flow_count, 2);
+                assert_eq!(stats[0].byte_in_count, 6400);
+                assert_eq!(stats[0].band_stats[0].packet_band_count, 40);
+            }
+            _ => panic!("expected meter stats"),
+        }
+
+        // a length that ends inside the band counters is refused
+        stats[12..14].copy_from_slice(&48u16.to_be_bytes());
+        assert!(MultipartReplyEvent::parse(&stats[..56]).is_err());
+    }
+
+    #[test]
+    fn test_meter_band_lengths() {
+        let experimenter = MeterBand::Experimenter {
+            rate: 10,
+            burst_size: 0,
+            experimenter: 0x2320,
+            data: vec![1, 2, 3, 4, 5, 6, 7, 8],
+        };
+        let mut bytes = Vec::new();
+        experimenter.marshal(&mut bytes).unwrap();
+        assert_eq!(&bytes[..4], &[0xff, 0xff, 0, 24]);
+        let parsed = MeterBand::parse_list(&mut Cursor::new(bytes.clone()), bytes.len()).unwrap();
+        assert_eq!(parsed, vec![experimenter]);
+
+        let odd = MeterBand::Experimenter {
+            rate: 10,
+            burst_size: 0,
+            experimenter: 0x2320,
+            data: vec![1, 2, 3],
+        };
+        assert!(odd.marshal(&mut Vec::new()).is_err());
+
+        // the experimenter band claims more bytes than the list holds
+        assert!(MeterBand::parse_list(&mut Cursor::new(bytes), 16).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tenjin_sdn::openflow::ofp13::{
+        events::{
+            actions::ToInstruction,
+            flow_mod::instructions::Instrucion,
+            multipart::{MultipartAssembler, MULTIPART_REPLY_MORE},
+        },
+        Action, FlowStatsRequestEvent, MatchFields, MessageMarshal, MultipartReplyBody,
+        MultipartReplyEvent, OfpMsgEvent, Openflow13, PseudoPort,
+    };
+
+    fn flow_stats_entry(in_port: u32, packets: u64) -> Vec<u8> {
+        let mut match_fields = MatchFields::match_all();
+        match_fields.in_port = Some(in_port);
+        let mut match_bytes = Vec::new();
+        match_fields.marshal(&mut match_bytes).unwrap();
+        let mut instruct_bytes = Vec::new();
+        Instrucion::InstructActions(
+            vec![Action::output(PseudoPort::PhysicalPort(2))].to_instruct(),
+        )
+        .marshal(&mut instruct_bytes);
+
+        let length = 48 + match_bytes.len() + instruct_bytes.len();
+        let mut entry = Vec::new();
+        entry.extend_from_slice(&(length as u16).to_be_bytes());
+        entry.extend_from_slice(&[0, 0]); // table id, padding
+        entry.extend_from_slice(&10u32.to_be_bytes()); // duration sec
+        entry.extend_from_slice(&0u32.to_be_bytes()); // duration nsec
+        entry.extend_from_slice(&100u16.to_be_bytes()); // priority
+        entry.extend_from_slice(&30u16.to_be_bytes()); // idle timeout
+        entry.extend_from_slice(&0u16.to_be_bytes()); // hard timeout
+        entry.extend_from_slice(&1u16.to_be_bytes()); // flags
+        entry.extend_from_slice(&[0; 4]);
+        entry.extend_from_slice(&0xabu64.to_be_bytes()); // cookie
+        entry.extend_from_slice(&packets.to_be_bytes());
+        entry.extend_from_slice(&(packets * 64).to_be_bytes());
+        entry.append(&mut match_bytes);
+        entry.append(&mut instruct_bytes);
+        entry
+    }
+
+    fn multipart_part(flags: u16, body: &[u8]) -> Vec<u8> {
+        multipart_typed(1, flags, body)
+    }
+
+    fn multipart_typed(typ: u16, flags: u16, body: &[u8]) -> Vec<u8> {
+        let mut part = typ.to_be_bytes().to_vec();
+        part.extend_from_slice(&flags.to_be_bytes());
+        part.extend_from_slice(&[0; 4]);
+        part.extend_from_slice(body);
+        part
+    }
+
+    #[test]
+    fn test_flow_stats_request_marshal() {
+        let mut in_port = MatchFields::match_all();
+        in_port.in_port = Some(1);
+        let request = FlowStatsRequestEvent::new(0, in_port).cookie(0xab, !0);
+        let mut bytes = Vec::new();
+        request.marshal(&mut bytes);
+
+        assert_eq!(bytes.len(), 8 + 32 + 16);
+        assert_eq!(request.size_of(), bytes.len());
+        assert_eq!(&bytes[..4], &[0, 1, 0, 0]);
+        assert_eq!(&bytes[12..16], &[0xff; 4]); // out port any
+        assert_eq!(&bytes[24..32], &0xabu64.to_be_bytes());
+        // match type oxm, length 12, in_port 1, padded to 8 bytes
+        assert_eq!(
+            &bytes[40..],
+            &[0, 1, 0, 12, 0x80, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_match_fields_roundtrip() {
+        let mut fields = MatchFields::match_all();
+        fields.eth_typ = Some(0x0800);
+        fields.ipv4_src = Some("10.0.0.1".parse().unwrap());
+        fields.ip_proto = Some(6);
+        fields.tcp_dst = Some(80);
+        let mut bytes = Vec::new();
+        fields.marshal(&mut bytes).unwrap();
+        assert_eq!(bytes.len() % 8, 0);
+
+        let parsed = MatchFields::parse(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(parsed.eth_typ, Some(0x0800));
+        assert_eq!(parsed.ipv4_src, Some("10.0.0.1".parse().unwrap()));
+        assert_eq!(parsed.ip_proto, Some(6));
+        assert_eq!(parsed.tcp_dst, Some(80));
+    }
+
+    #[test]
+    fn test_flow_stats_reply_reassembly() {
+        let assembler = MultipartAssembler::new();
+        let first = multipart_part(MULTIPART_REPLY_MORE, &flow_stats_entry(1, 5));
+        let last = multipart_part(0, &flow_stats_entry(3, 7));
+
+        assert!(assembler.push(9, first).unwrap().is_none());
+        assert_eq!(assembler.pending(), 1);
+        let joined = assembler.push(9, last).unwrap().unwrap();
+        assert_eq!(assembler.pending(), 0);
+
+        let reply = MultipartReplyEvent::parse(&joined).unwrap();
+        assert!(!reply.more());
+        let stats = match reply.body {
+            MultipartReplyBody::Flow(stats) => stats,
+            _ => panic!("expected flow stats"),
+        };
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].match_fields.in_port, Some(1));
+        assert_eq!(stats[1].match_fields.in_port, Some(3));
+        assert_eq!(stats[1].packet_count, 7);
+        assert_eq!(stats[1].byte_count, 7 * 64);
+        assert_eq!(stats[0].priority, 100);
+        assert_eq!(stats[0].idle_timeout.to_int(), 30);
+        assert!(stats[0].flags.send_flow_rem);
+        match &stats[0].instructions[..] {
+            [Instrucion::InstructActions(instruct)] => match &instruct.actions[..] {
+                [Action::Oputput(PseudoPort::PhysicalPort(2), _)] => (),
+                actions => panic!("unexpected actions {:?}", actions),
+            },
+            instructions => panic!("unexpected instructions {:?}", instructions),
+        }
+    }
+
+    #[test]
+    fn test_multipart_reply_size_limit() {
+        let entry = flow_stats_entry(1, 5);
+        let assembler = MultipartAssembler::with_max_size(8 + 2 * entry.len());
+        let part = multipart_part(MULTIPART_REPLY_MORE, &entry);
+
+        assert!(assembler.push(4, part.clone()).unwrap().is_none());
+        assert!(assembler.push(4, part.clone()).unwrap().is_none());
+        assert!(assembler.push(4, part).is_err());
+        assert_eq!(assembler.pending(), 0);
+    }
+
+    #[test]
+    fn test_port_and_aggregate_stats() {
+        let ofp = Openflow13::new();
+        let mut bytes = Vec::new();
+        ofp.port_stats_req(Some(3)).marshal(&mut bytes);
+        assert_eq!(bytes, vec![0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0]);
+
+        let mut port = 3u32.to_be_bytes().to_vec();
+        port.extend_from_slice(&[0; 4]);
+        for counter in 1..=12u64 {
+            port.extend_from_slice(&counter.to_be_bytes());
+        }
+        port.extend_from_slice(&60u32.to_be_bytes());
+        port.extend_from_slice(&0u32.to_be_bytes());
+        let reply = MultipartReplyEvent::parse(&multipart_typed(4, 0, &port)).unwrap();
+        match reply.body {
+            MultipartReplyBody::PortStats(stats) => {
+                assert_eq!(stats.len(), 1);
+                assert_eq!(stats[0].port_no, 3);
+                assert_eq!(stats[0].rx_packets, 1);
+                assert_eq!(stats[0].tx_bytes, 4);
+                assert_eq!(stats[0].collisions, 12);
+                assert_eq!(stats[0].duration_sec, 60);
+            }
+            _ => panic!("expected port stats"),
+        }
+
+        let mut aggregate = 10u64.to_be_bytes().to_vec();
+        aggregate.extend_from_slice(&640u64.to_be_bytes());
+        aggregate.extend_from_slice(&2u32.to_be_bytes());
+        aggregate.extend_from_slice(&[0; 4]);
+        let reply = MultipartReplyEvent::parse(&multipart_typed(2, 0, &aggregate)).unwrap();
+        match reply.body {
+            MultipartReplyBody::Aggregate(stats) => {
+                assert_eq!(stats.packet_count, 10);
+                assert_eq!(stats.byte_count, 640);
+                assert_eq!(stats.flow_count, 2);
+            }
+            _ => panic!("expected aggregate stats"),
+        }
+    }
+
+    #[test]
+    fn test_desc_and_port_desc_reply() {
+        let mut desc = Vec::new();
+        for (text, len) in [
+            ("Nicira, Inc.", 256),
+            ("Open vSwitch", 256),
+            ("3.1.0", 256),
+            ("None", 32),
+            ("s1", 256),
+        ] {
+            let mut field = text.as_bytes().to_vec();
+            field.resize(len, 0);
+            desc.append(&mut field);
+        }
+        let reply = MultipartReplyEvent::parse(&multipart_typed(0, 0, &desc)).unwrap();
+        match reply.body {
+            MultipartReplyBody::Desc(desc) => {
+                assert_eq!(desc.mfr_desc, "Nicira, Inc.");
+                assert_eq!(desc.sw_desc, "3.1.0");
+                assert_eq!(desc.serial_num, "None");
+                assert_eq!(desc.dp_desc, "s1");
+            }
+            _ => panic!("expected switch description"),
+        }
+
+        let mut port = 1u32.to_be_bytes().to_vec();
+        port.extend_from_slice(&[0; 4]);
+        port.extend_from_slice(&[0x02, 0, 0, 0, 0, 0x01, 0, 0]);
+        let mut name = b"s1-eth1".to_vec();
+        name.resize(16, 0);
+        port.append(&mut name);
+        port.extend_from_slice(&0u32.to_be_bytes()); // config
+        port.extend_from_slice(&4u32.to_be_bytes()); // state live
+        port.extend_from_slice(&0x2820u32.to_be_bytes()); // 1gb fd, copper, autoneg
+        port.extend_from_slice(&[0; 12]);
+        port.extend_from_slice(&1_000_000u32.to_be_bytes());
+        port.extend_from_slice(&0u32.to_be_bytes());
+        let reply = MultipartReplyEvent::parse(&multipart_typed(13, 0, &port)).unwrap();
+        match reply.body {
+            MultipartReplyBody::PortDesc(ports) => {
+                assert_eq!(ports.len(), 1);
+                assert_eq!(ports[0].port_no, 1);
+                assert_eq!(ports[0].name, "s1-eth1");
+                assert!(ports[0].state.live && !ports[0].state.link_down);
+                assert!(ports[0].curr.rate_1gb_fd && ports[0].curr.copper);
+                assert!(ports[0].curr.autoneg && !ports[0].curr.fiber);
+                assert_eq!(u32::from(ports[0].curr.clone()), 0x2820);
+                assert_eq!(ports[0].curr_speed, 1_000_000);
+            }
+            _ => panic!("expected port description"),
+        }
+    }
+}
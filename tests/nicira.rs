@@ -0,0 +1,385 @@
+#![cfg(feature = "nicira")]
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tenjin_sdn::openflow::{
+        nicira::{
+            Conntrack, CtZone, Learn, LearnDst, LearnSpec, LearnSrc, NiciraCodec, NxAction,
+            NxMatch, NxMessage, NxmHeader, PacketIn2, NX_VENDOR_ID,
+        },
+        ofp10,
+        ofp13::{self, Action, MatchFields, MessageMarshal},
+        ExperimenterRegistry,
+    };
+
+    /// Marshals an action and checks it against the expected bytes and back
+    fn check_ofp13(action: NxAction, expected: &[u8]) {
+        let mut bytes = Vec::new();
+        action.to_ofp13().marshal(&mut bytes).unwrap();
+        assert_eq!(bytes, expected, "{:?}", action);
+
+        let parsed = match Action::parse(&mut Cursor::new(bytes)).unwrap() {
+            Action::Experimenter(raw) => {
+                assert_eq!(raw.experimenter, NX_VENDOR_ID);
+                NxAction::decode(&raw.data).unwrap()
+            }
+            other => panic!("unexpected action {:?}", other),
+        };
+        assert_eq!(parsed, action);
+    }
+
+    #[test]
+    fn test_nx_resubmit() {
+        // resubmit:3
+        check_ofp13(
+            NxAction::Resubmit {
+                in_port: 3,
+                table: None,
+            },
+            &[
+                0xff, 0xff, 0x00, 0x10, 0x00, 0x00, 0x23, 0x20, 0x00, 0x01, 0x00, 0x03, 0x00, 0x00,
+                0x00, 0x00,
+            ],
+        );
+        // resubmit(,2)
+        check_ofp13(
+            NxAction::Resubmit {
+                in_port: NxAction::RESUBMIT_IN_PORT,
+                table: Some(2),
+            },
+            &[
+                0xff, 0xff, 0x00, 0x10, 0x00, 0x00, 0x23, 0x20, 0x00, 0x0e, 0xff, 0xf8, 0x02, 0x00,
+                0x00, 0x00,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_nx_note_and_dec_ttl() {
+        // note:de.ad.be.ef.00.00, the padding is part of the note
+        check_ofp13(
+            NxAction::Note(vec![0xde, 0xad, 0xbe, 0xef, 0x00, 0x00]),
+            &[
+                0xff, 0xff, 0x00, 0x10, 0x00, 0x00, 0x23, 0x20, 0x00, 0x08, 0xde, 0xad, 0xbe, 0xef,
+                0x00, 0x00,
+            ],
+        );
+        // dec_ttl(1,2)
+        check_ofp13(
+            NxAction::DecTtl {
+                controller_ids: vec![1, 2],
+            },
+            &[
+                0xff, 0xff, 0x00, 0x18, 0x00, 0x00, 0x23, 0x20, 0x00, 0x15, 0x00, 0x02, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_nx_load_and_output_reg() {
+        // load:0x5->NXM_NX_REG0[]
+        check_ofp13(
+            NxAction::RegLoad {
+                dst: NxmHeader::reg(0),
+                ofs: 0,
+                n_bits: 32,
+                value: 5,
+            },
+            &[
+                0xff, 0xff, 0x00, 0x18, 0x00, 0x00, 0x23, 0x20, 0x00, 0x07, 0x00, 0x1f, 0x00, 0x01,
+                0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05,
+            ],
+        );
+        // load:0x1->NXM_NX_REG1[8..15]
+        check_ofp13(
+            NxAction::RegLoad {
+                dst: NxmHeader::reg(1),
+                ofs: 8,
+                n_bits: 8,
+                value: 1,
+            },
+            &[
+                0xff, 0xff, 0x00, 0x18, 0x00, 0x00, 0x23, 0x20, 0x00, 0x07, 0x02, 0x07, 0x00, 0x01,
+                0x02, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+            ],
+        );
+        // output:NXM_NX_REG0[]
+        check_ofp13(
+            NxAction::OutputReg {
+                src: NxmHeader::reg(0),
+                ofs: 0,
+                n_bits: 32,
+                max_len: 0xffff,
+            },
+            &[
+                0xff, 0xff, 0x00, 0x18, 0x00, 0x00, 0x23, 0x20, 0x00, 0x0f, 0x00, 0x1f, 0x00, 0x01,
+                0x00, 0x04, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_nx_conntrack() {
+        // ct(commit,zone=5,table=3)
+        check_ofp13(
+            NxAction::Conntrack(Conntrack {
+                flags: Conntrack::COMMIT,
+                zone: CtZone::Immediate(5),
+                recirc_table: 3,
+                ..Conntrack::new()
+            }),
+            &[
+                0xff, 0xff, 0x00, 0x18, 0x00, 0x00, 0x23, 0x20, 0x00, 0x23, 0x00, 0x01, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ],
+        );
+        // ct(zone=NXM_NX_REG0[0..15]) with a nested set-field of ct_mark
+        let nested = NxAction::Note(vec![1, 2, 3, 4, 5, 6]).to_ofp13();
+        let mut actions = Vec::new();
+        nested.marshal(&mut actions).unwrap();
+        let ct = NxAction::Conntrack(Conntrack {
+            zone: CtZone::Field {
+                src: NxmHeader::reg(0),
+                ofs: 0,
+                n_bits: 16,
+            },
+            actions,
+            ..Conntrack::new()
+        });
+        let mut bytes = Vec::new();
+        ct.to_ofp13().marshal(&mut bytes).unwrap();
+        assert_eq!(&bytes[2..4], &[0x00, 0x28]);
+        assert_eq!(&bytes[12..18], &[0x00, 0x01, 0x00, 0x04, 0x00, 0x0f]);
+        let parsed = match Action::parse(&mut Cursor::new(bytes)).unwrap() {
+            Action::Experimenter(raw) => NxAction::decode(&raw.data).unwrap(),
+            other => panic!("unexpected action {:?}", other),
+        };
+        assert_eq!(parsed, ct);
+    }
+
+    #[test]
+    fn test_nx_learn() {
+        // learn(table=10,NXM_OF_VLAN_TCI[0..11],NXM_OF_ETH_DST[]=NXM_OF_ETH_SRC[],
+        //       load:NXM_OF_IN_PORT[]->NXM_NX_REG0[0..15],output:NXM_OF_IN_PORT[])
+        let learn = NxAction::Learn(Learn::new(
+            10,
+            vec![
+                LearnSpec::match_field(NxmHeader::VLAN_TCI, NxmHeader::VLAN_TCI, 12),
+                LearnSpec::match_field(NxmHeader::ETH_DST, NxmHeader::ETH_SRC, 48),
+                LearnSpec::load_field(NxmHeader::IN_PORT, NxmHeader::reg(0), 16),
+                LearnSpec::output(NxmHeader::IN_PORT, 16),
+            ],
+        ));
+        #[rustfmt::skip]
+        let expected = [
+            0xff, 0xff, 0x00, 0x58, 0x00, 0x00, 0x23, 0x20,
+            0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00,
+            // NXM_OF_VLAN_TCI[0..11]
+            0x00, 0x0c, 0x00, 0x00, 0x08, 0x02, 0x00, 0x00,
+            0x00, 0x00, 0x08, 0x02, 0x00, 0x00,
+            // NXM_OF_ETH_DST[]=NXM_OF_ETH_SRC[]
+            0x00, 0x30, 0x00, 0x00, 0x04, 0x06, 0x00, 0x00,
+            0x00, 0x00, 0x02, 0x06, 0x00, 0x00,
+            // load:NXM_OF_IN_PORT[]->NXM_NX_REG0[0..15]
+            0x08, 0x10, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00,
+            0x00, 0x01, 0x00, 0x04, 0x00, 0x00,
+            // output:NXM_OF_IN_PORT[]
+            0x10, 0x10, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00,
+            // padding
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        check_ofp13(learn, &expected);
+
+        // immediate sources keep their value
+        let load = NxAction::Learn(Learn::new(
+            1,
+            vec![LearnSpec {
+                n_bits: 32,
+                src: LearnSrc::Immediate(vec![0, 0, 0, 10]),
+                dst: LearnDst::Load {
+                    field: NxmHeader::reg(1),
+                    ofs: 0,
+                },
+            }],
+        ));
+        let mut bytes = Vec::new();
+        load.to_ofp13().marshal(&mut bytes).unwrap();
+        let parsed = match Action::parse(&mut Cursor::new(bytes)).unwrap() {
+            Action::Experimenter(raw) => NxAction::decode(&raw.data).unwrap(),
+            other => panic!("unexpected action {:?}", other),
+        };
+        assert_eq!(parsed, load);
+    }
+
+    #[test]
+    fn test_nx_action_v1_0() {
+        // resubmit(,2) in an OpenFlow 1.0 action list
+        let action = NxAction::Resubmit {
+            in_port: NxAction::RESUBMIT_IN_PORT,
+            table: Some(2),
+        };
+        let mut bytes = Vec::new();
+        action.to_ofp10().marshal(&mut bytes);
+        assert_eq!(
+            bytes,
+            vec![
+                0xff, 0xff, 0x00, 0x10, 0x00, 0x00, 0x23, 0x20, 0x00, 0x0e, 0xff, 0xf8, 0x02, 0x00,
+                0x00, 0x00,
+            ]
+        );
+        match &ofp10::Action::parse_sequence(&mut Cursor::new(bytes))[..] {
+            [ofp10::Action::Vendor(NX_VENDOR_ID, data)] => {
+                assert_eq!(NxAction::decode(data).unwrap(), action)
+            }
+            actions => panic!("unexpected actions {:?}", actions),
+        }
+    }
+
+    #[test]
+    fn test_nx_reg_matches() {
+        let mut registry = ExperimenterRegistry::new();
+        registry.register(NiciraCodec);
+
+        // in_port=1,reg0=0x5,reg3=0x10/0xf0,ct_state=+trk
+        let mut fields = MatchFields::match_all();
+        fields.in_port = Some(1);
+        for nx in [
+            NxMatch::Reg {
+                index: 0,
+                value: 5,
+                mask: None,
+            },
+            NxMatch::Reg {
+                index: 3,
+                value: 0x10,
+                mask: Some(0xf0),
+            },
+            NxMatch::CtState {
+                value: NxMatch::CS_TRACKED,
+                mask: Some(NxMatch::CS_TRACKED),
+            },
+        ] {
+            fields.extensions.push(nx.to_oxm());
+        }
+        let mut bytes = Vec::new();
+        fields.marshal(&mut bytes).unwrap();
+        #[rustfmt::skip]
+        let expected = [
+            0x00, 0x01, 0x00, 0x2c,
+            0x80, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01,
+            0x00, 0x01, 0x00, 0x04, 0x00, 0x00, 0x00, 0x05,
+            0x00, 0x01, 0x07, 0x08, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0xf0,
+            0x00, 0x01, 0xd3, 0x08, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0x20,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(bytes, expected);
+
+        let parsed = MatchFields::parse(&mut Cursor::new(bytes)).unwrap();
+        let decoded: Vec<NxMatch> = parsed
+            .extensions
+            .iter()
+            .map(|oxm| {
+                let value = registry.decode_oxm(oxm).unwrap().unwrap();
+                value.downcast_ref::<NxMatch>().unwrap().clone()
+            })
+            .collect();
+        assert_eq!(
+            decoded[0],
+            NxMatch::Reg {
+                index: 0,
+                value: 5,
+                mask: None
+            }
+        );
+        assert_eq!(decoded.len(), 3);
+    }
+
+    #[test]
+    fn test_nx_packet_in2() {
+        // NXT_PACKET_IN2 body: packet, table_id=3, cookie=0x1234, reason=action,
+        // metadata in_port=1,reg0=0x7 and userdata 0x01.02
+        #[rustfmt::skip]
+        let body = [
+            // packet
+            0x00, 0x00, 0x00, 0x0a, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            // table_id
+            0x00, 0x03, 0x00, 0x05, 0x03, 0x00, 0x00, 0x00,
+            // cookie
+            0x00, 0x04, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0x34,
+            // reason
+            0x00, 0x05, 0x00, 0x05, 0x01, 0x00, 0x00, 0x00,
+            // metadata
+            0x00, 0x06, 0x00, 0x14,
+            0x80, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01,
+            0x00, 0x01, 0x00, 0x04, 0x00, 0x00, 0x00, 0x07,
+            0x00, 0x00, 0x00, 0x00,
+            // userdata
+            0x00, 0x07, 0x00, 0x06, 0x01, 0x02, 0x00, 0x00,
+        ];
+        let mut raw = vec![0x00, 0x00, 0x23, 0x20, 0x00, 0x00, 0x00, 0x1e];
+        raw.extend_from_slice(&body);
+        let message = ofp13::ExperimenterEvent::parse(&raw).unwrap();
+
+        let mut registry = ExperimenterRegistry::new();
+        registry.register(NiciraCodec);
+        let value = registry
+            .decode_message(message.experimenter, message.exp_type, &message.data)
+            .unwrap()
+            .unwrap();
+        let packet_in = match value.downcast_ref::<NxMessage>() {
+            Some(NxMessage::PacketIn2(packet_in)) => packet_in.clone(),
+            other => panic!("unexpected message {:?}", other),
+        };
+        assert_eq!(
+            packet_in,
+            PacketIn2 {
+                packet: vec![0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff],
+                table_id: Some(3),
+                cookie: Some(0x1234),
+                reason: Some(1),
+                metadata: body[52..68].to_vec(),
+                userdata: Some(vec![1, 2]),
+                ..PacketIn2::default()
+            }
+        );
+        let metadata = packet_in.metadata_fields().unwrap();
+        assert_eq!(metadata.in_port, Some(1));
+        assert_eq!(
+            NxMatch::from_oxm(&metadata.extensions[0]).unwrap(),
+            NxMatch::Reg {
+                index: 0,
+                value: 7,
+                mask: None
+            }
+        );
+
+        // encoding gives back the same body
+        let mut bytes = Vec::new();
+        NxMessage::PacketIn2(packet_in)
+            .to_ofp13()
+            .marshal(&mut bytes);
+        assert_eq!(bytes, raw);
+    }
+
+    #[test]
+    fn test_nx_set_packet_in_format() {
+        let message = NxMessage::SetPacketInFormat(NxMessage::FORMAT_NXT_PACKET_IN2);
+        let mut bytes = Vec::new();
+        message.to_ofp13().marshal(&mut bytes);
+        assert_eq!(
+            bytes,
+            vec![0x00, 0x00, 0x23, 0x20, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0x02]
+        );
+
+        // OpenFlow 1.0 carries the subtype at the start of the vendor body
+        let vendor = message.to_ofp10();
+        let (exp_type, data) = vendor.split_type().unwrap();
+        assert_eq!(NxMessage::decode(exp_type, data).unwrap(), message);
+    }
+}
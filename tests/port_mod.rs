@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use tenjin_sdn::{
+        openflow::{
+            ofp10::{self, MessageMarshal as _},
+            ofp13::{MessageMarshal, PortConfig, PortFeatures, PortModEvent},
+        },
+        utils::MacAddr,
+    };
+
+    #[test]
+    fn test_port_mod_v1_3() {
+        let mac = MacAddr::new([0, 1, 2, 3, 4, 5]);
+        let port_mod = PortModEvent::new(3, mac)
+            .port_down(true)
+            .no_packet_in(false);
+        let mut bytes = Vec::new();
+        port_mod.marshal(&mut bytes);
+
+        assert_eq!(bytes.len(), port_mod.size_of());
+        assert_eq!(&bytes[..4], &[0, 0, 0, 3]);
+        assert_eq!(&bytes[8..14], &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(&bytes[16..20], &[0, 0, 0, 1]); // config
+        assert_eq!(&bytes[20..24], &[0, 0, 0, 0x41]); // mask
+        assert_eq!(&bytes[24..28], &[0, 0, 0, 0]); // advertise unchanged
+
+        let config = PortConfig::from(0x65);
+        assert!(config.port_down && config.no_recv && config.no_fwd && config.no_packet_in);
+        assert_eq!(u32::from(config), 0x65);
+
+        let features = PortFeatures {
+            rate_1gb_fd: true,
+            copper: true,
+            ..Default::default()
+        };
+        let mut bytes = Vec::new();
+        PortModEvent::new(1, mac)
+            .advertise(features)
+            .marshal(&mut bytes);
+        assert_eq!(&bytes[24..28], &[0, 0, 0x08, 0x20]);
+    }
+
+    #[test]
+    fn test_port_mod_v1_0() {
+        let mac = MacAddr::new([0, 1, 2, 3, 4, 5]);
+        let port_mod = ofp10::PortModEvent::new(2, mac).no_flood(true);
+        let mut bytes = Vec::new();
+        port_mod.marshal(&mut bytes);
+
+        assert_eq!(bytes.len(), port_mod.size_of());
+        assert_eq!(&bytes[..8], &[0, 2, 0, 1, 2, 3, 4, 5]);
+        assert_eq!(&bytes[8..12], &[0, 0, 0, 0x10]); // config
+        assert_eq!(&bytes[12..16], &[0, 0, 0, 0x10]); // mask
+    }
+}
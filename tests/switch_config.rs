@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use tenjin_sdn::openflow::{
+        ofp10::{self, MessageMarshal as _, OfpMsgEvent as _},
+        ofp13::{events::FragHandling, MessageMarshal, Msg, OfpMsgEvent, Openflow13, SwitchConfig},
+    };
+
+    #[test]
+    fn test_switch_config_v1_3() {
+        let ofp = Openflow13::new();
+        assert_eq!(ofp.get_config_req().msg_usize(), 7);
+        assert!(matches!(Msg::from(8), Msg::ConfigReply));
+        assert!(matches!(Msg::from(23), Msg::QueueGetConfigReply));
+
+        let config = SwitchConfig::new(FragHandling::Drop, SwitchConfig::NO_BUFFER);
+        let set_config = ofp.set_config(config.clone());
+        let mut bytes = Vec::new();
+        set_config.marshal(&mut bytes);
+        assert_eq!(set_config.msg_usize(), 9);
+        assert_eq!(bytes, vec![0, 1, 0xff, 0xff]);
+        assert_eq!(SwitchConfig::parse(&bytes).unwrap(), config);
+        assert!(SwitchConfig::parse(&[0, 3, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_switch_config_v1_0() {
+        let ofp = ofp10::Openflow10::new();
+        let config = ofp10::SwitchConfig::new(ofp10::events::FragHandling::Reassemble, 1500);
+        let set_config = ofp.set_config(config);
+        let mut bytes = Vec::new();
+        set_config.marshal(&mut bytes);
+        assert_eq!(set_config.msg_usize(), 9);
+        assert_eq!(bytes, vec![0, 2, 0x05, 0xdc]);
+        assert_eq!(
+            ofp10::SwitchConfig::parse(&bytes).unwrap().miss_send_len,
+            1500
+        );
+    }
+}
@@ -0,0 +1,375 @@
+#[cfg(test)]
+mod tests {
+    use std::{io::ErrorKind, time::Duration};
+    use tenjin_sdn::openflow::ofp13::{
+        events::{ControllerRole, PortStatusReason},
+        Datapath, DatapathRegistry, EchoRequestEvent, FeaturesReplyEvent, PortStatusEvent,
+        SwitchHandle, TableFeatures,
+    };
+    use tenjin_sdn::openflow::ofp13::{FlowModEvent, InstructionSet, MatchFields, MessageMarshal};
+    use tenjin_sdn::openflow::RequestError;
+    use tenjin_sdn::openflow::{
+        ofp13::{tcp_listener_handler, ControllerFrame13, PacketInEvent},
+        MessageFramer,
+    };
+    use tokio::{
+        io::AsyncWriteExt,
+        net::TcpStream,
+        sync::mpsc::{channel, Sender},
+    };
+
+    #[tokio::test]
+    async fn test_switch_handle_send_msg_v1_3() {
+        let (sender, mut receiver) = channel(8);
+        let switch = SwitchHandle::new(
+            "127.0.0.1:6653".parse().unwrap(),
+            4,
+            sender,
+            DatapathRegistry::new(),
+        );
+        let cloned = switch.clone();
+
+        cloned
+            .send_msg(EchoRequestEvent::new(vec![0xab, 0xcd]), 9)
+            .await
+            .unwrap();
+        let bytes = receiver.recv().await.unwrap();
+        assert_eq!(bytes, vec![4, 2, 0, 10, 0, 0, 0, 9, 0xab, 0xcd]);
+
+        // a flow jumping back to its own table is refused before it is sent
+        let backwards = FlowModEvent::add_flow(10, MatchFields::match_all(), vec![], 3, None)
+            .instructions(InstructionSet::new().goto_table(3));
+        let error = switch.send_msg(backwards, 10).await.unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert!(receiver.try_recv().is_err());
+
+        assert_eq!(switch.datapath_id(), None);
+        cloned.set_datapath_id(42);
+        assert_eq!(switch.datapath_id(), Some(42));
+
+        drop(receiver);
+        assert!(!switch.is_connected());
+        assert!(switch.send_raw(vec![]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_registry_remove_only_same_connection() {
+        let registry = DatapathRegistry::new();
+        let handle = || {
+            let (sender, _) = channel(1);
+            let switch = SwitchHandle::new(
+                "127.0.0.1:6653".parse().unwrap(),
+                4,
+                sender,
+                registry.clone(),
+            );
+            switch.set_datapath_id(7);
+            switch
+        };
+        let features = FeaturesReplyEvent::parse(&vec![
+            0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 1, 0, 254, 0, 0, 0, 0, 0, 0, 0x4f, 0, 0, 0, 0,
+        ])
+        .unwrap();
+
+        let old = handle();
+        let new = handle();
+        registry.insert(Datapath::new(old.clone(), features.clone()));
+        registry.insert(Datapath::new(new.clone(), features));
+        assert_eq!(registry.datapath_ids(), vec![7]);
+
+        // the old connection closing must not drop the reconnected switch
+        assert!(old.registry().remove(&old).is_none());
+        assert!(registry.switch(7).unwrap().same_connection(&new));
+        assert!(registry.remove(&new).is_some());
+        assert!(registry.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_switch_handle_request_reply() {
+        let (sender, mut receiver) = channel(8);
+        let switch = SwitchHandle::new(
+            "127.0.0.1:6653".parse().unwrap(),
+            4,
+            sender,
+            DatapathRegistry::new(),
+        );
+
+        // answer the echo request like a switch would
+        let responder = switch.clone();
+        tokio::spawn(async move {
+            let request = receiver.recv().await.unwrap();
+            let xid = u32::from_be_bytes(request[4..8].try_into().unwrap());
+            let echo = request[8..].to_vec();
+            assert!(!responder.complete_request(0, 3, echo.clone()));
+            assert!(responder.complete_request(xid, 3, echo));
+
+            // the second request is answered with an error message
+            let request = receiver.recv().await.unwrap();
+            let xid = u32::from_be_bytes(request[4..8].try_into().unwrap());
+            responder.complete_request(xid, 1, vec![0, 1, 0, 0]);
+
+            // the third request gets a multipart reply that cannot be joined
+            let request = receiver.recv().await.unwrap();
+            let xid = u32::from_be_bytes(request[4..8].try_into().unwrap());
+            let error = responder.assemble_multipart(xid, vec![0, 1]).unwrap_err();
+            assert!(responder.fail_request(xid, error));
+        });
+
+        let reply = switch
+            .request(EchoRequestEvent::new(vec![1, 2, 3]), Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(reply.payload, vec![1, 2, 3]);
+
+        let result = switch
+            .request(EchoRequestEvent::new(vec![]), Duration::from_secs(1))
+            .await;
+        assert!(matches!(result, Err(RequestError::Switch(_))));
+
+        let result = switch
+            .request(EchoRequestEvent::new(vec![]), Duration::from_secs(1))
+            .await;
+        assert!(matches!(result, Err(RequestError::Io(_))));
+
+        // nobody answers requests on this connection
+        let (sender, _receiver) = channel(8);
+        let silent = SwitchHandle::new(
+            "127.0.0.1:6653".parse().unwrap(),
+            4,
+            sender,
+            DatapathRegistry::new(),
+        );
+        let result = silent
+            .request(EchoRequestEvent::new(vec![]), Duration::from_millis(10))
+            .await;
+        assert!(matches!(result, Err(RequestError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_port_status_updates_ports() {
+        let (sender, _) = channel(1);
+        let switch = SwitchHandle::new(
+            "127.0.0.1:6653".parse().unwrap(),
+            4,
+            sender,
+            DatapathRegistry::new(),
+        );
+        let features = FeaturesReplyEvent::parse(&vec![
+            0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 1, 0, 254, 0, 0, 0, 0, 0, 0, 0x4f, 0, 0, 0, 0,
+        ])
+        .unwrap();
+        let mut datapath = Datapath::new(switch, features);
+
+        let status = |reason: u8, state: u32| {
+            let mut payload = vec![reason, 0, 0, 0, 0, 0, 0, 0];
+            payload.extend_from_slice(&[0, 0, 0, 3, 0, 0, 0, 0]);
+            payload.extend_from_slice(&[0, 1, 2, 3, 4, 5, 0, 0]);
+            payload.extend_from_slice(b"eth3\0\0\0\0\0\0\0\0\0\0\0\0");
+            payload.extend_from_slice(&0u32.to_be_bytes());
+            payload.extend_from_slice(&state.to_be_bytes());
+            payload.extend_from_slice(&[0; 24]);
+            PortStatusEvent::parse(&payload).unwrap()
+        };
+
+        let added = status(0, 0);
+        assert_eq!(added.reason, PortStatusReason::Add);
+        assert_eq!(added.port.name, "eth3");
+        datapath.apply_port_status(&added);
+        assert!(!datapath.ports[&3].state.link_down);
+
+        let link_down = status(2, 1);
+        assert_eq!(link_down.reason, PortStatusReason::Modify);
+        datapath.apply_port_status(&link_down);
+        assert!(datapath.ports[&3].state.link_down);
+
+        datapath.apply_port_status(&status(1, 1));
+        assert!(datapath.ports.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_waits_for_barrier() {
+        let (sender, mut receiver) = channel(8);
+        let switch = SwitchHandle::new(
+            "127.0.0.1:6653".parse().unwrap(),
+            4,
+            sender,
+            DatapathRegistry::new(),
+        );
+
+        let responder = switch.clone();
+        tokio::spawn(async move {
+            // first batch: two flow mods and a barrier, all applied
+            let mut xids = Vec::new();
+            for expected in [14, 14, 20] {
+                let msg = receiver.recv().await.unwrap();
+                assert_eq!(msg[1], expected);
+                xids.push(u32::from_be_bytes(msg[4..8].try_into().unwrap()));
+            }
+            responder.complete_request(xids[2], 21, vec![]);
+
+            // second batch: the flow mod is rejected before the barrier reply
+            let flow_mod = receiver.recv().await.unwrap();
+            let barrier = receiver.recv().await.unwrap();
+            let xid = u32::from_be_bytes(flow_mod[4..8].try_into().unwrap());
+            responder.complete_request(xid, 1, vec![0, 5, 0, 0]);
+            let xid = u32::from_be_bytes(barrier[4..8].try_into().unwrap());
+            responder.complete_request(xid, 21, vec![]);
+
+            let barrier = receiver.recv().await.unwrap();
+            assert_eq!(barrier.len(), 8);
+            let xid = u32::from_be_bytes(barrier[4..8].try_into().unwrap());
+            responder.complete_request(xid, 21, vec![]);
+        });
+
+        let batch: Vec<Box<dyn MessageMarshal + Send>> = vec![
+            Box::new(FlowModEvent::delete_all()),
+            Box::new(FlowModEvent::delete_flow(MatchFields::match_all(), 0)),
+        ];
+        let wait = Duration::from_secs(1);
+        assert!(switch.send_batch(batch, wait).await.is_ok());
+
+        let result = switch.send_batch([FlowModEvent::delete_all()], wait).await;
+        assert!(matches!(result, Err(RequestError::Switch(_))));
+        assert!(switch.barrier(wait).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_slave_role_refuses_state_changes() {
+        let (sender, mut receiver) = channel(8);
+        let switch = SwitchHandle::new(
+            "127.0.0.1:6653".parse().unwrap(),
+            4,
+            sender,
+            DatapathRegistry::new(),
+        );
+        assert_eq!(switch.role(), ControllerRole::Equal);
+        assert_eq!(switch.next_generation_id(), 0);
+
+        let responder = switch.clone();
+        tokio::spawn(async move {
+            let request = receiver.recv().await.unwrap();
+            assert_eq!(request[1], 24);
+            // role slave, generation id 5
+            assert_eq!(&request[8..12], &[0, 0, 0, 3]);
+            assert_eq!(&request[16..24], &5u64.to_be_bytes());
+            let xid = u32::from_be_bytes(request[4..8].try_into().unwrap());
+            responder.complete_request(xid, 25, request[8..].to_vec());
+
+            // only the echo request reaches the switch
+            let request = receiver.recv().await.unwrap();
+            assert_eq!(request[1], 2);
+        });
+
+        let wait = Duration::from_secs(1);
+        let reply = switch
+            .request_role(ControllerRole::Slave, 5, wait)
+            .await
+            .unwrap();
+        assert_eq!(reply.role, ControllerRole::Slave);
+        assert_eq!(switch.role(), ControllerRole::Slave);
+        assert_eq!(switch.generation_id(), Some(5));
+        assert_eq!(switch.next_generation_id(), 6);
+
+        assert!(switch
+            .send_msg(FlowModEvent::delete_all(), 1)
+            .await
+            .is_err());
+        let result = switch.send_batch([FlowModEvent::delete_all()], wait).await;
+        assert!(matches!(result, Err(RequestError::Io(_))));
+        // a table features request that configures tables
+        let table = TableFeatures {
+            table_id: 0,
+            name: "classifier".to_string(),
+            metadata_match: 0,
+            metadata_write: 0,
+            config: 0,
+            max_entries: 16,
+            properties: Vec::new(),
+        };
+        let result = switch.set_table_features(vec![table], wait).await;
+        assert!(matches!(result, Err(RequestError::Io(_))));
+        // a flow mod marshaled by hand
+        let mut bytes = vec![4, 14, 0, 0, 0, 0, 0, 3];
+        FlowModEvent::delete_all().marshal(&mut bytes);
+        let len = (bytes.len() as u16).to_be_bytes();
+        bytes[2..4].copy_from_slice(&len);
+        assert!(switch.send_raw(bytes).await.is_err());
+        assert!(switch
+            .send_msg(EchoRequestEvent::new(vec![]), 2)
+            .await
+            .is_ok());
+    }
+
+    #[derive(Clone)]
+    struct EchoOnConnect {
+        replies: Option<Sender<Vec<u8>>>,
+    }
+
+    impl ControllerFrame13 for EchoOnConnect {
+        fn new() -> Self {
+            Self { replies: None }
+        }
+
+        async fn packet_in_handler(&mut self, _: u32, _: PacketInEvent, _: &SwitchHandle) {}
+
+        async fn switch_features_handler(
+            &self,
+            _: u32,
+            _: FeaturesReplyEvent,
+            switch: &SwitchHandle,
+        ) {
+            let echo = EchoRequestEvent::new(vec![0xbe, 0xef]);
+            let reply = switch.request(echo, Duration::from_secs(5)).await.unwrap();
+            if let Some(replies) = &self.replies {
+                replies.send(reply.payload).await.unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_awaited_in_callback() {
+        let address = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        let (sender, mut replies) = channel(1);
+        let controller = EchoOnConnect {
+            replies: Some(sender),
+        };
+        let listen_on = address.clone();
+        tokio::spawn(async move { tcp_listener_handler(&listen_on, &controller).await });
+
+        let mut stream = loop {
+            match TcpStream::connect(&address).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        };
+        stream.write_all(&[4, 0, 0, 8, 0, 0, 0, 1]).await.unwrap();
+
+        // Answer the features request, then the echo sent from the features callback
+        let mut framer = MessageFramer::new();
+        while let Some(message) = framer.read_message(&mut stream).await.unwrap() {
+            match message[1] {
+                5 => {
+                    let mut reply = vec![4, 6, 0, 32];
+                    reply.extend_from_slice(&message[4..8]);
+                    reply.extend_from_slice(&[0; 24]);
+                    stream.write_all(&reply).await.unwrap();
+                }
+                2 => {
+                    let mut reply = message.clone();
+                    reply[1] = 3;
+                    stream.write_all(&reply).await.unwrap();
+                    break;
+                }
+                _ => (),
+            }
+        }
+        let payload = tokio::time::timeout(Duration::from_secs(5), replies.recv())
+            .await
+            .unwrap();
+        assert_eq!(payload, Some(vec![0xbe, 0xef]));
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tenjin_sdn::openflow::ofp13::{
+        events::{
+            flow_mod::instructions::InstructType, multipart::OxmId, ActionType, MultipartReplyBody,
+            OxmMatchFields, TableFeatureProp,
+        },
+        MessageMarshal, MultipartReplyEvent, OfpMsgEvent, Openflow13, TableFeatures,
+    };
+
+    fn table() -> TableFeatures {
+        TableFeatures {
+            table_id: 0,
+            name: "classifier".to_string(),
+            metadata_match: u64::MAX,
+            metadata_write: u64::MAX,
+            config: 0,
+            max_entries: 1024,
+            properties: vec![
+                TableFeatureProp::Instructions {
+                    miss: false,
+                    ids: vec![
+                        InstructType::GotoTable.into(),
+                        InstructType::ApplyActions.into(),
+                    ],
+                },
+                TableFeatureProp::Instructions {
+                    miss: true,
+                    ids: vec![InstructType::ApplyActions.into()],
+                },
+                TableFeatureProp::NextTables {
+                    miss: false,
+                    table_ids: vec![1, 2, 3],
+                },
+                TableFeatureProp::ApplyActions {
+                    miss: false,
+                    ids: vec![ActionType::Output.into(), ActionType::PushVlan.into()],
+                },
+                TableFeatureProp::Match(vec![OxmId {
+                    class: 0x8000,
+                    field: OxmMatchFields::EthDst as u8,
+                    hasmask: true,
+                    length: 6,
+                    experimenter: None,
+                }]),
+                TableFeatureProp::Experimenter {
+                    miss: true,
+                    experimenter: 0x2320,
+                    exp_type: 1,
+                    data: vec![0xaa],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_table_features_reply() {
+        let table = table();
+        let mut bytes = vec![0, 12, 0, 0, 0, 0, 0, 0];
+        table.marshal(&mut bytes);
+        assert_eq!(bytes.len(), 8 + table.size_of());
+        assert_eq!(table.size_of() % 8, 0);
+        assert_eq!(&bytes[8..10], &(table.size_of() as u16).to_be_bytes());
+
+        let reply = MultipartReplyEvent::parse(&bytes).unwrap();
+        let tables = match reply.body {
+            MultipartReplyBody::TableFeatures(tables) => tables,
+            _ => panic!("unexpected multipart body"),
+        };
+        assert_eq!(tables, vec![table]);
+
+        let parsed = &tables[0];
+        assert_eq!(parsed.name, "classifier");
+        assert_eq!(parsed.instructions(false).len(), 2);
+        assert_eq!(parsed.instructions(true).len(), 1);
+        assert_eq!(parsed.next_tables(true), &[1, 2, 3]);
+        assert_eq!(
+            parsed.apply_actions(false)[1].kind(),
+            Some(ActionType::PushVlan)
+        );
+        assert!(parsed.write_actions(false).is_empty());
+        assert!(parsed.supports_instruction(InstructType::GotoTable));
+        assert!(!parsed.supports_instruction(InstructType::Meter));
+        assert!(parsed.supports_match(OxmMatchFields::EthDst));
+        assert!(!parsed.supports_match(OxmMatchFields::InPort));
+    }
+
+    #[test]
+    fn test_table_features_unknown_property() {
+        let mut bytes = Vec::new();
+        TableFeatures {
+            properties: Vec::new(),
+            ..table()
+        }
+        .marshal(&mut bytes);
+        bytes[1] += 8;
+        bytes.extend_from_slice(&[0, 9, 0, 4, 0, 0, 0, 0]);
+        assert!(TableFeatures::parse(&mut Cursor::new(bytes)).is_err());
+    }
+
+    #[test]
+    fn test_table_mod() {
+        let ofp = Openflow13::new();
+        assert_eq!(ofp.table_features_req().size_of(), 8);
+        let table_mod = ofp.table_mod(0xff, 3);
+        let mut bytes = Vec::new();
+        table_mod.marshal(&mut bytes);
+        assert_eq!(table_mod.msg_usize(), 17);
+        assert_eq!(bytes, vec![0xff, 0, 0, 0, 0, 0, 0, 3]);
+    }
+}